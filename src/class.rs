@@ -1,4 +1,5 @@
 use crate::fatops::ClassInfo;
+use crate::importer::ConstantItem;
 use crate::{
     field_descriptor_to_ftype, IString, Method, VariableType, ERR_SUPER_INVALID, ERR_THIS_INVALID,
 };
@@ -9,9 +10,19 @@ pub(crate) struct Class {
     ifaces: Box<[ClassInfo]>,
     fields: Vec<(IString, VariableType)>,
     static_fields: Vec<(IString, VariableType)>,
+    constants: Vec<(IString, ConstantValue)>,
     static_methods: Vec<(IString, Method)>,
     virtual_methods: Vec<(IString, Method)>,
 }
+/// Compile-time constant a static field is initialized with, taken from its `ConstantValue` attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConstantValue {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(IString),
+}
 pub fn java_class_to_cpp_class(path: &str) -> IString {
     path.replace("/", "::").into()
 }
@@ -30,6 +41,10 @@ fn java_to_cpp_paths() {
         "java_cs_lang_cs_Object"
     );
 }
+/// Finds a class with C++ name `cpp_name` among the classes being translated.
+pub(crate) fn find_class<'a>(classes: &'a [Class], cpp_name: &str) -> Option<&'a Class> {
+    classes.iter().find(|class| class.cpp_name() == cpp_name)
+}
 impl Class {
     pub(crate) fn is_interface(&self) -> bool {
         self.access.is_interface()
@@ -55,13 +70,86 @@ impl Class {
     pub(crate) fn virtual_methods(&self) -> &[(IString, Method)] {
         &self.virtual_methods
     }
+    pub(crate) fn virtual_method(&self, name: &str) -> Option<&Method> {
+        self.virtual_methods
+            .iter()
+            .find(|(method_name, _)| &**method_name == name)
+            .map(|(_, method)| method)
+    }
     pub(crate) fn fields(&self) -> &[(IString, VariableType)] {
         &self.fields
     }
     pub(crate) fn static_fields(&self) -> &[(IString, VariableType)] {
         &self.static_fields
     }
-    pub(crate) fn from_java_class(java_class: &crate::importer::ImportedJavaClass) -> Self {
+    /// Returns the compile-time constant static field `field_name` is initialized with.
+    pub(crate) fn constant_value(&self, field_name: &str) -> Option<&ConstantValue> {
+        self.constants
+            .iter()
+            .find(|(name, _)| &**name == field_name)
+            .map(|(_, value)| value)
+    }
+    /// Returns the mangled name of the static initializer(`<clinit>`) of this class, if it has one.
+    pub(crate) fn clinit(&self) -> Option<&str> {
+        let clinit = crate::mangle_method_name("<clinit>", "()V");
+        self.static_methods
+            .iter()
+            .find(|(name, _)| *name == clinit)
+            .map(|(name, _)| &**name)
+    }
+    /// Removes methods for which `keep` returns false, and returns their names.
+    pub(crate) fn retain_methods(&mut self, mut keep: impl FnMut(&str) -> bool) -> Vec<IString> {
+        let mut removed = Vec::new();
+        for methods in [&mut self.static_methods, &mut self.virtual_methods] {
+            methods.retain(|(name, _)| {
+                let retained = keep(name);
+                if !retained {
+                    removed.push(name.clone());
+                }
+                retained
+            });
+        }
+        removed
+    }
+    /// Returns classes this class refers to: its superclass and interfaces, types of its fields, and classes used by its methods.
+    pub(crate) fn referenced_classes(&self) -> Vec<ClassInfo> {
+        let mut classes = vec![ClassInfo::from_java_path(&self.parrent.replace("::", "/"))];
+        classes.extend(self.ifaces.iter().cloned());
+        for (_, ftype) in self.fields.iter().chain(self.static_fields.iter()) {
+            crate::fatops::push_vtype_class(ftype, &mut classes);
+        }
+        for (_, method) in self.static_methods.iter().chain(self.virtual_methods.iter()) {
+            for vtype in method.args().iter().chain(std::iter::once(method.ret_val())) {
+                crate::fatops::push_vtype_class(vtype, &mut classes);
+            }
+            for op in method.ops() {
+                op.referenced_classes(&mut classes);
+            }
+            classes.extend(method.exceptions().iter().filter_map(|handler| handler.catch_type.clone()));
+        }
+        classes
+    }
+    /// Creates a class named `name`, extending `parrent` and implementing `ifaces`, with `methods` and no fields, for tests which need
+    /// no class file.
+    #[cfg(test)]
+    pub(crate) fn from_methods(name: &str, parrent: &str, ifaces: &[&str], methods: Vec<Method>) -> Self {
+        let (virtual_methods, static_methods) = methods
+            .into_iter()
+            .map(|method| (IString::from(method.name()), method))
+            .partition(|(_, method)| method.is_virtual());
+        Class {
+            name: java_class_to_cpp_class(name),
+            access: crate::importer::AccessFlags::read(&mut &[0, 0][..]).unwrap(),
+            parrent: java_class_to_cpp_class(parrent),
+            ifaces: ifaces.iter().map(|iface| ClassInfo::from_java_path(iface)).collect(),
+            fields: Vec::new(),
+            static_fields: Vec::new(),
+            constants: Vec::new(),
+            static_methods,
+            virtual_methods,
+        }
+    }
+    pub(crate) fn from_java_class(java_class: &crate::importer::ImportedJavaClass) -> std::io::Result<Self> {
         let name = match java_class.lookup_class(java_class.this_class()) {
             Some(name) => name,
             None => {
@@ -86,11 +174,27 @@ impl Class {
             Vec::with_capacity(java_class.fields().len());
         let mut static_fields: Vec<(IString, VariableType)> =
             Vec::with_capacity(java_class.fields().len());
+        let mut constants: Vec<(IString, ConstantValue)> = Vec::new();
         for field in java_class.fields() {
             let (name_index, descriptor_index) = (field.name_index, field.descriptor_index);
             let name = java_class.lookup_utf8(name_index).unwrap();
             let ftype = field_descriptor_to_ftype(descriptor_index, java_class);
             if field.flags.is_static() {
+                let constant = field.constant_value().map(|value_index| {
+                    match java_class.lookup_item(value_index) {
+                        Some(ConstantItem::Intiger(value)) => ConstantValue::Int(*value),
+                        Some(ConstantItem::Long(value)) => ConstantValue::Long(*value),
+                        Some(ConstantItem::Float(value)) => ConstantValue::Float(*value),
+                        Some(ConstantItem::Double(value)) => ConstantValue::Double(*value),
+                        Some(ConstantItem::ConstString { string_index }) => ConstantValue::String(
+                            java_class.lookup_utf8(*string_index).unwrap().into(),
+                        ),
+                        item => panic!("Invalid `ConstantValue` {item:?} of field {name}!"),
+                    }
+                });
+                if let Some(constant) = constant {
+                    constants.push((name.into(), constant));
+                }
                 static_fields.push((name.into(), ftype));
             } else {
                 fields.push((name.into(), ftype));
@@ -106,14 +210,14 @@ impl Class {
                     method.name(java_class),
                     method.descriptor(java_class),
                 );
-                let method = Method::from_raw_method(method, &mangled_name, java_class);
+                let method = Method::from_raw_method(method, &mangled_name, java_class)?;
                 virtual_methods.push((mangled_name.into(), method));
             } else {
                 let mangled_name = crate::mangle_method_name(
                     method.name(java_class),
                     method.descriptor(java_class),
                 );
-                let method = Method::from_raw_method(method, &mangled_name, java_class);
+                let method = Method::from_raw_method(method, &mangled_name, java_class)?;
                 static_methods.push((mangled_name.into(), method));
             }
         }
@@ -123,15 +227,16 @@ impl Class {
             ifaces.push(class_info);
         }
         let access = *java_class.flags();
-        Class {
+        Ok(Class {
             access,
             name: class_name,
             parrent,
             fields,
             ifaces: ifaces.into(),
             static_fields,
+            constants,
             static_methods,
             virtual_methods,
-        }
+        })
     }
 }
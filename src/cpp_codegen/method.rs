@@ -1,31 +1,39 @@
 use crate::{fatops::FatOp, ClassInfo, IString, VariableType};
 use std::io::Write;
-use crate::fatops::DynamicMethodHandle;
+use super::{constant_initializer, escape_cpp_string, CodegenOptions, SourceWriter};
+use crate::class::ConstantValue;
+use crate::fatops::{ConcatPart, DynamicMethodHandle, ExceptionHandler};
+use crate::type_inference::handler_catch_type;
 pub(crate) use super::method_writer::{MethodWriter,LocalKind};
+use super::method_writer::object;
 enum BasicBlock {
     Raw { ops: Box<[FatOp]>, starts: usize },
     //Scope(Box<[BasicBlock]>),
 }
 macro_rules! load_impl {
     ($mw:ident,$index:ident,$kind:expr,$vtype:expr) => {{
-        let local = $mw.get_local(*$index, $kind);
-        $mw.vstack_push(&local.0, local.1);
+        $mw.push_local(*$index, $kind);
         "".into()
     }};
 }
 macro_rules! store_impl {
     ($mw:ident,$index:ident,$kind:expr) => {{
         let (vtype, value): (VariableType, IString) = $mw.vstack_pop().unwrap();
-        let (local,_): (IString,_) = $mw.ensure_local_exists(*$index, $kind, vtype);
+        let (local, local_type): (IString, _) = $mw.ensure_local_exists(*$index, $kind);
+        $mw.materialize_reads_of(&local);
+        let value = $mw.convert_reference(&value, &vtype, &local_type);
         format!("{local} = {value};")
     }};
 }
 macro_rules! get_field_impl {
     ($mw:ident,$field_name:ident,$vartype:expr) => {{
         let field_owner = $mw.vstack_pop().unwrap();
+        let field_owner = $mw.null_checked(
+            &field_owner.1,
+            &format!("Cannot read field \"{field_name}\"", field_name = $field_name),
+        );
         let im_name = $mw.get_intermidiate();
         $mw.vstack_push(&im_name, $vartype);
-        let field_owner = field_owner.1;
         format!(
             "{ctype} {im_name} = {field_owner}->{field_name};",
             field_name = $field_name,
@@ -40,7 +48,10 @@ macro_rules! set_field_impl {
         if !valtype.is_unknown() {
             //assert_eq!(valtype, $vartype);
         }
-        let field_owner = field_owner.1;
+        let field_owner = $mw.null_checked(
+            &field_owner.1,
+            &format!("Cannot assign field \"{field_name}\"", field_name = $field_name),
+        );
         format!(
             "{field_owner}->{field_name} = {value};",
             field_name = $field_name,
@@ -50,7 +61,10 @@ macro_rules! set_field_impl {
         let (valtype, value) = $mw.vstack_pop().unwrap();
         let field_owner = $mw.vstack_pop().unwrap();
         assert!($vartype.assignable(&valtype));
-        let field_owner = field_owner.1;
+        let field_owner = $mw.null_checked(
+            &field_owner.1,
+            &format!("Cannot assign field \"{field_name}\"", field_name = $field_name),
+        );
         format!(
             "{field_owner}->{field_name} = ({ctype}){value};",
             field_name = $field_name,
@@ -61,7 +75,10 @@ macro_rules! set_field_impl {
         let (_valtype, value) = $mw.vstack_pop().unwrap();
         let field_owner = $mw.vstack_pop().unwrap();
         //assert_eq!(valtype,$vartype);
-        let field_owner = field_owner.1;
+        let field_owner = $mw.null_checked(
+            &field_owner.1,
+            &format!("Cannot assign field \"{field_name}\"", field_name = $field_name),
+        );
         format!(
             "{field_owner}->{field_name} = {value};",
             field_name = $field_name,
@@ -77,7 +94,7 @@ macro_rules! get_static_impl {
         }
         $mw.add_include(&*$field_owner.class_path());
         format!(
-            "{ctype} {im_name} = {field_owner}::{static_name};",
+            "{field_owner}::_ensure_clinit_();{ctype} {im_name} = {field_owner}::{static_name};",
             static_name = $static_name,
             ctype = $vartype.c_type(),
             field_owner = $field_owner.cpp_class()
@@ -89,7 +106,7 @@ macro_rules! set_static_impl {
         let (_, value) = $mw.vstack_pop().unwrap();
         //debug_assert_eq!(vtype, $vartype);
         format!(
-            "{field_owner}::{static_name} = {value};",
+            "{field_owner}::_ensure_clinit_();{field_owner}::{static_name} = {value};",
             static_name = $static_name,
             field_owner = $field_owner.cpp_class()
         )
@@ -112,6 +129,65 @@ macro_rules! arthm_impl {
         )
     }};
 }
+/// Calls the stdlib helper `$func`, which implements a JVM integer operation on values of type `$vartype`.
+macro_rules! java_arthm_impl {
+    ($mw:ident,$vartype:expr,$func:literal) => {{
+        let (_btype, b) = $mw.vstack_pop().unwrap();
+        let (atype, a) = $mw.vstack_pop().unwrap();
+        assert!($vartype.assignable(&atype));
+        let im_name = $mw.get_intermidiate();
+        $mw.vstack_push(&im_name, $vartype);
+        format!(
+            concat!("{ctype} {im} = ", $func, "<{ctype}>({a},{b});"),
+            ctype = $vartype.c_type(),
+            im = im_name,
+            a = a,
+            b = b
+        )
+    }};
+}
+/// Shifts a `long` by an `int` distance, using the stdlib helper `$func`.
+macro_rules! shift_impl {
+    ($mw:ident,$func:literal) => {{
+        let (_btype, b) = $mw.vstack_pop().unwrap();
+        let (atype, a) = $mw.vstack_pop().unwrap();
+        debug_assert_eq!(atype, VariableType::Long);
+        let im_name = $mw.get_intermidiate();
+        $mw.vstack_push(&im_name, VariableType::Long);
+        format!(
+            concat!("int64_t {im} = ", $func, "<int64_t>({a},{b});"),
+            im = im_name,
+            a = a,
+            b = b
+        )
+    }};
+}
+macro_rules! neg_impl {
+    ($mw:ident,$vartype:expr,$func:literal) => {{
+        let (atype, a) = $mw.vstack_pop().unwrap();
+        debug_assert_eq!(atype, $vartype);
+        let im = $mw.get_intermidiate();
+        $mw.vstack_push(&im, $vartype);
+        format!(
+            concat!("{ctype} {im} = ", $func, ";"),
+            ctype = $vartype.c_type(),
+            im = im,
+            a = a
+        )
+    }};
+}
+macro_rules! fp_to_int_impl {
+    ($mw:ident,$src_type:expr,$target:expr) => {{
+        let (src, val) = $mw.vstack_pop().unwrap();
+        assert_eq!(src, $src_type);
+        let im_name = $mw.get_intermidiate();
+        $mw.vstack_push(&im_name, $target);
+        format!(
+            "{target} {im_name} = java_fp_to_int<{target}>({val});",
+            target = $target.c_type()
+        )
+    }};
+}
 macro_rules! convert_impl {
     ($mw:ident,$src_type:expr,$target:expr) => {{
         let (src, val) = $mw.vstack_pop().unwrap();
@@ -124,30 +200,296 @@ macro_rules! convert_impl {
         )
     }};
 }
-fn args_to_clist(args:&[VariableType])->IString{
-    let mut arg_iter = args.iter();
-    let mut res = String::with_capacity(args.len() * 8);
-    if let Some(arg) = arg_iter.next(){
-        res.push_str(&arg.c_type());
-    }
-    for arg in arg_iter{
-        res.push(',');
-        res.push_str(&arg.c_type());
+/// Returns the class boxing values of primitive type `vtype`, the descriptor of `vtype`, and the name of the method unboxing them.
+fn box_class(vtype: &VariableType) -> Option<(ClassInfo, &'static str, &'static str)> {
+    let (class, desc, unbox) = match vtype {
+        VariableType::Bool => ("java/lang/Boolean", "Z", "booleanValue"),
+        VariableType::Byte => ("java/lang/Byte", "B", "byteValue"),
+        VariableType::Char => ("java/lang/Character", "C", "charValue"),
+        VariableType::Short => ("java/lang/Short", "S", "shortValue"),
+        VariableType::Int => ("java/lang/Integer", "I", "intValue"),
+        VariableType::Long => ("java/lang/Long", "J", "longValue"),
+        VariableType::Float => ("java/lang/Float", "F", "floatValue"),
+        VariableType::Double => ("java/lang/Double", "D", "doubleValue"),
+        _ => return None,
+    };
+    Some((ClassInfo::from_java_path(class), desc, unbox))
+}
+/// Returns the primitive type boxed by instances of `class_info`, if it is a box class.
+fn boxed_type(class_info: &ClassInfo) -> Option<VariableType> {
+    [
+        VariableType::Bool,
+        VariableType::Byte,
+        VariableType::Char,
+        VariableType::Short,
+        VariableType::Int,
+        VariableType::Long,
+        VariableType::Float,
+        VariableType::Double,
+    ]
+    .into_iter()
+    .find(|vtype| box_class(vtype).is_some_and(|(class, _, _)| class.cpp_class() == class_info.cpp_class()))
+}
+/// Converts `value` of type `src` to `target`, as needed when passing values between a functional interface method and its implementation.
+/// Primitive values passed as references are boxed with `valueOf`, and references passed as primitives are unboxed with `xxxValue`.
+fn adapt_value(mw: &mut MethodWriter, value: &str, src: &VariableType, target: &VariableType) -> IString {
+    let is_ref = |vtype: &VariableType| matches!(vtype, VariableType::ObjectRef(_) | VariableType::ArrayRef(_));
+    if src == target {
+        value.into()
+    } else if is_ref(src) && is_ref(target) {
+        format!("managed_cast<{pointee}>({value})", pointee = pointee_type(target)).into()
+    } else if is_ref(target) {
+        let (class, desc, _) = box_class(src).unwrap_or_else(|| panic!("Can't box a value of type {src:?}!"));
+        mw.add_include(&class.class_path());
+        let value_of = crate::mangle_method_name("valueOf", &format!("({desc})L{};", class.java_path()));
+        let boxed = format!("{class}::{value_of}({value})", class = class.cpp_class());
+        adapt_value(mw, &boxed, &VariableType::ObjectRef(class), target)
+    } else if is_ref(src) {
+        // Boxes of other primitive types(e.g. `Integer` passed as `long`) are unboxed to their own type and converted.
+        let (boxed, value) = match src {
+            VariableType::ObjectRef(class) if boxed_type(class).is_some() => (boxed_type(class).unwrap(), IString::from(value)),
+            _ => {
+                let (class, _, _) = box_class(target).unwrap_or_else(|| panic!("Can't unbox a value to type {target:?}!"));
+                mw.add_include(&class.class_path());
+                mw.add_include("java_cs_lang_cs_ClassCastException");
+                let value = format!(
+                    "checked_cast<{class}>({value},\"{java_name}\")",
+                    class = class.cpp_class(),
+                    java_name = class.java_path().replace('/', ".")
+                );
+                (target.clone(), value.into())
+            }
+        };
+        let (class, desc, unbox) = box_class(&boxed).unwrap();
+        mw.add_include(&class.class_path());
+        let action = format!("Cannot invoke \"{}.{unbox}()\"", class.java_path().replace('/', "."));
+        let value = mw.null_checked(&value, &action);
+        let unboxed = format!("{value}->{unbox}()", unbox = crate::mangle_method_name(unbox, &format!("(){desc}")));
+        adapt_value(mw, &unboxed, &boxed, target)
+    } else {
+        format!("({ctype})({value})", ctype = target.c_type()).into()
     }
-    res.into()
 }
-fn impl_lambda(mw:&mut MethodWriter,name:&str,args:&[VariableType],ret:&VariableType)->String{
-    let lambda_name = format!("LAMBDA_CLASS_{name}");
-    let random = 0;
-    let lambda_impl_name = format!("{lambda_name}_IMPL_{random}");
-    if let Some(dep) = ret.dependency(){
+/// Converts `values` to argument types `args` of a method, and joins them into an argument list.
+fn adapt_args(mw: &mut MethodWriter, values: &[(IString, VariableType)], args: &[VariableType]) -> IString {
+    assert_eq!(values.len(), args.len(), "Lambda argument count does not match its implementation!");
+    let values: Vec<IString> = values
+        .iter()
+        .zip(args.iter())
+        .map(|((value, vtype), arg)| adapt_value(mw, value, vtype, arg))
+        .collect();
+    values.join(",").into()
+}
+/// Writes local class `closure`, implementing `method` of `interface` by calling `target`. Values captured by the lambda are stored in fields of `closure`.
+#[allow(clippy::too_many_arguments)]
+fn write_closure(
+    mw: &mut MethodWriter,
+    closure: &str,
+    interface: &ClassInfo,
+    method: &str,
+    args: &[VariableType],
+    ret: &VariableType,
+    captured: &[VariableType],
+    target: &DynamicMethodHandle,
+) {
+    for dep in captured.iter().chain(args.iter()).chain(std::iter::once(ret)).filter_map(VariableType::dependency) {
         mw.add_include(&dep);
+    }
+    mw.write_raw(&format!("struct {closure}: public {iface}{{", iface = interface.cpp_class()));
+    let mut values: Vec<(IString, VariableType)> = Vec::with_capacity(captured.len() + args.len());
+    let mut ctor_args = Vec::with_capacity(captured.len());
+    let mut ctor_inits = Vec::with_capacity(captured.len());
+    for (index, vtype) in captured.iter().enumerate() {
+        mw.write_raw(&format!("\t{ctype} c{index};", ctype = vtype.c_type()));
+        ctor_args.push(format!("{ctype} c{index}", ctype = vtype.c_type()));
+        ctor_inits.push(format!("c{index}(c{index})"));
+        values.push((format!("c{index}").into(), vtype.clone()));
+    }
+    let ctor_inits = if ctor_inits.is_empty() {
+        String::new()
+    } else {
+        format!(":{}", ctor_inits.join(","))
+    };
+    mw.write_raw(&format!("\t{closure}({ctor_args}){ctor_inits}{{}}", ctor_args = ctor_args.join(",")));
+    let mut method_args = Vec::with_capacity(args.len());
+    for (index, vtype) in args.iter().enumerate() {
+        method_args.push(format!("{ctype} a{index}", ctype = vtype.c_type()));
+        values.push((format!("a{index}").into(), vtype.clone()));
+    }
+    mw.write_raw(&format!(
+        "\tvirtual {ret_ctype} {method}({method_args}){{",
+        ret_ctype = ret.c_type(),
+        method_args = method_args.join(",")
+    ));
+    let (call, call_ret) = match target {
+        DynamicMethodHandle::InvokeStatic(class_info, name, target_args, target_ret) => {
+            mw.add_include(&class_info.class_path());
+            mw.write_raw(&format!("\t\t{class}::_ensure_clinit_();", class = class_info.cpp_class()));
+            (
+                format!("{class}::{name}({args})", class = class_info.cpp_class(), args = adapt_args(mw, &values, target_args)),
+                target_ret.clone(),
+            )
+        }
+        DynamicMethodHandle::InvokeVirtual(class_info, name, target_args, target_ret)
+        | DynamicMethodHandle::InvokeInterface(class_info, name, target_args, target_ret) => {
+            mw.add_include(&class_info.class_path());
+            let (objref, objref_type) = &values[0];
+            let objref = adapt_value(mw, objref, objref_type, &VariableType::ObjectRef(class_info.clone()));
+            (
+                format!("{objref}->{name}({args})", args = adapt_args(mw, &values[1..], target_args)),
+                target_ret.clone(),
+            )
+        }
+        DynamicMethodHandle::InvokeSpecial(class_info, name, target_args, target_ret) => {
+            mw.add_include(&class_info.class_path());
+            let (objref, objref_type) = &values[0];
+            let objref = adapt_value(mw, objref, objref_type, &VariableType::ObjectRef(class_info.clone()));
+            (
+                format!(
+                    "{objref}->{class}::{name}({args})",
+                    class = class_info.cpp_class(),
+                    args = adapt_args(mw, &values[1..], target_args)
+                ),
+                target_ret.clone(),
+            )
+        }
+        DynamicMethodHandle::NewInvokeSpecial(class_info, name, target_args) => {
+            mw.add_include(&class_info.class_path());
+            let class = class_info.cpp_class();
+            mw.write_raw(&format!("\t\t{class}::_ensure_clinit_();"));
+            mw.write_raw(&format!("\t\tManagedPointer<{class}> obj = new_managed({class},);"));
+            let mut ctor_args = String::from("obj");
+            if !target_args.is_empty() {
+                ctor_args.push(',');
+                ctor_args.push_str(&adapt_args(mw, &values, target_args));
+            }
+            mw.write_raw(&format!("\t\t{class}::{name}({ctor_args});"));
+            ("obj".into(), VariableType::ObjectRef(class_info.clone()))
+        }
+        _ => panic!("Field method handle {target:?} can't implement a lambda!"),
     };
-    let lambda_def = format!("struct {lambda_impl_name}:{lambda_name}{{~virtual {lambda_impl_name}();virtual {ret_ctype} {name}({args}) = this::lambda$main$0; {lambda_impl_name}();}};",
-    ret_ctype = ret.c_type(), args = args_to_clist(args));
-    let im = mw.get_intermidiate();
-    mw.vstack_push(&im, VariableType::ObjectRef(ClassInfo::from_raw(&lambda_name)));
-    format!("{lambda_def}; ManagedPointer<{lambda_name}> {im} = managed_from_raw(new {lambda_impl_name}());")
+    if *ret == VariableType::Void {
+        mw.write_raw(&format!("\t\t{call};"));
+    } else {
+        let value = adapt_value(mw, &call, &call_ret, ret);
+        mw.write_raw(&format!("\t\treturn {value};"));
+    }
+    mw.write_raw("\t}");
+    mw.write_raw("};");
+}
+/// Allocates array `target` of type `atype`, and, if more `lengths` are specified, fills it with allocated sub-arrays.
+fn write_multi_array(mw: &mut MethodWriter, target: &str, atype: &VariableType, lengths: &[IString]) {
+    let element = match atype {
+        VariableType::ArrayRef(element) => element,
+        _ => panic!("Multi dimensional array type {atype:?} is not an array!"),
+    };
+    mw.write_raw(&format!(
+        "{target} = managed_from_raw(RuntimeArray<{ctype}>::New({length}{array_class}));",
+        ctype = element.array_element_c_type(),
+        length = lengths[0],
+        array_class = array_class_arg(atype)
+    ));
+    if lengths.len() > 1 {
+        let index = mw.get_intermidiate();
+        let sub_array = mw.get_intermidiate();
+        mw.write_raw(&format!(
+            "for(int {index} = 0; {index} < {length}; {index}++)",
+            length = lengths[0]
+        ));
+        mw.begin_scope();
+        mw.write_raw(&format!("{ctype} {sub_array};", ctype = element.c_type()));
+        write_multi_array(mw, &sub_array, element, &lengths[1..]);
+        mw.write_raw(&format!("{target}->Set({index},{sub_array});"));
+        mw.end_scope();
+    }
+}
+/// Returns the C++ type a `ManagedPointer` to a reference of type `vtype` points to.
+pub(super) fn pointee_type(vtype: &VariableType) -> IString {
+    match vtype {
+        VariableType::ObjectRef(class_info) => class_info.cpp_class().into(),
+        VariableType::ArrayRef(element) => {
+            format!("RuntimeArray<{}>", element.array_element_c_type()).into()
+        }
+        _ => panic!("{vtype:?} is not a reference type!"),
+    }
+}
+/// Returns C++ code evaluating to the runtime type(`ArrayClass`) of arrays of type `atype`.
+fn array_class(atype: &VariableType) -> IString {
+    let mut dimensions = 0;
+    let mut element = atype;
+    while let VariableType::ArrayRef(inner) = element {
+        dimensions += 1;
+        element = inner;
+    }
+    let element = match element {
+        VariableType::ObjectRef(class_info) => class_info.cpp_class().into(),
+        primitive => primitive.c_type(),
+    };
+    format!("array_class_of<{element},{dimensions}>()").into()
+}
+/// Returns the argument of `RuntimeArray::New` specifying the runtime type of arrays of type `atype`. Arrays of primitives need none.
+fn array_class_arg(atype: &VariableType) -> String {
+    match atype {
+        VariableType::ArrayRef(element) if element.is_reference() => {
+            format!(",{}", array_class(atype))
+        }
+        _ => String::new(),
+    }
+}
+/// Returns the name of the `RuntimeArray` method `accessor`(`Get` or `Set`), or of its variant skipping bounds checks if they are disabled.
+fn array_accessor(mw: &MethodWriter, accessor: &str) -> IString {
+    if mw.options().bounds_checks {
+        accessor.into()
+    } else {
+        format!("{accessor}Unchecked").into()
+    }
+}
+/// Returns `arr_ref` checked against null, as an array. Receivers not statically known to be arrays(e.g. locals only
+/// ever assigned `null`) are cast to arrays of `element`, the type of elements the op accesses.
+fn array_receiver(
+    mw: &mut MethodWriter,
+    arr_ref: &str,
+    arr_ref_type: &VariableType,
+    element: VariableType,
+    action: &str,
+) -> IString {
+    if arr_ref_type.is_array() {
+        return mw.null_checked(arr_ref, action);
+    }
+    let array = VariableType::ArrayRef(Box::new(element));
+    let arr_ref = if arr_ref_type.is_unknown() {
+        format!(
+            "interface_cast<{pointee}>((ManagedPointer<java::lang::Object>){arr_ref})",
+            pointee = pointee_type(&array)
+        )
+        .into()
+    } else {
+        mw.convert_reference(arr_ref, arr_ref_type, &array)
+    };
+    mw.null_checked(&arr_ref, action)
+}
+/// Describes a call of a method of `class_info`, in messages of `java.lang.NullPointerException`.
+fn invoke_action(class_info: &ClassInfo) -> String {
+    format!(
+        "Cannot invoke a method of {class}",
+        class = class_info.cpp_class().replace("::", ".")
+    )
+}
+/// Returns the name of `vtype` as it would be written in Java source, used in exception messages.
+fn java_type_name(vtype: &VariableType) -> IString {
+    match vtype {
+        VariableType::Void => "void".into(),
+        VariableType::Bool => "boolean".into(),
+        VariableType::Byte => "byte".into(),
+        VariableType::Char => "char".into(),
+        VariableType::Short => "short".into(),
+        VariableType::Int => "int".into(),
+        VariableType::Long => "long".into(),
+        VariableType::Float => "float".into(),
+        VariableType::Double => "double".into(),
+        VariableType::ObjectRef(class_info) => class_info.cpp_class().replace("::", ".").into(),
+        VariableType::ArrayRef(element) => format!("{}[]", java_type_name(element)).into(),
+    }
 }
 macro_rules! conditional_impl {
     ($mw:ident,$cmp:literal,$target:ident) => {{
@@ -183,20 +525,20 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             LocalKind::ObjectRef,
             VariableType::ObjectRef(ClassInfo::unknown())
         ),
-        FatOp::DLoad(index) => load_impl!(mw, index, LocalKind::Float, VariableType::Double),
+        FatOp::DLoad(index) => load_impl!(mw, index, LocalKind::Double, VariableType::Double),
         FatOp::FLoad(index) => load_impl!(mw, index, LocalKind::Float, VariableType::Float),
         FatOp::ILoad(index) => load_impl!(mw, index, LocalKind::Int, VariableType::Int),
-        FatOp::LLoad(index) => load_impl!(mw, index, LocalKind::Int, VariableType::Long),
+        FatOp::LLoad(index) => load_impl!(mw, index, LocalKind::Long, VariableType::Long),
         FatOp::AConstNull => {
             mw.vstack_push("nullptr", VariableType::ObjectRef(ClassInfo::unknown()));
             "".into()
         }
         FatOp::FConst(value) => {
-            mw.vstack_push(&format!("{value:.16}f"), VariableType::Float);
+            mw.vstack_push(&constant_initializer(&ConstantValue::Float(*value)), VariableType::Float);
             "".into()
         }
         FatOp::DConst(value) => {
-            mw.vstack_push(&format!("{value:.32}f"), VariableType::Double);
+            mw.vstack_push(&constant_initializer(&ConstantValue::Double(*value)), VariableType::Double);
             "".into()
         }
         FatOp::BConst(value) => {
@@ -204,11 +546,11 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             "".into()
         }
         FatOp::IConst(value) => {
-            mw.vstack_push(&format!("{value:}"), VariableType::Int);
+            mw.vstack_push(&constant_initializer(&ConstantValue::Int(*value)), VariableType::Int);
             "".into()
         }
         FatOp::LConst(value) => {
-            mw.vstack_push(&format!("{value:}"), VariableType::Long);
+            mw.vstack_push(&constant_initializer(&ConstantValue::Long(*value)), VariableType::Long);
             "".into()
         }
         FatOp::SConst(value) => {
@@ -311,87 +653,115 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             let (_value_type, value) = mw.vstack_pop().unwrap();
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            //assert!(arr_ref_type.is_array(),"arr_ref_type:{arr_ref_type:?}");
             assert_eq!(index_type, VariableType::Int);
-            format!("{arr_ref}->Set({index},{value});")
+            let element = match op {
+                FatOp::AAStore => object(),
+                FatOp::BAStore => VariableType::Byte,
+                FatOp::CAStore => VariableType::Char,
+                FatOp::DAStore => VariableType::Double,
+                FatOp::FAStore => VariableType::Float,
+                FatOp::IAStore => VariableType::Int,
+                FatOp::SAStore => VariableType::Short,
+                _ => VariableType::Long,
+            };
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, element, "Cannot store to array");
+            let set = array_accessor(mw, "Set");
+            format!("{arr_ref}->{set}({index},{value});")
         }
         FatOp::AALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, object(), "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
-            let im_name = mw.get_intermidiate();
-            let arr_member_type = if let VariableType::ArrayRef(arr_member_type) = arr_ref_type{
-                arr_member_type
-            }else{panic!()};
-            mw.vstack_push(&im_name, *arr_member_type);
-            format!("auto {im_name} = {arr_ref}->Get({index});")
+            if let VariableType::ArrayRef(arr_member_type) = arr_ref_type {
+                let im_name = mw.get_intermidiate();
+                let code = format!(
+                    "{ctype} {im_name} = element_cast<{pointee}>({arr_ref}->{get}({index}));",
+                    ctype = arr_member_type.c_type(),
+                    pointee = pointee_type(&arr_member_type)
+                );
+                mw.vstack_push(&im_name, *arr_member_type);
+                code
+            } else {
+                // Receivers not known to be arrays are always `null`, so the load throws, and its value is never used.
+                mw.vstack_push("nullptr", VariableType::unknown());
+                format!("{arr_ref}->{get}({index});")
+            }
         }
         FatOp::BALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Byte, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Byte);
-            format!("uint8_t {im_name} = (uint8_t){arr_ref}->Get({index});")
+            format!("uint8_t {im_name} = (uint8_t){arr_ref}->{get}({index});")
         }
         FatOp::CALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Char, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Char);
-            format!("char16_t {im_name} = (char16_t){arr_ref}->Get({index});")
+            format!("char16_t {im_name} = (char16_t){arr_ref}->{get}({index});")
         }
         FatOp::DALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Double, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Double);
-            format!("double {im_name} = (double){arr_ref}->Get({index});")
+            format!("double {im_name} = (double){arr_ref}->{get}({index});")
         }
         FatOp::FALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Float, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Float);
-            format!("float {im_name} = (float){arr_ref}->Get({index});")
+            format!("float {im_name} = (float){arr_ref}->{get}({index});")
         }
         FatOp::IALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Int, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Int);
-            format!("int {im_name} = (int){arr_ref}->Get({index});")
+            format!("int {im_name} = (int){arr_ref}->{get}({index});")
         }
         FatOp::LALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Long, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Long);
-            format!("long {im_name} = (long){arr_ref}->Get({index});")
+            format!("int64_t {im_name} = (int64_t){arr_ref}->{get}({index});")
         }
         FatOp::SALoad => {
             let (index_type, index) = mw.vstack_pop().unwrap();
             let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
-            assert!(arr_ref_type.is_array() || arr_ref_type.is_unknown());
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, VariableType::Short, "Cannot load from array");
+            let get = array_accessor(mw, "Get");
             assert_eq!(index_type, VariableType::Int);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Short);
-            format!("short {im_name} = (short){arr_ref}->Get({index});")
+            format!("short {im_name} = (short){arr_ref}->{get}({index});")
         }
         FatOp::ArrayLength => {
-            let (_arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
+            let (arr_ref_type, arr_ref) = mw.vstack_pop().unwrap();
+            let arr_ref = array_receiver(mw, &arr_ref, &arr_ref_type, object(), "Cannot read the array length");
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Int);
             format!("int {im_name} = {arr_ref}->GetLength();")
@@ -481,24 +851,19 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         ),
         FatOp::IInc(local, by) => {
             let (local,_) = mw.get_local(*local, LocalKind::Int);
-            format!("{local} += {by};")
+            mw.materialize_reads_of(&local);
+            format!("{local} = java_add<int32_t>({local},{by});")
         }
         FatOp::DAdd => arthm_impl!(mw, VariableType::Double, "+"),
         FatOp::DSub => arthm_impl!(mw, VariableType::Double, "-"),
         FatOp::DMul => arthm_impl!(mw, VariableType::Double, "*"),
         FatOp::DDiv => arthm_impl!(mw, VariableType::Double, "/"),
-        FatOp::DNeg => {
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Double);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Double);
-            format!("double {im} = !{a};")
-        }
+        FatOp::DNeg => neg_impl!(mw, VariableType::Double, "-{a}"),
         FatOp::DRem => {
             let (btype, b) = mw.vstack_pop().unwrap();
             let (atype, a) = mw.vstack_pop().unwrap();
             assert_eq!(atype, btype);
-            assert_eq!(atype, VariableType::Float);
+            assert_eq!(atype, VariableType::Double);
             let im_name = mw.get_intermidiate();
             mw.vstack_push(&im_name, VariableType::Double);
             format!("double {im_name} = fmod({a},{b});")
@@ -507,13 +872,7 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         FatOp::FSub => arthm_impl!(mw, VariableType::Float, "-"),
         FatOp::FMul => arthm_impl!(mw, VariableType::Float, "*"),
         FatOp::FDiv => arthm_impl!(mw, VariableType::Float, "/"),
-        FatOp::FNeg => {
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Float);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Float);
-            format!("float {im} = !{a};")
-        }
+        FatOp::FNeg => neg_impl!(mw, VariableType::Float, "-{a}"),
         FatOp::FRem => {
             let (btype, b) = mw.vstack_pop().unwrap();
             let (atype, a) = mw.vstack_pop().unwrap();
@@ -523,67 +882,53 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             mw.vstack_push(&im_name, VariableType::Float);
             format!("float {im_name} = fmod({a},{b});")
         }
-        FatOp::IAdd => arthm_impl!(mw, VariableType::Int, "+"),
-        FatOp::ISub => arthm_impl!(mw, VariableType::Int, "-"),
-        FatOp::IMul => arthm_impl!(mw, VariableType::Int, "*"),
-        FatOp::IDiv => arthm_impl!(mw, VariableType::Int, "/"),
-        FatOp::IShl => arthm_impl!(mw, VariableType::Int, "<<"),
-        FatOp::IShr => arthm_impl!(mw, VariableType::Int, ">>"),
-        FatOp::IUShr => {
-            let (_, b) = mw.vstack_pop().unwrap();
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Int);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Int);
-            format!("int {im} = (int)(((unsigned int){a})>>{b});")
+        FatOp::IAdd => java_arthm_impl!(mw, VariableType::Int, "java_add"),
+        FatOp::ISub => java_arthm_impl!(mw, VariableType::Int, "java_sub"),
+        FatOp::IMul => java_arthm_impl!(mw, VariableType::Int, "java_mul"),
+        FatOp::IDiv => {
+            mw.add_include("java_cs_lang_cs_ArithmeticException");
+            java_arthm_impl!(mw, VariableType::Int, "java_div")
         }
+        FatOp::IRem => {
+            mw.add_include("java_cs_lang_cs_ArithmeticException");
+            java_arthm_impl!(mw, VariableType::Int, "java_rem")
+        }
+        FatOp::IShl => java_arthm_impl!(mw, VariableType::Int, "java_shl"),
+        FatOp::IShr => java_arthm_impl!(mw, VariableType::Int, "java_shr"),
+        FatOp::IUShr => java_arthm_impl!(mw, VariableType::Int, "java_ushr"),
         FatOp::IAnd => arthm_impl!(mw, VariableType::Int, "&"),
         FatOp::IOr => arthm_impl!(mw, VariableType::Int, "|"),
         FatOp::IXOr => arthm_impl!(mw, VariableType::Int, "^"),
-        FatOp::INeg => {
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Int);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Int);
-            format!("int {im} = !{a};")
-        }
-        FatOp::IRem => arthm_impl!(mw, VariableType::Long, "%"),
-        FatOp::LAdd => arthm_impl!(mw, VariableType::Long, "+"),
-        FatOp::LSub => arthm_impl!(mw, VariableType::Long, "-"),
-        FatOp::LMul => arthm_impl!(mw, VariableType::Long, "*"),
-        FatOp::LDiv => arthm_impl!(mw, VariableType::Long, "/"),
-        FatOp::LShl => arthm_impl!(mw, VariableType::Long, "<<"),
-        FatOp::LShr => arthm_impl!(mw, VariableType::Long, ">>"),
-        FatOp::LUShr => {
-            let (_, b) = mw.vstack_pop().unwrap();
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Long);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Long);
-            format!("long {im} = (long)(((unsigned long){a})>>{b});")
-        }
+        FatOp::INeg => neg_impl!(mw, VariableType::Int, "java_neg<int32_t>({a})"),
+        FatOp::LAdd => java_arthm_impl!(mw, VariableType::Long, "java_add"),
+        FatOp::LSub => java_arthm_impl!(mw, VariableType::Long, "java_sub"),
+        FatOp::LMul => java_arthm_impl!(mw, VariableType::Long, "java_mul"),
+        FatOp::LDiv => {
+            mw.add_include("java_cs_lang_cs_ArithmeticException");
+            java_arthm_impl!(mw, VariableType::Long, "java_div")
+        }
+        FatOp::LRem => {
+            mw.add_include("java_cs_lang_cs_ArithmeticException");
+            java_arthm_impl!(mw, VariableType::Long, "java_rem")
+        }
+        FatOp::LShl => shift_impl!(mw, "java_shl"),
+        FatOp::LShr => shift_impl!(mw, "java_shr"),
+        FatOp::LUShr => shift_impl!(mw, "java_ushr"),
         FatOp::LAnd => arthm_impl!(mw, VariableType::Long, "&"),
         FatOp::LOr => arthm_impl!(mw, VariableType::Long, "|"),
         FatOp::LXOr => arthm_impl!(mw, VariableType::Long, "^"),
-        FatOp::LNeg => {
-            let (atype, a) = mw.vstack_pop().unwrap();
-            debug_assert_eq!(atype, VariableType::Long);
-            let im = mw.get_intermidiate();
-            mw.vstack_push(&im, VariableType::Long);
-            format!("long {im} = !{a};")
-        }
-        FatOp::LRem => arthm_impl!(mw, VariableType::Long, "%"),
+        FatOp::LNeg => neg_impl!(mw, VariableType::Long, "java_neg<int64_t>({a})"),
         FatOp::D2F => convert_impl!(mw, VariableType::Double, VariableType::Float),
-        FatOp::D2I => convert_impl!(mw, VariableType::Double, VariableType::Int),
-        FatOp::D2L => convert_impl!(mw, VariableType::Double, VariableType::Long),
+        FatOp::D2I => fp_to_int_impl!(mw, VariableType::Double, VariableType::Int),
+        FatOp::D2L => fp_to_int_impl!(mw, VariableType::Double, VariableType::Long),
         FatOp::F2D => convert_impl!(mw, VariableType::Float, VariableType::Double),
-        FatOp::F2I => convert_impl!(mw, VariableType::Float, VariableType::Int),
+        FatOp::F2I => fp_to_int_impl!(mw, VariableType::Float, VariableType::Int),
         FatOp::I2B => convert_impl!(mw, VariableType::Int, VariableType::Byte),
         FatOp::I2C => convert_impl!(mw, VariableType::Int, VariableType::Char),
         FatOp::I2D => convert_impl!(mw, VariableType::Int, VariableType::Double),
         FatOp::I2F => convert_impl!(mw, VariableType::Int, VariableType::Float),
         FatOp::I2S => convert_impl!(mw, VariableType::Int, VariableType::Short),
-        FatOp::F2L => convert_impl!(mw, VariableType::Float, VariableType::Long),
+        FatOp::F2L => fp_to_int_impl!(mw, VariableType::Float, VariableType::Long),
         FatOp::I2L => convert_impl!(mw, VariableType::Int, VariableType::Long),
         FatOp::L2I => convert_impl!(mw, VariableType::Long, VariableType::Int),
         FatOp::L2F => convert_impl!(mw, VariableType::Long, VariableType::Float),
@@ -665,6 +1010,7 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         FatOp::IfICmpLessEqual(target) => conditional_impl!(mw, "<=", target),
         FatOp::IfICmpGreater(target) => conditional_impl!(mw, ">", target),
         FatOp::IfZero(target) => conditional_impl!(mw, "==", "0", target),
+        FatOp::IfNotZero(target) => conditional_impl!(mw, "!=", "0", target),
         FatOp::IfLessEqualZero(target) => conditional_impl!(mw, "<=", "0", target),
         FatOp::IfGreterEqualZero(target) => conditional_impl!(mw, ">=", "0", target),
         FatOp::IfGreterZero(target) => conditional_impl!(mw, ">", "0", target),
@@ -675,74 +1021,39 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         FatOp::IfACmpNe(target) => conditional_impl!(mw, "!=", target),
         FatOp::GoTo(target) => format!("goto bb{target};"),
         FatOp::Dup => {
-            let value = mw.vstack_pop().unwrap();
-            mw.vstack_push(&value.1, value.0.clone());
-            mw.vstack_push(&value.1, value.0.clone());
+            mw.vstack_dup(1, 0);
             "".into()
         }
         FatOp::DupX1 => {
-            let value = mw.vstack_pop().unwrap();
-            let value_2 = mw.vstack_pop().unwrap();
-            mw.vstack_push(&value.1, value.0.clone());
-            mw.vstack_push(&value_2.1, value_2.0.clone());
-            mw.vstack_push(&value.1, value.0.clone());
+            mw.vstack_dup(1, 1);
             "".into()
         }
         FatOp::DupX2 => {
-            let value = mw.vstack_pop().unwrap();
-            let value_2 = mw.vstack_pop().unwrap();
-            if value_2.0.is_wide() {
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
-            } else {
-                let value_3 = mw.vstack_pop().unwrap();
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value_3.1, value_3.0.clone());
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
-            }
+            // A wide value takes two slots, so it is skipped alone.
+            let skipped = if mw.vstack_type(1).is_wide() { 1 } else { 2 };
+            mw.vstack_dup(1, skipped);
             "".into()
         }
         FatOp::Swap => {
-            let value = mw.vstack_pop().unwrap();
-            let value_2 = mw.vstack_pop().unwrap();
-            if value.0.is_wide() | value_2.0.is_wide() {
+            if mw.vstack_type(0).is_wide() | mw.vstack_type(1).is_wide() {
                 panic!("Swap attempted at values bigger than word(Double or Long).");
             }
-            mw.vstack_push(&value.1, value.0.clone());
-            mw.vstack_push(&value_2.1, value_2.0.clone());
+            mw.vstack_swap();
             "".into()
         }
         FatOp::Dup2X1 => {
-            let value = mw.vstack_pop().unwrap();
-            if value.0.is_wide() {
-                let value_2 = mw.vstack_pop().unwrap();
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
+            if mw.vstack_type(0).is_wide() {
+                mw.vstack_dup(1, 1);
             } else {
-                let value_2 = mw.vstack_pop().unwrap();
-                let value_3 = mw.vstack_pop().unwrap();
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value_3.1, value_3.0.clone());
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
+                mw.vstack_dup(2, 1);
             }
             "".into()
         }
         FatOp::Dup2 => {
-            let value = mw.vstack_pop().unwrap();
-            if value.0.is_wide() {
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
+            if mw.vstack_type(0).is_wide() {
+                mw.vstack_dup(1, 0);
             } else {
-                let value_2 = mw.vstack_pop().unwrap();
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
-                mw.vstack_push(&value_2.1, value_2.0.clone());
-                mw.vstack_push(&value.1, value.0.clone());
+                mw.vstack_dup(2, 0);
             }
             "".into()
         }
@@ -762,98 +1073,123 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             mw.vstack_push(&im, VariableType::ObjectRef(class_info.clone()));
             mw.add_include(&*class_info.class_path());
             format!(
-                "ManagedPointer<{name}> {im} = new_managed({name},);",
+                "{name}::_ensure_clinit_();ManagedPointer<{name}> {im} = new_managed({name},);",
                 name = class_info.cpp_class()
             )
         }
-        FatOp::CheckedCast(class_info) => {
-            let (vtype, value) = mw.vstack_pop().unwrap();
-            mw.vstack_push(&value, vtype);
-            mw.add_include(&*class_info.class_path());
-            format!(
-                "if(typeid(*{value}) != typeid({name}) = throw new java::lang::ClassCastException();",
-                name = class_info.cpp_class()
-            )
+        FatOp::CheckedCast(vtype) => {
+            let (_src_type, value) = mw.vstack_pop().unwrap();
+            let im = mw.get_intermidiate();
+            mw.vstack_push(&im, vtype.clone());
+            if let Some(dependency) = vtype.dependency() {
+                mw.add_include(&dependency);
+            }
+            mw.add_include("java_cs_lang_cs_ClassCastException");
+            if vtype.is_array() {
+                format!(
+                    "{ctype} {im} = checked_array_cast<{pointee}>({value},{array_class});",
+                    ctype = vtype.c_type(),
+                    pointee = pointee_type(vtype),
+                    array_class = array_class(vtype)
+                )
+            } else {
+                format!(
+                    "{ctype} {im} = checked_cast<{pointee}>({value},\"{java_name}\");",
+                    ctype = vtype.c_type(),
+                    pointee = pointee_type(vtype),
+                    java_name = java_type_name(vtype)
+                )
+            }
         }
-        FatOp::InstanceOf(class_info) => {
-            let (vtype, value) = mw.vstack_pop().unwrap();
+        FatOp::InstanceOf(vtype) => {
+            let (_src_type, value) = mw.vstack_pop().unwrap();
             let im = mw.get_intermidiate();
             mw.vstack_push(&im, VariableType::Int);
-            mw.add_include(&*class_info.class_path());
-            format!(
-                "int {im} = typeid(*{value}) != typeid({name}) ? 1:0;",
-                name = class_info.cpp_class()
-            )
+            if let Some(dependency) = vtype.dependency() {
+                mw.add_include(&dependency);
+            }
+            if vtype.is_array() {
+                format!(
+                    "int {im} = instance_of_array({value},{array_class}) ? 1:0;",
+                    array_class = array_class(vtype)
+                )
+            } else {
+                format!(
+                    "int {im} = instance_of<{pointee}>({value}) ? 1:0;",
+                    pointee = pointee_type(vtype)
+                )
+            }
         }
         FatOp::ANewArray(class_info) => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
-            mw.vstack_push(
-                &im,
-                VariableType::ArrayRef(Box::new(VariableType::ObjectRef(class_info.clone()))),
-            );
+            let atype = VariableType::ArrayRef(Box::new(VariableType::ObjectRef(class_info.clone())));
             mw.add_include(&*class_info.class_path());
-            format!(
-                "ManagedPointer<RuntimeArray<ManagedPointer<{name}>>> {im} = managed_from_raw(new RuntimeArray<ManagedPointer<{name}>>({length}));",
-                name = class_info.cpp_class()
-            )
+            let code = format!(
+                "{ctype} {im} = managed_from_raw({pointee}::New({length},{array_class}));",
+                ctype = atype.c_type(),
+                pointee = pointee_type(&atype),
+                array_class = array_class(&atype)
+            );
+            mw.vstack_push(&im, atype);
+            code
         }
         FatOp::BNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Byte)));
-            format!("ManagedPointer<RuntimeArray<int8_t>> {im} = managed_from_raw(new RuntimeArray<int8_t>({length}));",)
+            format!("ManagedPointer<RuntimeArray<int8_t>> {im} = managed_from_raw(RuntimeArray<int8_t>::New({length}));",)
         }
         FatOp::CNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Char)));
-            format!("ManagedPointer<RuntimeArray<char16_t>> {im} = managed_from_raw(new RuntimeArray<char16_t>({length}));",)
+            format!("ManagedPointer<RuntimeArray<char16_t>> {im} = managed_from_raw(RuntimeArray<char16_t>::New({length}));",)
         }
         FatOp::DNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Double)));
-            format!("ManagedPointer<RuntimeArray<double>> {im} = managed_from_raw(new RuntimeArray<double>({length}));",)
+            format!("ManagedPointer<RuntimeArray<double>> {im} = managed_from_raw(RuntimeArray<double>::New({length}));",)
         }
         FatOp::FNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Float)));
-            format!("ManagedPointer<RuntimeArray<float>> {im} = managed_from_raw(new RuntimeArray<float>({length}));",)
+            format!("ManagedPointer<RuntimeArray<float>> {im} = managed_from_raw(RuntimeArray<float>::New({length}));",)
         }
         FatOp::INewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Int)));
-            format!("ManagedPointer<RuntimeArray<int>> {im} = managed_from_raw(new RuntimeArray<int>({length}));",)
+            format!("ManagedPointer<RuntimeArray<int>> {im} = managed_from_raw(RuntimeArray<int>::New({length}));",)
         }
         FatOp::LNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Long)));
-            format!("ManagedPointer<RuntimeArray<long>> {im} = managed_from_raw(new RuntimeArray<long>({length}));",)
+            format!("ManagedPointer<RuntimeArray<int64_t>> {im} = managed_from_raw(RuntimeArray<int64_t>::New({length}));",)
         }
         FatOp::SNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Short)));
-            format!("ManagedPointer<RuntimeArray<short>> {im} = managed_from_raw(new RuntimeArray<short>({length}));",)
+            format!("ManagedPointer<RuntimeArray<short>> {im} = managed_from_raw(RuntimeArray<short>::New({length}));",)
         }
         FatOp::ZNewArray => {
             let im = mw.get_intermidiate();
             let (length_type, length) = mw.vstack_pop().unwrap();
             assert_eq!(length_type, VariableType::Int);
             mw.vstack_push(&im, VariableType::ArrayRef(Box::new(VariableType::Bool)));
-            format!("ManagedPointer<RuntimeArray<bool>> {im} = managed_from_raw(new RuntimeArray<bool>({length}));",)
+            format!("ManagedPointer<RuntimeArray<bool>> {im} = managed_from_raw(RuntimeArray<bool>::New({length}));",)
         }
         FatOp::StringConst(const_string) => {
             let im_name = mw.get_intermidiate();
@@ -877,10 +1213,10 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
                     "java/lang/Class",
                 )),
             );
-            format!("ManagedPointer<java::lang::Class> {im_name} = managed_from_raw(new java::lang::Class(u\"{class_name}\"));",
+            format!("ManagedPointer<java::lang::Class> {im_name} = java::lang::Class::for_name(u\"{class_name}\");",
             class_name = class_info.cpp_class())
         }
-        FatOp::InvokeVirtual(_class_name, vmethod_name, args, ret) => {
+        FatOp::InvokeVirtual(class_info, vmethod_name, args, ret) => {
             let mut code = String::new();
             let argc = args.len();
             let mut args: Vec<IString> = Vec::with_capacity(argc);
@@ -889,7 +1225,7 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             }
             args.reverse();
             let mut args = args.iter();
-            let objref = args.next().unwrap();
+            let objref = mw.null_checked(args.next().unwrap(), &invoke_action(class_info));
             if let Some(dep) = ret.dependency() {
                 mw.add_include(&dep);
             }
@@ -913,7 +1249,7 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             code.push_str(");");
             code
         }
-        FatOp::InvokeInterface(_class_name, vmethod_name, args, ret) => {
+        FatOp::InvokeInterface(iface, vmethod_name, args, ret) => {
             let mut code = String::new();
             let argc = args.len();
             let mut args: Vec<IString> = Vec::with_capacity(argc);
@@ -922,7 +1258,13 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             }
             args.reverse();
             let mut args = args.iter();
-            let objref = args.next().unwrap();
+            // The object may not be statically known to implement the interface, so the call must go through the interface type.
+            let objref = format!(
+                "interface_cast<{iface}>({objref})",
+                iface = iface.cpp_class(),
+                objref = mw.null_checked(args.next().unwrap(), &invoke_action(iface))
+            );
+            mw.add_include(&iface.class_path());
             if let Some(dep) = ret.dependency() {
                 mw.add_include(&dep);
             }
@@ -949,11 +1291,7 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         FatOp::InvokeSpecial(method_class_info, method_name, args, ret) => {
             mw.add_include(&method_class_info.class_path());
             let mut code = String::new();
-            let argc = if method_name.contains("_init_") {
-                args.len() + 1
-            } else {
-                args.len()
-            };
+            let argc = args.len() + 1;
             //println!("argc:{argc}");
             let mut args: Vec<IString> = Vec::with_capacity(argc);
             for _ in 0..argc {
@@ -965,20 +1303,32 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             if let Some(dep) = ret.dependency() {
                 mw.add_include(&dep);
             }
-            if *ret == crate::VariableType::Void {
-                code.push_str(&format!(
+            // Constructors(mangled `_init_` followed by their signature) are static, and take the object as their first argument. Other methods(private or super methods) are called non-virtually.
+            let is_init = method_name.starts_with("_init__");
+            let call = if is_init {
+                format!(
                     "{method_class_name}::{method_name}({objref}",
                     method_class_name = method_class_info.cpp_class()
-                ));
+                )
             } else {
-                let im_name = mw.get_intermidiate();
-                code.push_str(&format!(
-                    "{ret} {im_name} = {method_class_name}::{method_name}({objref}",
-                    ret = ret.c_type(),
+                format!(
+                    "{objref}->{method_class_name}::{method_name}(",
+                    objref = mw.null_checked(objref, &invoke_action(method_class_info)),
                     method_class_name = method_class_info.cpp_class()
-                ));
+                )
+            };
+            if *ret == crate::VariableType::Void {
+                code.push_str(&call);
+            } else {
+                let im_name = mw.get_intermidiate();
+                code.push_str(&format!("{ret} {im_name} = {call}", ret = ret.c_type()));
                 mw.vstack_push(&im_name, ret.clone());
             }
+            if !is_init {
+                if let Some(arg) = args.next() {
+                    code.push_str(arg);
+                }
+            }
             for arg in args {
                 code.push(',');
                 code.push_str(arg);
@@ -999,6 +1349,10 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             if let Some(dep) = ret.dependency() {
                 mw.add_include(&dep);
             }
+            code.push_str(&format!(
+                "{method_class_name}::_ensure_clinit_();",
+                method_class_name = method_class_info.cpp_class()
+            ));
             if *ret == crate::VariableType::Void {
                 code.push_str(&format!(
                     "{method_class_name}::{method_name}(",
@@ -1025,29 +1379,88 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
         }
         FatOp::Throw => {
             let (_, exception) = mw.vstack_pop().unwrap();
-            format!("throw {exception};")
+            let exception = mw.null_checked(&exception, "Cannot throw exception");
+            mw.add_include("java_cs_lang_cs_Throwable");
+            // All exceptions are thrown as `Throwable`, so that they can be caught by a single handler.
+            format!("throw ManagedPointer<java::lang::Throwable>({exception});")
         }
         FatOp::MonitorEnter => {
             let (_, object) = mw.vstack_pop().unwrap();
-            format!("{object}.monitor_enter();")
+            let object = mw.null_checked(&object, "Cannot enter synchronized block");
+            format!("{object}->monitor_enter();")
         }
         FatOp::MonitorExit => {
             let (_, object) = mw.vstack_pop().unwrap();
-            format!("{object}.monitor_exit();")
-        }
-        FatOp::InvokeDynamic(dynamic_handle,name,args,ret) => {
-            println!("ret:{ret:?}");
-            match dynamic_handle{
-                DynamicMethodHandle::InvokeStatic(class_info,method_name,_dyn_args,dyn_ret)=>{
-                    println!("dyn_ret:{dyn_ret:?}");
-                    if class_info.cpp_class() == "java::lang::invoke::LambdaMetafactory" && method_name.contains("metafactory"){
-                        impl_lambda(mw,name,args,ret)
-                    }else{
-                        panic!("Invoke Dynamic requires runtime codegen, which is not supported!");
+            let object = mw.null_checked(&object, "Cannot exit synchronized block");
+            format!("{object}->monitor_exit();")
+        }
+        FatOp::StringConcat(parts) => {
+            let argc = parts.iter().filter(|part| matches!(part, ConcatPart::Arg(_))).count();
+            let mut values: Vec<IString> = Vec::with_capacity(argc);
+            for _ in 0..argc {
+                values.push(mw.vstack_pop().unwrap().1);
+            }
+            let mut values = values.into_iter().rev();
+            mw.add_include("java_cs_lang_cs_String");
+            mw.add_include("java_cs_lang_cs_StringBuilder");
+            let builder = mw.get_intermidiate();
+            mw.write_raw(&format!(
+                "ManagedPointer<java::lang::StringBuilder> {builder} = new_managed(java::lang::StringBuilder,);"
+            ));
+            for part in parts.iter() {
+                match part {
+                    ConcatPart::Literal(text) => {
+                        mw.write_raw(&format!("{builder}->append(u\"{text}\");", text = escape_cpp_string(text)))
+                    }
+                    ConcatPart::Arg(vtype) => {
+                        let value = values.next().unwrap();
+                        let append = match vtype {
+                            VariableType::Byte | VariableType::Short | VariableType::Int => "append_I_java_cs_lang_cs_StringBuilder_",
+                            VariableType::Long => "append_J_java_cs_lang_cs_StringBuilder_",
+                            VariableType::Float => "append_F_java_cs_lang_cs_StringBuilder_",
+                            VariableType::Double => "append_D_java_cs_lang_cs_StringBuilder_",
+                            VariableType::Char => "append_C_java_cs_lang_cs_StringBuilder_",
+                            VariableType::Bool => "append_Z_java_cs_lang_cs_StringBuilder_",
+                            VariableType::ObjectRef(class_info) if class_info.cpp_class() == "java::lang::String" => {
+                                "append_java_cs_lang_cs_String__java_cs_lang_cs_StringBuilder_"
+                            }
+                            VariableType::ObjectRef(_) | VariableType::ArrayRef(_) => "append_java_cs_lang_cs_Object__java_cs_lang_cs_StringBuilder_",
+                            VariableType::Void => panic!("Can't concatenate a void value!"),
+                        };
+                        mw.write_raw(&format!("{builder}->{append}({value});"));
                     }
-                },
-                _=>panic!("Invoke Dynamic requires runtime codegen, which is not supported!"),
+                }
+            }
+            let im = mw.get_intermidiate();
+            mw.vstack_push(&im, VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/String")));
+            format!("ManagedPointer<java::lang::String> {im} = {builder}->toString__java_cs_lang_cs_String_();")
+        }
+        FatOp::InvokeDynamic(..) => {
+            panic!("Invoke Dynamic requires runtime codegen, which is not supported!")
+        }
+        FatOp::Lambda {
+            interface,
+            method,
+            args,
+            ret,
+            captured,
+            target,
+        } => {
+            let mut captured_values = Vec::with_capacity(captured.len());
+            for _ in 0..captured.len() {
+                captured_values.push(mw.vstack_pop().unwrap().1);
             }
+            captured_values.reverse();
+            mw.add_include(&interface.class_path());
+            let im = mw.get_intermidiate();
+            let closure = format!("Closure_{im}");
+            write_closure(mw, &closure, interface, method, args, ret, captured, target);
+            mw.vstack_push(&im, VariableType::ObjectRef(interface.clone()));
+            format!(
+                "ManagedPointer<{iface}> {im} = managed_from_raw(new {closure}({captured_values}));",
+                iface = interface.cpp_class(),
+                captured_values = captured_values.join(",")
+            )
         }
         FatOp::LookupSwitch{default_op,pairs}=>{
             let (key_type,key) = mw.vstack_pop().unwrap();
@@ -1063,7 +1476,40 @@ fn write_op(op: &FatOp, mw: &mut MethodWriter) {
             mw.end_scope();
             "".into()
         },
-        FatOp::Dup2X2 | &FatOp::MultiANewArray(_, _) => todo!(),
+        FatOp::TableSwitch{low,high,default,targets}=>{
+            let (key_type,key) = mw.vstack_pop().unwrap();
+            assert!(VariableType::Int.assignable(&key_type));
+            debug_assert_eq!((*high as i64 - *low as i64 + 1) as usize, targets.len());
+            mw.write_raw(&format!("switch ({key})"));
+            mw.begin_scope();
+            for (key,target) in (*low..=*high).zip(targets.iter()){
+                mw.write_raw(&format!("case {key}:"));
+                mw.write_raw(&format!("\tgoto bb{target};"));
+            }
+            mw.write_raw("default:");
+            mw.write_raw(&format!("\tgoto bb{default};"));
+            mw.end_scope();
+            "".into()
+        },
+        FatOp::MultiANewArray(atype, dimensions) => {
+            let mut lengths = Vec::with_capacity(*dimensions as usize);
+            for _ in 0..*dimensions {
+                let (length_type, length) = mw.vstack_pop().unwrap();
+                assert!(VariableType::Int.assignable(&length_type));
+                lengths.push(length);
+            }
+            // Outermost dimension is pushed first.
+            lengths.reverse();
+            if let Some(dep) = atype.dependency() {
+                mw.add_include(&dep);
+            }
+            let im = mw.get_intermidiate();
+            mw.write_raw(&format!("{ctype} {im};", ctype = atype.c_type()));
+            write_multi_array(mw, &im, atype, &lengths);
+            mw.vstack_push(&im, atype.clone());
+            "".into()
+        }
+        FatOp::Dup2X2 => todo!(),
         //_ => todo!("Unsuported op:\"{op:?}\""),
     };
     mw.write_op(op, &code);
@@ -1074,19 +1520,112 @@ impl BasicBlock {
             Self::Raw { starts, .. } => *starts,
         }
     }
-    fn write(&self, writer: &mut MethodWriter) {
-        writer.begin_bb(self.starts());
+    fn write(&self, writer: &mut MethodWriter, handlers: &[ExceptionHandler], next: Option<usize>) {
+        let is_handler = handlers.iter().any(|handler| handler.handler == self.starts());
+        writer.set_op(self.starts());
+        writer.begin_bb(self.starts(), is_handler);
         writer.begin_scope();
+        if is_handler {
+            begin_handler(self.starts(), handlers, writer);
+        }
+        // Exception table entries are ordered by priority, so only the first matching handler should be used.
+        let covering: Vec<&ExceptionHandler> = handlers
+            .iter()
+            .filter(|handler| handler.covers(self.starts()))
+            .collect();
+        if !covering.is_empty() {
+            writer.write_raw("try");
+            writer.begin_scope();
+        }
         match self {
             Self::Raw { ops, .. } => {
-                for op in ops.iter() {
-                    write_op(op, writer);
+                for (index, op) in ops.iter().enumerate() {
+                    writer.set_op(self.starts() + index);
+                    writer.update_frame_line();
+                    // Execution falls through to the next block only after the last op, jumps may also be in the middle of a block.
+                    let falls_to_next = index + 1 == ops.len() && op.falls_through();
+                    // Values left on the stack below the operands of a jump are passed to its targets before jumping.
+                    if let Some(targets) = op.jump_target() {
+                        let mut successors = targets.to_vec();
+                        successors.extend(next.filter(|_| falls_to_next));
+                        writer.pass_stack(&successors, branch_operands(op));
+                        write_op(op, writer);
+                    } else {
+                        write_op(op, writer);
+                        if falls_to_next {
+                            writer.pass_stack(next.as_slice(), 0);
+                        }
+                    }
                 }
             }
         }
+        if !covering.is_empty() {
+            writer.end_scope();
+            write_catch(&covering, writer);
+        }
         writer.end_scope();
     }
 }
+/// Returns the number of values a jump op pops off the stack.
+fn branch_operands(op: &FatOp) -> usize {
+    match op {
+        FatOp::GoTo(_) => 0,
+        FatOp::IfACmpEq(_)
+        | FatOp::IfACmpNe(_)
+        | FatOp::IfICmpEq(_)
+        | FatOp::IfICmpNe(_)
+        | FatOp::IfICmpGreater(_)
+        | FatOp::IfIGreterEqual(_)
+        | FatOp::IfICmpLess(_)
+        | FatOp::IfICmpLessEqual(_) => 2,
+        _ => 1,
+    }
+}
+/// Pushes exception caught by a handler starting at `starts` onto the stack.
+fn begin_handler(starts: usize, handlers: &[ExceptionHandler], writer: &mut MethodWriter) {
+    let catch_type = handler_catch_type(starts, handlers);
+    writer.add_include(&catch_type.class_path());
+    writer.vstack_clear();
+    if catch_type.cpp_class() == "java::lang::Throwable" {
+        writer.vstack_push("exception", VariableType::ObjectRef(catch_type));
+    } else {
+        writer.vstack_push(
+            &format!("managed_cast<{name}>(exception)", name = catch_type.cpp_class()),
+            VariableType::ObjectRef(catch_type),
+        );
+    }
+}
+fn write_catch(covering: &[&ExceptionHandler], writer: &mut MethodWriter) {
+    writer.add_include("java_cs_lang_cs_Throwable");
+    writer.push_locals(
+        "exception",
+        "\tManagedPointer<java::lang::Throwable> exception;\n",
+    );
+    writer.write_raw("catch(ManagedPointer<java::lang::Throwable> caught)");
+    writer.begin_scope();
+    for handler in covering {
+        match &handler.catch_type {
+            Some(catch_type) => {
+                writer.add_include(&catch_type.class_path());
+                writer.write_raw(&format!(
+                    "if(instance_of<{name}>(caught)){{exception = caught; goto bb{target};}}",
+                    name = catch_type.cpp_class(),
+                    target = handler.handler
+                ));
+            }
+            None => {
+                writer.write_raw(&format!(
+                    "exception = caught; goto bb{target};",
+                    target = handler.handler
+                ));
+                writer.end_scope();
+                return;
+            }
+        }
+    }
+    writer.write_raw("throw;");
+    writer.end_scope();
+}
 //May be unneded?
 fn bb_unroll(basic_spans: &[(usize, &[FatOp])]) -> Box<[BasicBlock]> {
     // Iteretes all the spans, returning only those which have at least one jump, which goes forward.
@@ -1097,17 +1636,23 @@ fn bb_unroll(basic_spans: &[(usize, &[FatOp])]) -> Box<[BasicBlock]> {
     });
     todo!();
 }
-fn fat_ops_to_bb_tree(fatops: &[FatOp]) -> Box<[BasicBlock]> {
+fn fat_ops_to_bb_tree(fatops: &[FatOp], handlers: &[ExceptionHandler]) -> Box<[BasicBlock]> {
     let mut jump_targets = Vec::with_capacity(fatops.len() / 3);
     for op in fatops {
         if let Some(targets) = op.jump_target() {
             targets.iter().for_each(|target| jump_targets.push(*target));
         }
     }
+    // Protected ranges must begin and end on block boundaries, so that a whole block can be wrapped in a `try`.
+    for handler in handlers {
+        jump_targets.push(handler.start);
+        jump_targets.push(handler.end);
+        jump_targets.push(handler.handler);
+    }
     let mut basic_spans: Vec<(usize, &[FatOp])> = Vec::new();
     let mut bb_beg = 0;
     for (index, _op) in fatops.iter().enumerate() {
-        if jump_targets.contains(&index) {
+        if jump_targets.contains(&index) && index > bb_beg {
             basic_spans.push((bb_beg, &fatops[bb_beg..index]));
             bb_beg = index;
         }
@@ -1125,41 +1670,43 @@ fn fat_ops_to_bb_tree(fatops: &[FatOp]) -> Box<[BasicBlock]> {
         })
         .collect()
 }
-fn push_method_sig_args(target: &mut String, method_name: &str, method: &crate::Method) {
-    let mut curr_id = if method.is_virtual() { 1 } else { 0 };
+/// Returns the JVM local slots holding arguments of `method`, with their types. `long` and `double` arguments take up two slots.
+fn arg_slots(method: &crate::Method) -> Vec<(u16, &VariableType)> {
+    let mut slot = if method.is_virtual() { 1 } else { 0 };
+    method
+        .args()
+        .iter()
+        .map(|arg| {
+            let arg_slot = slot;
+            slot += if matches!(arg, VariableType::Long | VariableType::Double) { 2 } else { 1 };
+            (arg_slot, arg)
+        })
+        .collect()
+}
+fn push_method_sig_args(target: &mut String, method_name: &str, method: &crate::Method, writer: &MethodWriter) {
     target.push_str(&format!(
         "{ret} {method_name}(",
         ret = method.ret_val().c_type()
     ));
-    let mut margs = method.args().iter();
-
-    match margs.next() {
-        Some(arg) => {
-            target.push_str(&format!(
-                "{ctype} l{curr_id}{postfix}",
-                ctype = &arg.c_type(),
-                postfix = arg.type_postifx()
-            ));
-            curr_id += 1;
-        }
-        None => (),
-    }
-    for arg in margs {
-        target.push_str(&format!(
-            ",{ctype} l{curr_id}{postfix}",
-            ctype = &arg.c_type(),
-            postfix = arg.type_postifx()
-        ));
-        curr_id += 1;
-    }
+    let args: Vec<String> = arg_slots(method)
+        .into_iter()
+        .map(|(slot, arg)| {
+            format!("{ctype} {name}", ctype = &arg.c_type(), name = writer.arg_name(slot, arg))
+        })
+        .collect();
+    target.push_str(&args.join(","));
     target.push(')');
 }
 pub(crate) fn create_method_impl(
-    mut out: impl Write,
+    out: &mut SourceWriter<impl Write>,
     method: &crate::Method,
+    options: CodegenOptions,
 ) -> Result<(), std::io::Error> {
-    let bb_tree = fat_ops_to_bb_tree(method.ops());
-    let mut writer = MethodWriter::new(method.args());
+    let bb_tree = fat_ops_to_bb_tree(method.ops(), method.exceptions());
+    let this = method
+        .is_virtual()
+        .then(|| VariableType::ObjectRef(ClassInfo::from_java_path(method.class_name())));
+    let mut writer = MethodWriter::new(this, &arg_slots(method), method, options);
     let mut fn_sig = String::new();
     push_method_sig_args(
         &mut fn_sig,
@@ -1169,19 +1716,8 @@ pub(crate) fn create_method_impl(
             method_name = method.name()
         ),
         method,
+        &writer,
     );
-    if !method.is_virtual()
-        && method.name() == "main__arr_java_cs_lang_cs_String__V"
-        && *method.ret_val() == VariableType::Void
-        && method.args()
-            == &[VariableType::ArrayRef(Box::new(VariableType::ObjectRef(
-                ClassInfo::from_java_path("java/lang/String"),
-            )))]
-    {
-        write!(out,"int main(int argc, char** argv){{\n\t//Skip fist exec path\n\targc -= 1;argv += 1;\n\tManagedPointer<RuntimeArray<ManagedPointer<java::lang::String>>> args = managed_from_raw(new RuntimeArray<ManagedPointer<java::lang::String>>(argc));\n\tfor(int arg = 0; arg < argc; arg++){{\n\t\targs->Set(arg,java::lang::String::from_cstring(argv[arg]));\n\t}}\n\t{class_name}::{method_name}(args);\n\treturn 0;\n}}\n",
-        class_name = method.class_name(),
-        method_name = method.name())?;
-    }
     writer.set_sig(&fn_sig);
     writer.add_include(method.class_name());
     if method.is_virtual() {
@@ -1193,9 +1729,67 @@ pub(crate) fn create_method_impl(
             ),
         );
     }
-    for bb in bb_tree.iter() {
-        bb.write(&mut writer);
+    // The frame is pushed on the shadow stack before anything can throw, and popped when the method returns or throws.
+    if options.stack_traces {
+        writer.add_include("java_cs_lang_cs_Throwable");
+        let (class, method_name) = method.java_name().rsplit_once('.').unwrap_or(("", method.java_name()));
+        let file = match method.source_file() {
+            Some(file) => format!("u\"{}\"", escape_cpp_string(file)),
+            None => "nullptr".into(),
+        };
+        // Frames of constructors of exceptions are not a part of their stack traces.
+        let constructed = match arg_slots(method).first() {
+            Some((0, this)) if method_name == "<init>" => format!(",&*{}", writer.arg_name(0, this)),
+            _ => String::new(),
+        };
+        writer.push_locals(
+            "shadow_frame",
+            &format!(
+                "\tShadowFrame shadow_frame(u\"{class}\",u\"{method_name}\",{file}{constructed});\n",
+                class = escape_cpp_string(class),
+                method_name = escape_cpp_string(method_name)
+            ),
+        );
+    }
+    // The monitor of a `synchronized` method is held by a guard, which releases it on both normal and exceptional exit.
+    if method.is_synchronized() {
+        let monitor = if method.is_virtual() {
+            "l0a".to_owned()
+        } else {
+            writer.add_include("java_cs_lang_cs_Class");
+            format!(
+                "java::lang::Class::for_name(u\"{class}\")",
+                class = crate::class::java_class_to_cpp_class(method.class_name())
+            )
+        };
+        writer.push_locals(
+            "monitor_guard",
+            &format!("\tMonitorGuard monitor_guard({monitor});\n"),
+        );
+    }
+    for (index, bb) in bb_tree.iter().enumerate() {
+        let next = bb_tree.get(index + 1).map(BasicBlock::starts);
+        bb.write(&mut writer, method.exceptions(), next);
+    }
+    let is_main = !method.is_virtual()
+        && method.name() == "main__arr_java_cs_lang_cs_String__V"
+        && *method.ret_val() == VariableType::Void
+        && method.args()
+            == &[VariableType::ArrayRef(Box::new(VariableType::ObjectRef(
+                ClassInfo::from_java_path("java/lang/String"),
+            )))];
+    // Exceptions thrown out of `main` are reported, and make the program exit with status 1. Either way, the program exits once all non-daemon threads finish.
+    if is_main {
+        writer.add_include("java_cs_lang_cs_Throwable");
+        writer.add_include("java_cs_lang_cs_Thread");
+    }
+    let (code, line_ops) = writer.final_code();
+    out.write_method(method, &code, &line_ops, options.line_directives)?;
+    if is_main {
+        let entry_point = format!("int main(int argc, char** argv){{\n#ifdef GC_OBJS\n\tGC_INIT();\n#endif\n\t//Skip fist exec path\n\targc -= 1;argv += 1;\n\tManagedPointer<RuntimeArray<ManagedPointer<java::lang::Object>>> args = managed_from_raw(RuntimeArray<ManagedPointer<java::lang::Object>>::New(argc,array_class_of<java::lang::String,1>()));\n\tfor(int arg = 0; arg < argc; arg++){{\n\t\targs->Set(arg,java::lang::String::from_cstring(argv[arg]));\n\t}}\n\ttry{{\n\t\t{class_name}::_ensure_clinit_();\n\t\t{class_name}::{method_name}(args);\n\t}}\n\tcatch(ManagedPointer<java::lang::Throwable> exception){{\n\t\treport_uncaught(\"main\",exception);\n\t\tjava::lang::Thread::join_non_daemon();\n\t\treturn 1;\n\t}}\n\tjava::lang::Thread::join_non_daemon();\n\treturn 0;\n}}\n",
+        class_name = method.class_name(),
+        method_name = method.name());
+        out.write_entry_point(method, &entry_point)?;
     }
-    out.write_all(writer.final_code().as_bytes())?;
     Ok(())
 }
@@ -1,6 +1,8 @@
-use super::IncludeBuilder;
-use crate::{fatops::FatOp, IString, VariableType};
-use std::collections::HashSet;
+use super::{CodegenOptions, IncludeBuilder};
+use crate::{fatops::FatOp, live_ranges::local_access, type_inference::TypeState, IString, VariableType};
+use crate::fatops::ClassInfo;
+use std::collections::{HashMap, HashSet};
+#[derive(Clone, Copy)]
 pub(crate) enum LocalKind {
     ObjectRef,
     Float,
@@ -14,63 +16,364 @@ pub(crate) struct MethodWriter {
     sig: IString,
     ident_level: usize,
     local_decl: String,
-    vstack: Vec<(VariableType, IString)>,
+    vstack: Vec<StackValue>,
     locals: HashSet<IString>,
-    local_types:Vec<Option<VariableType>>,
+    /// Types reference variables are declared with, by slot and variable number.
+    local_types: HashMap<(u16, u16), VariableType>,
+    /// Types of reference variables in Java source, by slot and variable number. Values of those variables are always loaded with them.
+    java_types: HashMap<(u16, u16), VariableType>,
     im_id: usize,
+    options: CodegenOptions,
+    /// Name of the local holding `this`, if local slot 0 holds it and is never reassigned.
+    this: Option<IString>,
+    /// Java name of the method, used in exception messages.
+    java_method: IString,
+    /// Inferred types of locals and stack values before each op.
+    types: Box<[Option<TypeState>]>,
+    /// Variable accessed by each op using a local.
+    variables: Box<[Option<u16>]>,
+    /// C++ names of variables named in Java source, by slot, kind postfix and variable number.
+    names: HashMap<(u16, char, u16), IString>,
+    /// Index of the op being written.
+    op: usize,
+    /// Ops written starting at each byte offset of `code`.
+    op_starts: Vec<(usize, usize)>,
+    /// Java source line of each op.
+    lines: Box<[Option<u16>]>,
+    /// Java source line last stored in the shadow stack frame of the method, in the current block.
+    frame_line: Option<u16>,
+}
+/// Name of the local holding the value at `depth` of the operand stack at the start of block `block`.
+fn stack_local(block: usize, depth: usize) -> IString {
+    format!("bb{block}_s{depth}").into()
+}
+/// Value on the operand stack: a C++ expression and its type.
+#[derive(Debug, Clone)]
+struct StackValue {
+    vtype: VariableType,
+    value: IString,
+    /// Local variable the expression reads, if it is a load of a local which was not evaluated yet.
+    reads: Option<IString>,
+}
+/// Type of `java.lang.Object` references.
+pub(super) fn object() -> VariableType {
+    VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/Object"))
+}
+/// Returns the types reference variables without a type known from `StackMapTable` frames should be declared with, by slot and
+/// variable number. A variable holding values of different C++ types is declared as `java.lang.Object`, and its value is cast to the
+/// inferred type when loaded.
+fn declared_local_types(method: &crate::Method) -> HashMap<(u16, u16), VariableType> {
+    let mut declared: HashMap<(u16, u16), VariableType> = HashMap::new();
+    for (index, op) in method.ops().iter().enumerate() {
+        let (Some((slot, is_store)), Some(Some(variable)), Some(Some(state))) = (
+            local_access(op),
+            method.variables().variables.get(index),
+            method.types().get(index),
+        ) else {
+            continue;
+        };
+        if !matches!(op, FatOp::ALoad(_) | FatOp::AStore(_)) {
+            continue;
+        }
+        // Stores take the value from the top of the stack.
+        let vtype = if is_store {
+            state.stack.last()
+        } else {
+            state.locals.get(slot as usize).and_then(Option::as_ref)
+        };
+        let Some(vtype) = vtype.filter(|vtype| vtype.is_reference() && !vtype.is_unknown()) else {
+            continue;
+        };
+        declared
+            .entry((slot, *variable))
+            .and_modify(|declared| {
+                if declared.c_type() != vtype.c_type() {
+                    *declared = object();
+                }
+            })
+            .or_insert_with(|| vtype.clone());
+    }
+    declared
+}
+/// C++ keywords, names the generated code and the runtime use without qualification, and lowercase macros of the C library. Locals
+/// named so in Java source are escaped.
+const RESERVED_NAMES: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break", "case", "catch", "char", "char8_t",
+    "char16_t", "char32_t", "class", "compl", "concept", "const", "consteval", "constexpr", "constinit", "const_cast", "continue",
+    "co_await", "co_return", "co_yield", "decltype", "default", "delete", "do", "double", "dynamic_cast", "else", "enum", "explicit",
+    "export", "extern", "false", "float", "for", "friend", "goto", "if", "inline", "int", "long", "mutable", "namespace", "new",
+    "noexcept", "not", "not_eq", "nullptr", "operator", "or", "or_eq", "private", "protected", "public", "register",
+    "reinterpret_cast", "requires", "return", "short", "signed", "sizeof", "static", "static_assert", "static_cast", "struct",
+    "switch", "template", "this", "thread_local", "throw", "true", "try", "typedef", "typeid", "typename", "union", "unsigned",
+    "using", "virtual", "void", "volatile", "wchar_t", "while", "xor", "xor_eq", "NULL", "NAN", "INFINITY", "main", "std", "gc",
+    "ManagedPointer", "RuntimeArray", "ArrayClass", "MonitorGuard", "monitor_guard", "ShadowFrame", "shadow_frame", "monitor_enter", "monitor_exit", "null_check",
+    "interface_cast", "managed_cast", "managed_from_this", "managed_from_raw", "new_managed", "checked_cast", "checked_array_cast",
+    "element_cast", "instance_of", "instance_of_array", "is_array_instance", "array_class_of", "is_subclass_of",
+    "primitive_descriptor", "unboxing", "fmod", "report_uncaught", "exception", "caught", "errno", "stdin", "stdout", "stderr",
+    "math_errhandling", "linux", "unix",
+];
+/// Returns the C++ name of a local named `name` in Java source. Names which are reserved, could be taken by names of generated
+/// locals(`l3a`, `i17`, `bb2_s0`), or look like C library typedefs(`int32_t`) or macros(`EOF`, `INT32_MAX`), get a `_` appended.
+fn escape_local_name(name: &str) -> String {
+    let name = name.replace('$', "_dolsig_");
+    let is_generated = ["bb", "loc", "l", "i"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    });
+    let is_reserved = RESERVED_NAMES.contains(&name.as_str())
+        || name.starts_with("java")
+        || name.starts_with("GC_")
+        || name.starts_with('_')
+        || name.contains("__")
+        || name.ends_with("_t")
+        || !name.contains(|c: char| c.is_ascii_lowercase());
+    if is_generated || is_reserved {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+#[test]
+fn local_name_escaping() {
+    assert_eq!(escape_local_name("index"), "index");
+    assert_eq!(escape_local_name("new"), "new_");
+    assert_eq!(escape_local_name("i2"), "i2_");
+    assert_eq!(escape_local_name("item2"), "item2");
+    assert_eq!(escape_local_name("bb0_s1"), "bb0_s1_");
+    assert_eq!(escape_local_name("java"), "java_");
+    assert_eq!(escape_local_name("a$b"), "a_dolsig_b");
+    assert_eq!(escape_local_name("exception"), "exception_");
+    assert_eq!(escape_local_name("uint8_t"), "uint8_t_");
+    assert_eq!(escape_local_name("INT32_MAX"), "INT32_MAX_");
+    assert_eq!(escape_local_name("errno"), "errno_");
+    assert_eq!(escape_local_name("maxValue"), "maxValue");
+}
+/// Returns C++ names of variables named in Java source. Variables of a slot with the same name and type(e.g. a local reassigned
+/// after its value was used) share a C++ variable, and distinct variables with the same name(e.g. locals of different scopes) get
+/// numbered postfixes. `this` keeps its generated name.
+fn local_names(method: &crate::Method, writer: &MethodWriter) -> HashMap<(u16, char, u16), IString> {
+    let mut variables: Vec<_> = method
+        .variables()
+        .names
+        .iter()
+        .filter(|(variable, _)| !(method.is_virtual() && **variable == (0, 'a', 0)))
+        .collect();
+    variables.sort();
+    let mut used = HashSet::new();
+    let mut shared: HashMap<(u16, char, &IString, IString), IString> = HashMap::new();
+    let mut names = HashMap::new();
+    for ((slot, postfix, number), name) in variables {
+        let declared = match postfix {
+            'a' => writer.reference_type(*slot, *number).c_type(),
+            _ => "".into(),
+        };
+        let unique = shared.entry((*slot, *postfix, name, declared)).or_insert_with(|| {
+            let name = escape_local_name(name);
+            let mut unique = name.clone();
+            let mut number = 1;
+            while !used.insert(unique.clone()) {
+                unique = format!("{name}_{number}");
+                number += 1;
+            }
+            unique.into()
+        });
+        names.insert((*slot, *postfix, *number), unique.clone());
+    }
+    names
 }
 impl MethodWriter {
-    pub(crate) fn ensure_local_exists(&mut self, id: u8, kind: LocalKind, vtype: VariableType) -> (IString,VariableType)  {
+    /// Declares local `id` of `kind`, if it was not declared yet. Returns its name and declared type.
+    pub(crate) fn ensure_local_exists(&mut self, id: u16, kind: LocalKind) -> (IString, VariableType) {
         let local = self.get_local(id, kind);
         if !self.locals.contains(&local.0) {
-            self.local_decl.push_str(&format!("\t{ctype} {local_name};\n",local_name = local.0,ctype = vtype.c_type()));
-            self.set_local_type(id,vtype);
+            if let Some(dependency) = local.1.dependency() {
+                self.add_include(&dependency);
+            }
+            self.local_decl.push_str(&format!("\t{ctype} {local_name};\n",local_name = local.0,ctype = local.1.c_type()));
             self.locals.insert(local.0.clone());
         }
         local
     }
-    fn set_local_type(&mut self, id:u8,vtype: VariableType){
-        while self.local_types.len() <= id as usize{
-            self.local_types.push(None);
+    /// Returns the name of variable `number` of slot `id`, holding locals with `postfix`. Variables without a name in Java source
+    /// are named `l{id}{postfix}` if they are the first variable of their slot(holding arguments), and `l{id}{postfix}_{number}` otherwise.
+    fn variable_name(&self, id: u16, postfix: char, number: u16) -> IString {
+        if let Some(name) = self.names.get(&(id, postfix, number)) {
+            return name.clone();
+        }
+        if number == 0 {
+            format!("l{id}{postfix}").into()
+        } else {
+            format!("l{id}{postfix}_{number}").into()
         }
-        self.local_types[id as usize] = Some(vtype);
     }
-    pub(crate) fn get_local_type(&self,id:u8)->VariableType{
-        //println!("local_types: {:?}, local_id:{id}, local_type:{:?}",self.local_types,self.local_types.get(id as usize));
-        match self.local_types.get(id as usize).cloned().flatten()
-        {
-            Some(vt)=>vt,
-            None=>VariableType::unknown(),
+    /// Returns the name of argument `arg` in local slot `slot`.
+    pub(crate) fn arg_name(&self, slot: u16, arg: &VariableType) -> IString {
+        self.variable_name(slot, arg.type_postifx().chars().next().unwrap(), 0)
+    }
+    /// Returns the type reference variable `number` of slot `id` is declared with.
+    fn reference_type(&self, id: u16, number: u16) -> VariableType {
+        let vtype = self.java_types.get(&(id, number)).or_else(|| self.local_types.get(&(id, number)));
+        vtype.cloned().unwrap_or_else(object)
+    }
+    /// Returns the name of the variable of slot `id` and `kind` accessed by the current op, and the type it is declared with.
+    pub(crate) fn get_local(&self, id: u16, kind: LocalKind) -> (IString,VariableType) {
+        let variable = self.variables.get(self.op).copied().flatten().unwrap_or(0);
+        let (postfix, vtype) = match kind {
+            LocalKind::ObjectRef => ('a', self.reference_type(id, variable)),
+            LocalKind::Float => ('f', VariableType::Float),
+            LocalKind::Double => ('d', VariableType::Double),
+            LocalKind::Int => ('i', VariableType::Int),
+            LocalKind::Long => ('l', VariableType::Long),
+        };
+        (self.variable_name(id, postfix, variable), vtype)
+    }
+    /// Returns the value of local `id` of `kind`, and its type. Values of reference variables without a known Java type are cast to
+    /// the type inferred for the current op, if they are declared with a different one.
+    pub(crate) fn load_local(&mut self, id: u16, kind: LocalKind) -> (IString, VariableType) {
+        let is_reference = matches!(kind, LocalKind::ObjectRef);
+        let (local, declared) = self.get_local(id, kind);
+        let variable = self.variables.get(self.op).copied().flatten().unwrap_or(0);
+        if !is_reference || self.java_types.contains_key(&(id, variable)) {
+            return (local, declared);
+        }
+        let inferred = self
+            .types
+            .get(self.op)
+            .and_then(|state| state.as_ref()?.locals.get(id as usize).cloned().flatten())
+            .filter(|vtype| vtype.is_reference() && !vtype.is_unknown());
+        match inferred {
+            Some(inferred) => (self.convert_reference(&local, &declared, &inferred), inferred),
+            None => (local, declared),
         }
     }
-    pub(crate) fn get_local(&self, id: u8, kind: LocalKind) -> (IString,VariableType) {
-        match kind {
-            LocalKind::ObjectRef => (format!("l{id}a").into(),self.get_local_type(id)),
-            LocalKind::Float => (format!("l{id}f").into(),VariableType::Float),
-            LocalKind::Double => (format!("l{id}d").into(),VariableType::Double),
-            LocalKind::Int => (format!("l{id}i").into(),VariableType::Int),
-            LocalKind::Long => (format!("l{id}l").into(),VariableType::Long),
+    /// Converts `value` of reference type `src` to `target`, using an upcast resolved at compile time, or a `dynamic_cast` otherwise.
+    pub(crate) fn convert_reference(&mut self, value: &str, src: &VariableType, target: &VariableType) -> IString {
+        if !target.is_reference() || !src.is_reference() || src.is_unknown() || src.c_type() == target.c_type() {
+            return value.into();
+        }
+        for dependency in [src.dependency(), target.dependency()].into_iter().flatten() {
+            self.add_include(&dependency);
         }
+        format!("interface_cast<{pointee}>({value})", pointee = super::method::pointee_type(target)).into()
+    }
+    /// Sets the index of the op being written.
+    pub(crate) fn set_op(&mut self, op: usize) {
+        self.op = op;
+        self.op_starts.push((self.code.len(), op));
+    }
+    /// Stores the Java source line of the current op in the shadow stack frame of the method, if it changed and stack traces are
+    /// enabled.
+    pub(crate) fn update_frame_line(&mut self) {
+        let line = self.lines.get(self.op).copied().flatten();
+        if !self.options.stack_traces || line.is_none() || line == self.frame_line {
+            return;
+        }
+        self.frame_line = line;
+        self.write_raw(&format!("shadow_frame.line = {};", line.unwrap()));
     }
     pub(crate) fn use_debuginfo(&self) -> bool {
         cfg!(debug_assertions)
     }
-    pub(crate) fn new(args:&[VariableType]) -> Self {
-        Self {
+    /// Creates a writer of a method taking arguments in local slots `args`, and `this` in slot 0 if it is an instance method.
+    pub(crate) fn new(
+        this: Option<VariableType>,
+        args: &[(u16, &VariableType)],
+        method: &crate::Method,
+        options: CodegenOptions,
+    ) -> Self {
+        let mut writer = Self {
             vstack: Vec::with_capacity(64),
             code: String::new(),
             sig: "".into(),
             includes: IncludeBuilder::new(""),
-            local_types: args.iter().map(|v|Some(v.clone())).collect(),
+            local_types: declared_local_types(method),
+            java_types: method
+                .variables()
+                .frame_types
+                .iter()
+                .filter(|(_, vtype)| !vtype.is_unknown())
+                .map(|(variable, vtype)| (*variable, vtype.clone()))
+                .collect(),
             ident_level: 1,
             local_decl: String::new(),
             locals: HashSet::new(),
             im_id: 0,
+            options,
+            this: None,
+            java_method: method.java_name().into(),
+            types: method.types().into(),
+            variables: method.variables().variables.clone(),
+            names: HashMap::new(),
+            op: 0,
+            op_starts: Vec::new(),
+            lines: method.lines().into(),
+            frame_line: None,
+        };
+        // Arguments are declared in the signature, with their types.
+        for (slot, arg) in this.iter().map(|this| (0, this)).chain(args.iter().copied()) {
+            if arg.is_reference() {
+                writer.java_types.insert((slot, 0), arg.clone());
+            }
+        }
+        writer.names = local_names(method, &writer);
+        let reassigned = method.ops().iter().any(|op| matches!(local_access(op), Some((0, true))));
+        if method.has_this() && !reassigned {
+            writer.this = Some(writer.variable_name(0, 'a', 0));
         }
+        for (slot, arg) in this.iter().map(|this| (0, this)).chain(args.iter().copied()) {
+            writer.locals.insert(writer.arg_name(slot, arg));
+        }
+        writer
+    }
+    /// Returns the types of stack values at the start of block `index`. They are merged from all of its predecessors by type inference,
+    /// before any code is written, so they do not depend on the order blocks are written in.
+    fn entry_stack(&self, index: usize) -> Vec<VariableType> {
+        self.types
+            .get(index)
+            .and_then(|state| state.as_ref())
+            .map(|state| state.stack.clone())
+            .unwrap_or_default()
     }
-    pub(crate) fn begin_bb(&mut self, index: usize) {
+    /// Begins block `index`, with the operand stack holding values passed to it by its predecessors. The stack of exception handlers
+    /// holds only the caught exception, pushed by the handler itself.
+    pub(crate) fn begin_bb(&mut self, index: usize, is_handler: bool) {
         self.write_ident();
         self.code.push_str(&format!("bb{index}:\n"));
+        self.vstack.clear();
+        // Blocks may be entered from ops on other lines.
+        self.frame_line = None;
+        if is_handler {
+            return;
+        }
+        for (depth, vtype) in self.entry_stack(index).into_iter().enumerate() {
+            let vtype = if vtype.is_unknown() { object() } else { vtype };
+            if let Some(dependency) = vtype.dependency() {
+                self.add_include(&dependency);
+            }
+            let local = stack_local(index, depth);
+            self.push_locals(&local, &format!("\t{ctype} {local};\n", ctype = vtype.c_type()));
+            self.vstack_push(&local, vtype);
+        }
+    }
+    /// Passes all but the topmost `keep` values of the operand stack to blocks `successors`, by storing them in their entry locals.
+    pub(crate) fn pass_stack(&mut self, successors: &[usize], keep: usize) {
+        let passed = self.vstack.len().saturating_sub(keep);
+        if passed == 0 {
+            return;
+        }
+        let values: Vec<(VariableType, IString)> =
+            self.vstack[..passed].iter().map(|value| (value.vtype.clone(), value.value.clone())).collect();
+        for successor in successors {
+            let entry_stack = self.entry_stack(*successor);
+            for (depth, (vtype, value)) in values.iter().enumerate() {
+                let value = match entry_stack.get(depth) {
+                    Some(entry_type) if !entry_type.is_unknown() => self.convert_reference(value, vtype, entry_type),
+                    _ => value.clone(),
+                };
+                let code = format!("{local} = {value};", local = stack_local(*successor, depth));
+                self.write_raw(&code);
+            }
+        }
     }
     pub(crate) fn begin_scope(&mut self) {
         self.write_ident();
@@ -87,6 +390,22 @@ impl MethodWriter {
             self.code.push('\t');
         }
     }
+    pub(crate) fn options(&self) -> CodegenOptions {
+        self.options
+    }
+    /// Wraps `value` in a check throwing `java.lang.NullPointerException` with a message describing `action`, unless null checks are disabled.
+    /// `this` of instance methods and constructors is never null, so it is not checked.
+    pub(crate) fn null_checked(&mut self, value: &str, action: &str) -> IString {
+        if !self.options.null_checks || self.this.as_deref() == Some(value) {
+            return value.into();
+        }
+        self.add_include("java_cs_lang_cs_NullPointerException");
+        format!(
+            "null_check({value},u\"{message}\")",
+            message = super::escape_cpp_string(&format!("{action} in {}", self.java_method))
+        )
+        .into()
+    }
     pub(crate) fn add_include(&mut self, include: &str) {
         self.includes.add_include(include);
     }
@@ -108,7 +427,37 @@ impl MethodWriter {
         }
     }
     pub(crate) fn vstack_push(&mut self, vvar: &str, vtype: VariableType) {
-        self.vstack.push((vtype, vvar.into()))
+        self.vstack.push(StackValue {
+            vtype,
+            value: vvar.into(),
+            reads: None,
+        })
+    }
+    /// Pushes the value of local `id` of `kind`. It is loaded lazily, when the value is used, unless the local changes before that.
+    pub(crate) fn push_local(&mut self, id: u16, kind: LocalKind) {
+        let (local, _) = self.get_local(id, kind);
+        let (value, vtype) = self.load_local(id, kind);
+        self.vstack.push(StackValue {
+            vtype,
+            value,
+            reads: Some(local),
+        })
+    }
+    /// Copies the topmost `copied` values of the operand stack, and inserts the copies below the `skipped` values under them(`dup_x1` copies
+    /// 1 value and skips 1).
+    pub(crate) fn vstack_dup(&mut self, copied: usize, skipped: usize) {
+        let top = self.vstack.len() - copied;
+        let copies = self.vstack[top..].to_vec();
+        self.vstack.splice(top - skipped..top - skipped, copies);
+    }
+    /// Swaps the two topmost values of the operand stack.
+    pub(crate) fn vstack_swap(&mut self) {
+        let top = self.vstack.len() - 1;
+        self.vstack.swap(top, top - 1);
+    }
+    /// Returns the type of the value `depth` values below the top of the operand stack.
+    pub(crate) fn vstack_type(&self, depth: usize) -> &VariableType {
+        &self.vstack[self.vstack.len() - 1 - depth].vtype
     }
     pub(crate) fn get_intermidiate(&mut self) -> IString {
         let im = format!("i{id}", id = self.im_id);
@@ -126,16 +475,63 @@ impl MethodWriter {
         println!("vstack:{:?}", self.vstack);
     }
     pub(crate) fn vstack_pop(&mut self) -> Option<(VariableType, IString)> {
-        self.vstack.pop()
+        self.vstack.pop().map(|value| (value.vtype, value.value))
     }
-    pub(crate) fn final_code(&self) -> IString {
-        format!(
-            "{includes}{sig}{{\n{local_decl}{code}}}",
+    /// Evaluates values on the stack which read `local` into intermediates, so that they keep the value it had when they were pushed,
+    /// before it is changed. Loads of locals are lazy, so `arr[i++]` would otherwise read `i` after its increment.
+    pub(crate) fn materialize_reads_of(&mut self, local: &str) {
+        for index in 0..self.vstack.len() {
+            if self.vstack[index].reads.as_deref() != Some(local) {
+                continue;
+            }
+            let im_name = self.get_intermidiate();
+            let StackValue { vtype, value, .. } = &self.vstack[index];
+            let code = format!("{ctype} {im_name} = {value};", ctype = vtype.c_type());
+            self.write_raw(&code);
+            self.vstack[index].value = im_name;
+            self.vstack[index].reads = None;
+        }
+    }
+    pub(crate) fn vstack_clear(&mut self) {
+        self.vstack.clear()
+    }
+    /// Returns the code of the method, and the op each of its lines was written for(`None` for lines not written for any op).
+    pub(crate) fn final_code(&self) -> (IString, Vec<Option<usize>>) {
+        let head = format!(
+            "{includes}{sig}{{\n{local_decl}",
             includes = self.includes.get_code(),
-            code = self.code,
             sig = self.sig,
             local_decl = self.local_decl
-        )
-        .into()
+        );
+        let mut ops = vec![None; head.matches('\n').count()];
+        let mut op_starts = self.op_starts.iter().peekable();
+        let mut op = None;
+        let mut offset = 0;
+        for line in self.code.split_inclusive('\n') {
+            while let Some((_, start_op)) = op_starts.next_if(|(start, _)| *start <= offset) {
+                op = Some(*start_op);
+            }
+            ops.push(op);
+            offset += line.len();
+        }
+        (format!("{head}{code}}}", code = self.code).into(), ops)
     }
-}
\ No newline at end of file
+}#[test]
+fn materialized_reads() {
+    let method = crate::Method::from_ops("Test", "test__V", false, Vec::new());
+    let options = CodegenOptions {
+        null_checks: true,
+        bounds_checks: true,
+        stack_traces: false,
+        line_directives: false,
+    };
+    let mut writer = MethodWriter::new(None, &[], &method, options);
+    // Copies of a load read the same local, while loads of other locals keep being lazy.
+    writer.push_local(1, LocalKind::Int);
+    writer.vstack_dup(1, 0);
+    writer.push_local(11, LocalKind::Int);
+    writer.materialize_reads_of("l1i");
+    let values: Vec<IString> = std::iter::from_fn(|| writer.vstack_pop().map(|(_, value)| value)).collect();
+    assert_eq!(values, ["l11i".into(), "i1".into(), "i0".into()] as [IString; 3]);
+    assert_eq!(&*writer.final_code().0, "{\n\tint32_t i0 = l1i;\n\tint32_t i1 = l1i;\n}");
+}
@@ -1,9 +1,24 @@
+use crate::class::{find_class, ConstantValue};
 use crate::{Class, IString};
 use std::collections::HashSet;
 use std::io::Write;
 mod method_writer;
 pub mod method;
+mod source_map;
 pub(crate) use method::create_method_impl;
+pub(crate) use source_map::SourceWriter;
+/// Runtime checks and debug information emitted in translated methods.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CodegenOptions {
+    /// Throw `java.lang.NullPointerException` instead of dereferencing null.
+    pub(crate) null_checks: bool,
+    /// Throw `java.lang.ArrayIndexOutOfBoundsException` instead of accessing arrays out of bounds.
+    pub(crate) bounds_checks: bool,
+    /// Keep a shadow call stack, so exceptions record Java stack traces.
+    pub(crate) stack_traces: bool,
+    /// Emit `#line` directives, so compiler diagnostics and debuggers point to Java source.
+    pub(crate) line_directives: bool,
+}
 pub(self) struct IncludeBuilder {
     header: String,
     includes: HashSet<IString>,
@@ -47,6 +62,71 @@ impl IncludeBuilder {
         &self.header
     }
 }
+/// Escapes `text`, so it can be used inside a C++ string literal.
+fn escape_cpp_string(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for curr in text.chars() {
+        match curr {
+            '\\' => res.push_str("\\\\"),
+            '"' => res.push_str("\\\""),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            _ if (curr as u32) < 0x20 || (curr as u32) > 0x7e => {
+                let mut units = [0; 2];
+                for unit in curr.encode_utf16(&mut units) {
+                    res.push_str(&format!("\\x{unit:04x}\" u\""));
+                }
+            }
+            _ => res.push(curr),
+        }
+    }
+    res
+}
+/// Converts `value` into a C++ expression, which can be used to initialize a static field.
+fn constant_initializer(value: &ConstantValue) -> IString {
+    match value {
+        // Like with `long`, `-2147483648` would be the negation of a `long` literal.
+        ConstantValue::Int(i32::MIN) => "(-2147483647 - 1)".into(),
+        ConstantValue::Int(value) => format!("{value}").into(),
+        // `-9223372036854775808` is not a valid C++ literal, since `9223372036854775808` does not fit in a `long long`.
+        ConstantValue::Long(i64::MIN) => "(-9223372036854775807LL - 1)".into(),
+        ConstantValue::Long(value) => format!("{value}LL").into(),
+        ConstantValue::Float(value) if value.is_nan() => "NAN".into(),
+        ConstantValue::Float(value) if value.is_infinite() => {
+            if value.is_sign_positive() { "INFINITY".into() } else { "-INFINITY".into() }
+        }
+        ConstantValue::Float(value) => format!("{value:?}f").into(),
+        ConstantValue::Double(value) if value.is_nan() => "((double)NAN)".into(),
+        ConstantValue::Double(value) if value.is_infinite() => {
+            if value.is_sign_positive() { "((double)INFINITY)".into() } else { "((double)-INFINITY)".into() }
+        }
+        ConstantValue::Double(value) => format!("{value:?}").into(),
+        ConstantValue::String(value) => format!(
+            "managed_from_raw(new java::lang::String(u\"{value}\"))",
+            value = escape_cpp_string(value)
+        )
+        .into(),
+    }
+}
+#[test]
+fn constant_initializers() {
+    assert_eq!(&*constant_initializer(&ConstantValue::Int(-7)), "-7");
+    assert_eq!(&*constant_initializer(&ConstantValue::Int(i32::MIN)), "(-2147483647 - 1)");
+    assert_eq!(&*constant_initializer(&ConstantValue::Long(1 << 40)), "1099511627776LL");
+    assert_eq!(
+        &*constant_initializer(&ConstantValue::Long(i64::MIN)),
+        "(-9223372036854775807LL - 1)"
+    );
+    assert_eq!(&*constant_initializer(&ConstantValue::Float(1.5)), "1.5f");
+    assert_eq!(&*constant_initializer(&ConstantValue::Float(f32::NEG_INFINITY)), "-INFINITY");
+    assert_eq!(&*constant_initializer(&ConstantValue::Double(1e-5)), "1e-5");
+    assert_eq!(&*constant_initializer(&ConstantValue::Double(f64::NAN)), "((double)NAN)");
+    assert_eq!(
+        &*constant_initializer(&ConstantValue::String("a\"b".into())),
+        "managed_from_raw(new java::lang::String(u\"a\\\"b\"))"
+    );
+}
 fn push_method_sig(target: &mut String, method_name: &str, method: &crate::Method) {
     target.push_str(&format!(
         "{ret} {method_name}(",
@@ -63,7 +143,97 @@ fn push_method_sig(target: &mut String, method_name: &str, method: &crate::Metho
     }
     target.push(')');
 }
-pub(crate) fn create_header<W: Write>(out: &mut W, class: &Class) -> std::io::Result<()> {
+fn push_forwarder(target: &mut String, method_name: &str, method: &crate::Method, implementor: &str) {
+    target.push_str(&format!(
+        "\tvirtual {ret} {method_name}(",
+        ret = method.ret_val().c_type()
+    ));
+    let arg_names: Vec<String> = (0..method.args().len()).map(|arg| format!("a{arg}")).collect();
+    let args: Vec<String> = method
+        .args()
+        .iter()
+        .zip(arg_names.iter())
+        .map(|(arg, name)| format!("{ctype} {name}", ctype = arg.c_type()))
+        .collect();
+    target.push_str(&args.join(","));
+    target.push_str(&format!(
+        "){{return {implementor}::{method_name}({args});}}\n",
+        args = arg_names.join(",")
+    ));
+}
+/// Returns all interfaces `class` implements, including interfaces extended by those interfaces.
+fn all_interfaces<'a>(class: &'a Class, classes: &'a [Class]) -> Vec<&'a Class> {
+    let mut ifaces: Vec<&Class> = Vec::new();
+    let mut to_visit: Vec<&str> = class.interfaces().iter().map(|iface| iface.cpp_class()).collect();
+    while let Some(iface) = to_visit.pop() {
+        let Some(iface) = find_class(classes, iface) else {
+            continue;
+        };
+        if ifaces.iter().any(|visited| visited.cpp_name() == iface.cpp_name()) {
+            continue;
+        }
+        to_visit.extend(iface.interfaces().iter().map(|iface| iface.cpp_class()));
+        ifaces.push(iface);
+    }
+    ifaces
+}
+/// Finds interface methods `class` inherits an implementation of, either from its superclass or from a default method of another interface.
+/// C++ does not consider such a method to override the interface method, so a forwarding method must be generated. Like in Java, a
+/// method of a superclass takes precedence over default methods, and a default method over the ones of interfaces it overrides.
+fn inherited_iface_impls<'a>(
+    class: &'a Class,
+    classes: &'a [Class],
+) -> Vec<(&'a str, &'a crate::Method, &'a str)> {
+    let ifaces = all_interfaces(class, classes);
+    let mut impls: Vec<(&str, &crate::Method, &str)> = Vec::new();
+    for iface in ifaces.iter() {
+        for (method_name, method) in iface.virtual_methods() {
+            if class.virtual_method(method_name).is_some() || impls.iter().any(|(name, _, _)| name == &&**method_name) {
+                continue;
+            }
+            let mut declaring = None;
+            let mut parrent = find_class(classes, class.parrent_cpp_name());
+            while let Some(curr) = parrent {
+                if let Some(method) = curr.virtual_method(method_name) {
+                    declaring = Some((curr.cpp_name(), method));
+                    break;
+                }
+                parrent = find_class(classes, curr.parrent_cpp_name());
+            }
+            let implementor = match declaring {
+                Some((parrent, method)) => (!method.is_abstract()).then_some(parrent),
+                None => most_specific_default(&ifaces, method_name, classes),
+            };
+            if let Some(implementor) = implementor {
+                impls.push((method_name, method, implementor));
+            }
+        }
+    }
+    impls
+}
+/// Returns the interface among `ifaces` with a default method `method_name`, which does not extend any other interface with one.
+fn most_specific_default<'a>(ifaces: &[&'a Class], method_name: &str, classes: &'a [Class]) -> Option<&'a str> {
+    let defaults: Vec<&Class> = ifaces
+        .iter()
+        .copied()
+        .filter(|iface| iface.virtual_method(method_name).is_some_and(|method| !method.is_abstract()))
+        .collect();
+    defaults
+        .iter()
+        .find(|candidate| {
+            !defaults.iter().any(|other| {
+                all_interfaces(other, classes)
+                    .iter()
+                    .any(|extended| extended.cpp_name() == candidate.cpp_name())
+            })
+        })
+        .map(|iface| iface.cpp_name())
+}
+pub(crate) fn create_header<W: Write>(
+    out: &mut W,
+    class: &Class,
+    classes: &[Class],
+) -> std::io::Result<()> {
     let mut includes = IncludeBuilder::new(&*class.path());
     includes.add_include(&*class.parrent_path());
     let mut class_methods = String::new();
@@ -90,11 +260,29 @@ pub(crate) fn create_header<W: Write>(out: &mut W, class: &Class) -> std::io::Re
                 includes.add_include(&dep);
             }
         }
+        if method.is_abstract() {
+            class_methods.push_str(" = 0");
+        }
         class_methods.push_str(";\n");
         if let Some(dep) = method.ret_val().dependency() {
             includes.add_include(&dep);
         }
     }
+    for (method_name, method, implementor) in inherited_iface_impls(class, classes) {
+        push_forwarder(&mut class_methods, method_name, method, implementor);
+        includes.add_include(&crate::class::cpp_class_to_path(implementor));
+    }
+    // Every class declares `_ensure_clinit_`, which runs its static initializer on first use. Classes without one only initialize their superclass.
+    if class.clinit().is_some() {
+        class_methods.push_str("\tstatic ClassInitializer _clinit_state_;\n\tstatic void _run_clinit_();\n\tstatic void _ensure_clinit_(){_clinit_state_.ensure(_run_clinit_);}\n");
+    } else if class.is_interface() {
+        class_methods.push_str("\tstatic void _ensure_clinit_(){}\n");
+    } else {
+        class_methods.push_str(&format!(
+            "\tstatic void _ensure_clinit_(){{{super_name}::_ensure_clinit_();}}\n",
+            super_name = class.parrent_cpp_name()
+        ));
+    }
     let mut class_fields = String::new();
     for (field_name, field_type) in class.static_fields() {
         class_fields.push_str(&format!(
@@ -124,7 +312,12 @@ pub(crate) fn create_header<W: Write>(out: &mut W, class: &Class) -> std::io::Re
         ifaces_list.push_str(iface.cpp_class());
         includes.add_include(&iface.class_path());
     }
-    let iface_inherit = if class.is_interface() { "virtual" } else { "" };
+    // `java::lang::Object` is always inherited virtually, so that classes implementing interfaces contain only one `Object`.
+    let iface_inherit = if class.is_interface() || class.parrent_cpp_name() == "java::lang::Object" {
+        "virtual"
+    } else {
+        ""
+    };
     write!(
         out,
         "#pragma once\n{includes}\nstruct {class_name}: public {iface_inherit} {super_name}{ifaces_list}\n{{\nvirtual ~{class_name}() = default;\n{class_fields}{class_methods}}};",
@@ -132,3 +325,35 @@ pub(crate) fn create_header<W: Write>(out: &mut W, class: &Class) -> std::io::Re
         super_name = class.parrent_cpp_name()
     )
 }
+/// Writes definitions of static fields of `class`, initializing compile-time constants, and the code running its static initializer.
+pub(crate) fn create_statics<W: Write>(out: &mut W, class: &Class) -> std::io::Result<()> {
+    let class_name = class.cpp_name();
+    for (static_name, static_type) in class.static_fields() {
+        match class.constant_value(static_name) {
+            Some(value) => writeln!(
+                out,
+                "{ctype} {class_name}::{static_name} = {value};",
+                ctype = static_type.c_type(),
+                value = constant_initializer(value)
+            )?,
+            None => writeln!(
+                out,
+                "{ctype} {class_name}::{static_name};",
+                ctype = static_type.c_type()
+            )?,
+        }
+    }
+    if let Some(clinit) = class.clinit() {
+        // `_clinit_state_` lets the initializing thread re-enter, and makes other threads wait for `<clinit>` to finish(JVMS §5.5).
+        let init_super = if class.is_interface() {
+            String::new()
+        } else {
+            format!("\t{super_name}::_ensure_clinit_();\n", super_name = class.parrent_cpp_name())
+        };
+        writeln!(
+            out,
+            "ClassInitializer {class_name}::_clinit_state_(typeid({class_name}));\nvoid {class_name}::_run_clinit_(){{\n{init_super}\t{clinit}();\n}}"
+        )?;
+    }
+    Ok(())
+}
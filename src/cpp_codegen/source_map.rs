@@ -0,0 +1,161 @@
+//! Maps lines of generated C++ code to the Java source they were translated from.
+use crate::IString;
+use std::io::Write;
+/// Line of a generated C++ file, translated from the op at `pc` of a Java method(or generated for the method, if `pc` is `None`).
+struct MappedLine {
+    cpp_line: usize,
+    class: IString,
+    method: IString,
+    pc: Option<u16>,
+    java_line: Option<u16>,
+}
+/// Writer of a generated C++ source file, which counts lines written to it and records which Java source lines of translated methods
+/// come from.
+pub(crate) struct SourceWriter<W: Write> {
+    out: W,
+    /// Name of the written file(e.g. `Foo.cpp`).
+    file: IString,
+    /// Number of lines written so far.
+    lines: usize,
+    mapped: Vec<MappedLine>,
+}
+/// Splits the qualified Java name of `method` into the names of its class and of the method.
+fn class_and_method(method: &crate::Method) -> (&str, &str) {
+    method.java_name().rsplit_once('.').unwrap_or(("", method.java_name()))
+}
+/// Returns `path` quoted, for use in a `#line` directive.
+fn quoted_path(path: &str) -> String {
+    format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+}
+/// Returns `code` with `#line` directives pointing to `path` inserted before lines whose Java line(in `java_lines`, by line of
+/// `code`) differs from the one of the previous directive, and the index each line of `code` has in it. Lines following a directive
+/// without one of their own are counted by the compiler as the Java lines after it.
+fn with_line_directives(code: &str, java_lines: &[Option<u16>], path: &str) -> (String, Vec<usize>) {
+    let mut text = String::with_capacity(code.len());
+    let mut positions = Vec::with_capacity(java_lines.len());
+    let mut position = 0;
+    let mut directive_line = None;
+    for (line, java_line) in code.split_inclusive('\n').zip(java_lines.iter().chain(std::iter::repeat(&None))) {
+        if java_line.is_some() && *java_line != directive_line {
+            text.push_str(&format!("#line {} {path}\n", java_line.unwrap(), path = quoted_path(path)));
+            position += 1;
+            directive_line = *java_line;
+        }
+        text.push_str(line);
+        positions.push(position);
+        position += 1;
+    }
+    (text, positions)
+}
+#[test]
+fn line_directives_are_not_repeated() {
+    let code = "int32_t i0 = a;\nint32_t i1 = b;\n//comment\nreturn i1;\nreturn i0;\n";
+    let (text, positions) = with_line_directives(code, &[Some(3), Some(3), None, Some(3), Some(4)], "Foo.java");
+    assert_eq!(
+        text,
+        "#line 3 \"Foo.java\"\nint32_t i0 = a;\nint32_t i1 = b;\n//comment\nreturn i1;\n#line 4 \"Foo.java\"\nreturn i0;\n"
+    );
+    assert_eq!(positions, [1, 2, 3, 4, 6]);
+}
+impl<W: Write> SourceWriter<W> {
+    pub(crate) fn new(out: W, file: &str) -> Self {
+        Self {
+            out,
+            file: file.into(),
+            lines: 0,
+            mapped: Vec::new(),
+        }
+    }
+    /// Writes `code` of `method`, where `ops` are the ops each line of `code` was written for(`None` for lines not translated from
+    /// any op). With `line_directives`, lines translated from ops are preceded by `#line` directives pointing to their Java source,
+    /// and line numbers of the C++ file are restored after the method.
+    pub(crate) fn write_method(
+        &mut self,
+        method: &crate::Method,
+        code: &str,
+        ops: &[Option<usize>],
+        line_directives: bool,
+    ) -> std::io::Result<()> {
+        let (class, method_name) = class_and_method(method);
+        // Source files are looked up in directories of their packages, like in a jar of sources.
+        let source_path = method.source_file().map(|source_file| match class.rsplit_once('.') {
+            Some((package, _)) => format!("{}/{source_file}", package.replace('.', "/")),
+            None => source_file.to_owned(),
+        });
+        // Comments describing ops are not translated from them.
+        let ops: Vec<Option<usize>> = code
+            .split_inclusive('\n')
+            .zip(ops.iter().chain(std::iter::repeat(&None)))
+            .map(|(line, op)| op.filter(|_| !line.trim_start().starts_with("//")))
+            .collect();
+        let java_lines: Vec<Option<u16>> = ops.iter().map(|op| op.and_then(|op| method.lines().get(op).copied().flatten())).collect();
+        let (mut text, positions) = match (line_directives, &source_path) {
+            (true, Some(path)) => with_line_directives(code, &java_lines, path),
+            _ => (code.to_owned(), (0..ops.len()).collect()),
+        };
+        for ((op, java_line), position) in ops.iter().zip(java_lines).zip(positions) {
+            if let Some(op) = op {
+                self.mapped.push(MappedLine {
+                    cpp_line: self.lines + position + 1,
+                    class: class.into(),
+                    method: method_name.into(),
+                    pc: Some(method.pcs()[*op]),
+                    java_line,
+                });
+            }
+        }
+        // Directives were inserted, so line numbers of the C++ file are restored. The last line may be unterminated.
+        if text.len() != code.len() {
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+            let next = self.lines + text.matches('\n').count() + 2;
+            text.push_str(&format!("#line {next} {file}\n", file = quoted_path(&self.file)));
+        }
+        self.write_all(text.as_bytes())
+    }
+    /// Writes `code` of the C++ `main` function, which calls `method`. Its lines are mapped to the first source line of `method`.
+    pub(crate) fn write_entry_point(&mut self, method: &crate::Method, code: &str) -> std::io::Result<()> {
+        let (class, method_name) = class_and_method(method);
+        let java_line = method.lines().first().copied().flatten();
+        for position in 0..code.split_inclusive('\n').count() {
+            self.mapped.push(MappedLine {
+                cpp_line: self.lines + position + 1,
+                class: class.into(),
+                method: method_name.into(),
+                pc: None,
+                java_line,
+            });
+        }
+        self.write_all(code.as_bytes())
+    }
+    /// Writes the source map of this file to `out`: a header, followed by lines of this file generated for Java methods, one per line,
+    /// as tab-separated values. Those are the C++ file and line, the Java class and method, and the bytecode offset and Java source
+    /// line(`-` if unknown) of the op the line was translated from.
+    pub(crate) fn write_map(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "cpp_file\tcpp_line\tclass\tmethod\tpc\tjava_line")?;
+        for line in &self.mapped {
+            let java_line = line.java_line.map_or("-".to_owned(), |line| line.to_string());
+            let pc = line.pc.map_or("-".to_owned(), |pc| pc.to_string());
+            writeln!(
+                out,
+                "{file}\t{cpp_line}\t{class}\t{method}\t{pc}\t{java_line}",
+                file = self.file,
+                cpp_line = line.cpp_line,
+                class = line.class,
+                method = line.method,
+            )?;
+        }
+        Ok(())
+    }
+}
+impl<W: Write> Write for SourceWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.out.write(buf)?;
+        self.lines += buf[..written].iter().filter(|byte| **byte == b'\n').count();
+        Ok(written)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.out.flush()
+    }
+}
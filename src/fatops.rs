@@ -1,5 +1,5 @@
 use super::{field_descriptor_to_ftype, method_desc_to_args, VariableType};
-use crate::importer::{opcodes::OpCode, ImportedJavaClass};
+use crate::importer::{opcodes::OpCode, CodeException, ImportedJavaClass};
 use crate::mangle_method_name;
 use crate::IString;
 use smallvec::*;
@@ -30,14 +30,141 @@ fn methodref_to_name_and_sig(
     //let method_id = self.code_container.lookup_or_insert_method(&mangled);
     (method_class.into(), mangled, args, ret)
 }
+/// Expands a call to `LambdaMetafactory.metafactory`(or `altMetafactory`), which creates an implementation of a functional interface.
+fn expand_lambda(
+    name_and_type_index: u16,
+    captured: Vec<VariableType>,
+    interface: VariableType,
+    bootstrap_args: &[u16],
+    class: &ImportedJavaClass,
+) -> FatOp {
+    let interface = match interface {
+        VariableType::ObjectRef(interface) => interface,
+        _ => panic!("Lambda must implement an interface, not {interface:?}!"),
+    };
+    // Bootstrap args are: erased signature of the interface method, the implementation method handle, and the instantiated signature.
+    let (name, _) = class.lookup_nametype(name_and_type_index).unwrap();
+    let name = class.lookup_utf8(name).unwrap();
+    let method_desc = class.lookup_method_type(bootstrap_args[0]).unwrap();
+    let (args, ret) = method_desc_to_args(method_desc);
+    let (reference_kind, reference_index) = class.lookup_method_handle(bootstrap_args[1]).unwrap();
+    FatOp::Lambda {
+        interface,
+        method: mangle_method_name(name, method_desc),
+        args: args.into(),
+        ret,
+        captured: captured.into(),
+        target: DynamicMethodHandle::from_kind(reference_kind, reference_index, class),
+    }
+}
+/// Part of a string concatenation recipe.
+#[derive(Debug, Clone)]
+pub(crate) enum ConcatPart {
+    Literal(IString),
+    Arg(VariableType),
+}
+/// Returns `value` as `Float.toString` or `Double.toString` would: in decimal notation if its magnitude is in [10^-3, 10^7), and in
+/// computerized scientific notation otherwise. `shortest` is the shortest decimal text identifying the value in its precision.
+fn java_float_text(value: f64, shortest: impl Fn(bool) -> String) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.into()
+    } else if value == 0.0 || (1e-3..1e7).contains(&value.abs()) {
+        let text = shortest(false);
+        if text.contains('.') { text } else { format!("{text}.0") }
+    } else {
+        let text = shortest(true);
+        let (mantissa, exponent) = text.split_once('e').unwrap();
+        if mantissa.contains('.') {
+            format!("{mantissa}E{exponent}")
+        } else {
+            format!("{mantissa}.0E{exponent}")
+        }
+    }
+}
+#[test]
+fn java_float_texts() {
+    let double = |value: f64| java_float_text(value, |scientific| if scientific { format!("{value:e}") } else { value.to_string() });
+    assert_eq!(double(1.0), "1.0");
+    assert_eq!(double(-0.0), "-0.0");
+    assert_eq!(double(0.001), "0.001");
+    assert_eq!(double(1e7), "1.0E7");
+    assert_eq!(double(1.5e-5), "1.5E-5");
+    assert_eq!(double(f64::NEG_INFINITY), "-Infinity");
+}
+/// Converts a constant used in a string concatenation recipe to its text, as `String.valueOf` would. Other constants(like
+/// `MethodHandle`s) have no text known at translation time, so they can't be translated.
+fn concat_constant_to_text(index: u16, class: &ImportedJavaClass) -> std::io::Result<String> {
+    use crate::importer::ConstantItem;
+    Ok(match class.lookup_item(index).unwrap() {
+        ConstantItem::ConstString { string_index } => class.lookup_utf8(*string_index).unwrap().to_owned(),
+        ConstantItem::Intiger(int) => int.to_string(),
+        ConstantItem::Long(long) => long.to_string(),
+        ConstantItem::Float(float) => {
+            java_float_text(*float as f64, |scientific| if scientific { format!("{float:e}") } else { float.to_string() })
+        }
+        ConstantItem::Double(double) => {
+            java_float_text(*double, |scientific| if scientific { format!("{double:e}") } else { double.to_string() })
+        }
+        // Classes are assumed not to be interfaces, which would be written as `interface Name`.
+        ConstantItem::Class { name_index } => format!("class {}", class.lookup_utf8(*name_index).unwrap().replace('/', ".")),
+        constant => {
+            return Err(std::io::Error::other(format!(
+                "Constant {constant:?} of a string concatenation can't be converted to text!"
+            )))
+        }
+    })
+}
+/// Expands a call to `StringConcatFactory.makeConcatWithConstants`(or `makeConcat`) into a `FatOp::StringConcat`.
+fn expand_string_concat(
+    args: Vec<VariableType>,
+    with_constants: bool,
+    bootstrap_args: &[u16],
+    class: &ImportedJavaClass,
+) -> std::io::Result<FatOp> {
+    if !with_constants {
+        return Ok(FatOp::StringConcat(args.into_iter().map(ConcatPart::Arg).collect()));
+    }
+    // In the recipe, '\u{1}' marks an argument, and '\u{2}' marks a constant taken from the rest of bootstrap args.
+    let recipe = concat_constant_to_text(bootstrap_args[0], class)?;
+    let mut args = args.into_iter();
+    let mut constants = bootstrap_args[1..].iter();
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    for curr in recipe.chars() {
+        match curr {
+            '\u{1}' => {
+                if !literal.is_empty() {
+                    parts.push(ConcatPart::Literal(std::mem::take(&mut literal).into()));
+                }
+                parts.push(ConcatPart::Arg(args.next().expect("Concat recipe has more arguments than its call site!")));
+            }
+            '\u{2}' => {
+                let constant = constants.next().expect("Concat recipe has more constants than its bootstrap method!");
+                literal.push_str(&concat_constant_to_text(*constant, class)?);
+            }
+            _ => literal.push(curr),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(ConcatPart::Literal(literal.into()));
+    }
+    Ok(FatOp::StringConcat(parts.into()))
+}
+/// Converts a class name from the constant pool(which is an array descriptor for array classes) to a `VariableType`.
+pub(crate) fn class_name_to_ftype(class_name: &str) -> VariableType {
+    if class_name.starts_with('[') {
+        crate::field_desc_str_to_ftype(class_name, 0)
+    } else {
+        VariableType::ObjectRef(ClassInfo::from_java_path(class_name))
+    }
+}
 #[derive(Debug, Clone, PartialEq)]
 pub struct ClassInfo {
     cpp_class: IString,
 }
 impl ClassInfo {
-    pub fn from_raw(cpp_class:&str)->Self{
-        Self{cpp_class:cpp_class.into()}
-    }
     pub fn from_java_path(java_path: &str) -> Self {
         let cpp_class = crate::class::java_class_to_cpp_class(java_path);
         //assert!(!cpp_class.contains('_'),"cpp_class: {java_path} {cpp_class}");
@@ -57,6 +184,43 @@ impl ClassInfo {
     pub fn class_path(&self) -> IString {
         crate::class::cpp_class_to_path(&self.cpp_class)
     }
+    pub fn java_path(&self) -> IString {
+        self.cpp_class.replace("::", "/").into()
+    }
+}
+/// Pushes `class_info` to `classes`, or its element class if it is an array class(like `[Ljava/lang/String;`). Arrays of primitives
+/// refer to no class.
+fn push_class(class_info: &ClassInfo, classes: &mut Vec<ClassInfo>) {
+    let java_path = class_info.java_path();
+    if java_path.starts_with('[') {
+        push_vtype_class(&class_name_to_ftype(&java_path), classes);
+    } else if !class_info.is_unknown() {
+        classes.push(class_info.clone());
+    }
+}
+/// Pushes the class `vtype` refers to(the element class, for arrays) to `classes`.
+pub(crate) fn push_vtype_class(vtype: &VariableType, classes: &mut Vec<ClassInfo>) {
+    match vtype {
+        VariableType::ObjectRef(class_info) => push_class(class_info, classes),
+        VariableType::ArrayRef(element) => push_vtype_class(element, classes),
+        _ => (),
+    }
+}
+#[test]
+fn array_classes_refer_to_elements() {
+    let mut classes = Vec::new();
+    for name in ["[Ljava/lang/String;", "[[LVector3;", "[I", "Vector3"] {
+        FatOp::ClassConst(ClassInfo::from_java_path(name)).referenced_classes(&mut classes);
+    }
+    let names: Vec<IString> = classes.iter().map(ClassInfo::java_path).collect();
+    assert_eq!(names, ["java/lang/String".into(), "Vector3".into(), "Vector3".into()] as [IString; 3]);
+}
+/// Pushes the class owning a method, and classes used in its signature, to `classes`.
+fn push_sig_classes(class_info: &ClassInfo, args: &[VariableType], ret: &VariableType, classes: &mut Vec<ClassInfo>) {
+    push_class(class_info, classes);
+    for arg in args.iter().chain(std::iter::once(ret)) {
+        push_vtype_class(arg, classes);
+    }
 }
 #[derive(Debug, Clone)]
 pub(crate) enum DynamicMethodHandle{
@@ -64,30 +228,57 @@ pub(crate) enum DynamicMethodHandle{
     GetStatic(u16),
     PutField(u16),
     PutStatic(u16),
-    InvokeVirtual(u16),
-    NewInvokeSpecial(u16),
+    InvokeVirtual(ClassInfo, IString, Box<[VariableType]>, VariableType),
+    /// Constructor reference, creates a new object of type `ClassInfo` and initializes it using the constructor `IString`.
+    NewInvokeSpecial(ClassInfo, IString, Box<[VariableType]>),
     InvokeStatic(ClassInfo, IString, Box<[VariableType]>, VariableType),
-    InvokeSpecial(u16),
-    InvokeInterface(u16),
+    InvokeSpecial(ClassInfo, IString, Box<[VariableType]>, VariableType),
+    InvokeInterface(ClassInfo, IString, Box<[VariableType]>, VariableType),
 }
 impl DynamicMethodHandle{
+    /// Pushes classes this method handle refers to to `classes`.
+    pub(crate) fn referenced_classes(&self, classes: &mut Vec<ClassInfo>) {
+        match self {
+            Self::InvokeVirtual(class_info, _, args, ret)
+            | Self::InvokeStatic(class_info, _, args, ret)
+            | Self::InvokeSpecial(class_info, _, args, ret)
+            | Self::InvokeInterface(class_info, _, args, ret) => push_sig_classes(class_info, args, ret, classes),
+            Self::NewInvokeSpecial(class_info, _, args) => push_sig_classes(class_info, args, &VariableType::Void, classes),
+            Self::GetField(_) | Self::GetStatic(_) | Self::PutField(_) | Self::PutStatic(_) => (),
+        }
+    }
     fn from_kind(kind:u8,index:u16,jc:&ImportedJavaClass)->Self{
+        let method_ref = || {
+            let (class, name, args, ret) = methodref_to_name_and_sig(index, jc);
+            (ClassInfo::from_java_path(&class), name, args.into(), ret)
+        };
         match kind{
             0=>panic!("Invalid reference kind (0) of a method handle!"),
             1=>Self::GetField(index),
             2=>Self::GetStatic(index),
             3=>Self::PutField(index),
             4=>Self::PutStatic(index),
-            5=>Self::InvokeVirtual(index),
+            5=>{
+                let (class_info, name, args, ret) = method_ref();
+                Self::InvokeVirtual(class_info, name, args, ret)
+            }
             6=>{
-                let (class, name, args, ret) = methodref_to_name_and_sig(index, jc);
-                let class_info = ClassInfo::from_java_path(&class);
-                Self::InvokeStatic(class_info,name,args.into(),ret)
+                let (class_info, name, args, ret) = method_ref();
+                Self::InvokeStatic(class_info, name, args, ret)
+            }
+            7=>{
+                let (class_info, name, args, ret) = method_ref();
+                Self::InvokeSpecial(class_info, name, args, ret)
+            }
+            8=>{
+                let (class_info, name, args, _ret) = method_ref();
+                Self::NewInvokeSpecial(class_info, name, args)
+            }
+            9=>{
+                let (class_info, name, args, ret) = method_ref();
+                Self::InvokeInterface(class_info, name, args, ret)
             }
-            7=>Self::InvokeSpecial(index),
-            8=>Self::NewInvokeSpecial(index),
-            9=>Self::InvokeInterface(index),
-            9.. =>panic!("Invalid reference kind ({kind}) of a method handle!"),
+            10.. =>panic!("Invalid reference kind ({kind}) of a method handle!"),
         }
     }
 }
@@ -102,16 +293,16 @@ pub(crate) enum FatOp {
     ClassConst(ClassInfo),
     FConst(f32),
     DConst(f64),
-    ALoad(u8),
-    DLoad(u8),
-    FLoad(u8),
-    ILoad(u8),
-    LLoad(u8),
-    AStore(u8),
-    DStore(u8),
-    FStore(u8),
-    IStore(u8),
-    LStore(u8),
+    ALoad(u16),
+    DLoad(u16),
+    FLoad(u16),
+    ILoad(u16),
+    LLoad(u16),
+    AStore(u16),
+    DStore(u16),
+    FStore(u16),
+    IStore(u16),
+    LStore(u16),
     DAdd,
     FAdd,
     IAdd,
@@ -152,6 +343,18 @@ pub(crate) enum FatOp {
     InvokeStatic(ClassInfo, IString, Box<[VariableType]>, VariableType),
     InvokeInterface(ClassInfo, IString, Box<[VariableType]>, VariableType), //Unfinshed
     InvokeDynamic(DynamicMethodHandle, IString, Box<[VariableType]>, VariableType), //Temporarly ignored(Hard to parse)
+    /// Pops values of `ConcatPart::Arg`s from the stack, and concatenates them with the literal parts into a new string.
+    StringConcat(Box<[ConcatPart]>),
+    /// Creates an object implementing functional `interface`, whose `method` forwards to `target`. The `captured` values are popped from the stack,
+    /// and passed to `target` before the arguments of `method`.
+    Lambda {
+        interface: ClassInfo,
+        method: IString,
+        args: Box<[VariableType]>,
+        ret: VariableType,
+        captured: Box<[VariableType]>,
+        target: DynamicMethodHandle,
+    },
     InvokeVirtual(ClassInfo, IString, Box<[VariableType]>, VariableType),
     ZGetStatic(ClassInfo, IString),
     BGetStatic(ClassInfo, IString),
@@ -257,7 +460,8 @@ pub(crate) enum FatOp {
     L2D,
     New(ClassInfo),
     ANewArray(ClassInfo),
-    MultiANewArray(ClassInfo, u8),
+    /// Allocates an array of type `VariableType`, with the outermost `u8` dimensions specified.
+    MultiANewArray(VariableType, u8),
     BNewArray,
     CNewArray,
     DNewArray,
@@ -266,8 +470,10 @@ pub(crate) enum FatOp {
     LNewArray,
     SNewArray,
     ZNewArray,
-    CheckedCast(ClassInfo),
-    InstanceOf(ClassInfo),
+    /// Casts the reference on top of the stack to `VariableType`, throwing `ClassCastException` if it is not null and not an instance of it.
+    CheckedCast(VariableType),
+    /// Pushes 1 if the reference on top of the stack is an instance of `VariableType`, and 0 otherwise(including null).
+    InstanceOf(VariableType),
     AAStore,
     BAStore,
     CAStore,
@@ -300,13 +506,14 @@ pub(crate) enum FatOp {
     IfNull(usize),
     IfNotNull(usize),
     IfZero(usize),
+    IfNotZero(usize),
     IfICmpNe(usize),
     IfICmpEq(usize),
     IfACmpNe(usize),
     IfICmpLessEqual(usize),
     IfICmpLess(usize),
     GoTo(usize),
-    IInc(u8, i8),
+    IInc(u16, i16),
     Throw,
     MonitorEnter,
     MonitorExit,
@@ -314,12 +521,151 @@ pub(crate) enum FatOp {
         default_op: usize,
         pairs: Box<[(i32, usize)]>,
     },
+    /// Dense switch, jumping to `targets[key - low]` if `key` is within `low..=high`, and to `default` otherwise.
+    TableSwitch {
+        low: i32,
+        high: i32,
+        default: usize,
+        targets: Box<[usize]>,
+    },
     //Emmited after split into basic blocks, for handling exceptions
     //BeginTry,
     //EndTry,
     //Catch,
 }
 impl FatOp {
+    /// Pushes classes this op refers to(owners of accessed fields and called methods, as well as types it uses) to `classes`.
+    pub(crate) fn referenced_classes(&self, classes: &mut Vec<ClassInfo>) {
+        match self {
+            Self::ClassConst(class_info)
+            | Self::New(class_info)
+            | Self::ANewArray(class_info)
+            | Self::ZGetStatic(class_info, _)
+            | Self::BGetStatic(class_info, _)
+            | Self::SGetStatic(class_info, _)
+            | Self::IGetStatic(class_info, _)
+            | Self::LGetStatic(class_info, _)
+            | Self::FGetStatic(class_info, _)
+            | Self::DGetStatic(class_info, _)
+            | Self::CGetStatic(class_info, _)
+            | Self::ZPutStatic(class_info, _)
+            | Self::BPutStatic(class_info, _)
+            | Self::SPutStatic(class_info, _)
+            | Self::IPutStatic(class_info, _)
+            | Self::LPutStatic(class_info, _)
+            | Self::FPutStatic(class_info, _)
+            | Self::DPutStatic(class_info, _)
+            | Self::CPutStatic(class_info, _)
+            | Self::ZGetField(class_info, _)
+            | Self::BGetField(class_info, _)
+            | Self::SGetField(class_info, _)
+            | Self::IGetField(class_info, _)
+            | Self::LGetField(class_info, _)
+            | Self::FGetField(class_info, _)
+            | Self::DGetField(class_info, _)
+            | Self::CGetField(class_info, _)
+            | Self::ZPutField(class_info, _)
+            | Self::BPutField(class_info, _)
+            | Self::SPutField(class_info, _)
+            | Self::IPutField(class_info, _)
+            | Self::LPutField(class_info, _)
+            | Self::FPutField(class_info, _)
+            | Self::DPutField(class_info, _)
+            | Self::CPutField(class_info, _) => push_class(class_info, classes),
+            Self::AGetStatic {
+                class_info,
+                type_info,
+                ..
+            }
+            | Self::APutStatic {
+                class_info,
+                type_info,
+                ..
+            }
+            | Self::AGetField {
+                class_info,
+                type_info,
+                ..
+            }
+            | Self::APutField {
+                class_info,
+                type_info,
+                ..
+            } => {
+                push_class(class_info, classes);
+                push_vtype_class(&VariableType::ObjectRef(type_info.clone()), classes);
+            }
+            Self::AAGetStatic {
+                class_info, atype, ..
+            }
+            | Self::AAPutStatic {
+                class_info, atype, ..
+            }
+            | Self::AAGetField {
+                class_info, atype, ..
+            }
+            | Self::AAPutField {
+                class_info, atype, ..
+            } => {
+                push_class(class_info, classes);
+                push_vtype_class(atype, classes);
+            }
+            Self::InvokeSpecial(class_info, _, args, ret)
+            | Self::InvokeStatic(class_info, _, args, ret)
+            | Self::InvokeInterface(class_info, _, args, ret)
+            | Self::InvokeVirtual(class_info, _, args, ret) => push_sig_classes(class_info, args, ret, classes),
+            Self::InvokeDynamic(handle, _, args, ret) => {
+                handle.referenced_classes(classes);
+                for arg in args.iter().chain(std::iter::once(ret)) {
+                    push_vtype_class(arg, classes);
+                }
+            }
+            Self::Lambda {
+                interface,
+                args,
+                ret,
+                captured,
+                target,
+                ..
+            } => {
+                push_sig_classes(interface, args, ret, classes);
+                for vtype in captured.iter() {
+                    push_vtype_class(vtype, classes);
+                }
+                target.referenced_classes(classes);
+            }
+            Self::StringConcat(parts) => {
+                classes.push(ClassInfo::from_java_path("java/lang/String"));
+                classes.push(ClassInfo::from_java_path("java/lang/StringBuilder"));
+                for part in parts.iter() {
+                    if let ConcatPart::Arg(vtype) = part {
+                        push_vtype_class(vtype, classes);
+                    }
+                }
+            }
+            Self::StringConst(_) => classes.push(ClassInfo::from_java_path("java/lang/String")),
+            Self::MultiANewArray(vtype, _) | Self::CheckedCast(vtype) | Self::InstanceOf(vtype) => {
+                push_vtype_class(vtype, classes)
+            }
+            _ => (),
+        }
+    }
+    /// Checks if execution may continue with the op following this one.
+    pub fn falls_through(&self) -> bool {
+        !matches!(
+            self,
+            Self::GoTo(_)
+                | Self::Return
+                | Self::AReturn
+                | Self::FReturn
+                | Self::IReturn
+                | Self::DReturn
+                | Self::LReturn
+                | Self::Throw
+                | Self::LookupSwitch { .. }
+                | Self::TableSwitch { .. }
+        )
+    }
     pub fn jump_target(&self) -> Option<SmallVec<[usize; 4]>> {
         match self {
             Self::IfACmpEq(target) => Some(smallvec![*target]),
@@ -332,6 +678,7 @@ impl FatOp {
             Self::IfNull(target) => Some(smallvec![*target]),
             Self::IfNotNull(target) => Some(smallvec![*target]),
             Self::IfZero(target) => Some(smallvec![*target]),
+            Self::IfNotZero(target) => Some(smallvec![*target]),
             Self::IfICmpNe(target) => Some(smallvec![*target]),
             Self::IfICmpEq(target) => Some(smallvec![*target]),
             Self::IfACmpNe(target) => Some(smallvec![*target]),
@@ -345,10 +692,56 @@ impl FatOp {
                 }
                 Some(sv)
             }
+            Self::TableSwitch {
+                default, targets, ..
+            } => {
+                let mut sv = smallvec![*default];
+                sv.extend(targets.iter().copied());
+                Some(sv)
+            }
             _ => None,
         }
     }
 }
+/// An entry of the exception table, with bytecode offsets translated into indices of `FatOp`s.
+#[derive(Debug, Clone)]
+pub(crate) struct ExceptionHandler {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) handler: usize,
+    /// `None` means any exception(used by `finally`).
+    pub(crate) catch_type: Option<ClassInfo>,
+}
+impl ExceptionHandler {
+    pub(crate) fn covers(&self, op: usize) -> bool {
+        (self.start..self.end).contains(&op)
+    }
+}
+pub(crate) fn expand_exceptions(
+    exceptions: &[CodeException],
+    ops: &[(OpCode, u16)],
+    class: &ImportedJavaClass,
+) -> Box<[ExceptionHandler]> {
+    exceptions
+        .iter()
+        .map(|exception| {
+            // `end_pc` is exclusive, and may point just past the last op.
+            let end = find_op_with_offset(ops, exception.end_pc).unwrap_or(ops.len());
+            let catch_type = if exception.catch_type == 0 {
+                None
+            } else {
+                let class_name = class.lookup_class(exception.catch_type).unwrap();
+                Some(ClassInfo::from_java_path(class_name))
+            };
+            ExceptionHandler {
+                start: find_op_with_offset(ops, exception.start_pc).unwrap(),
+                end,
+                handler: find_op_with_offset(ops, exception.handler_pc).unwrap(),
+                catch_type,
+            }
+        })
+        .collect()
+}
 pub(crate) fn find_op_with_offset(ops: &[(OpCode, u16)], idx: u16) -> Option<usize> {
     for (current, op) in ops.iter().enumerate() {
         if op.1 == idx {
@@ -357,7 +750,7 @@ pub(crate) fn find_op_with_offset(ops: &[(OpCode, u16)], idx: u16) -> Option<usi
     }
     None
 }
-pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Box<[FatOp]> {
+pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> std::io::Result<Box<[FatOp]>> {
     let mut fatops = Vec::with_capacity(ops.len());
     for op in ops {
         let cop = match &op.0 {
@@ -420,6 +813,7 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
             OpCode::IShr => FatOp::IShr,
             OpCode::IShl => FatOp::IShl,
             OpCode::LShl => FatOp::LShl,
+            OpCode::IUShr => FatOp::IUShr,
             OpCode::LUShr => FatOp::LUShr,
             OpCode::LShr => FatOp::LShr,
             OpCode::IAnd => FatOp::IAnd,
@@ -548,7 +942,7 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
             }
             OpCode::IfNotZero(op_offset) => {
                 let op_offset: u16 = (op.1 as i32 + *op_offset as i32) as u16;
-                FatOp::IfZero(find_op_with_offset(ops, op_offset).unwrap())
+                FatOp::IfNotZero(find_op_with_offset(ops, op_offset).unwrap())
             }
             OpCode::IfICmpNe(op_offset) => {
                 let op_offset: u16 = (op.1 as i32 + *op_offset as i32) as u16;
@@ -629,12 +1023,18 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
             }
             OpCode::ANewArray(index) => {
                 let class_name = class.lookup_class(*index).unwrap();
-                FatOp::ANewArray(ClassInfo::from_java_path(&class_name))
+                // Arrays of arrays(`new int[3][]`) are partially specified multi-dimensional arrays.
+                if class_name.starts_with('[') {
+                    let atype = crate::field_desc_str_to_ftype(&format!("[{class_name}"), 0);
+                    FatOp::MultiANewArray(atype, 1)
+                } else {
+                    FatOp::ANewArray(ClassInfo::from_java_path(&class_name))
+                }
             }
             OpCode::MultiANewArray(index, dimensions) => {
                 let class_name = class.lookup_class(*index).unwrap();
-                let class_info = ClassInfo::from_java_path(&class_name);
-                FatOp::MultiANewArray(class_info, *dimensions)
+                let atype = crate::field_desc_str_to_ftype(class_name, 0);
+                FatOp::MultiANewArray(atype, *dimensions)
             }
             OpCode::NewArray(typeid) => match *typeid {
                 4 => FatOp::ZNewArray,
@@ -649,13 +1049,11 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
             },
             OpCode::CheckCast(index) => {
                 let class_name = class.lookup_class(*index).unwrap();
-                let class_info = ClassInfo::from_java_path(&class_name);
-                FatOp::CheckedCast(class_info)
+                FatOp::CheckedCast(class_name_to_ftype(class_name))
             }
             OpCode::InstanceOf(index) => {
                 let class_name = class.lookup_class(*index).unwrap();
-                let class_info = ClassInfo::from_java_path(&class_name);
-                FatOp::InstanceOf(class_info)
+                FatOp::InstanceOf(class_name_to_ftype(class_name))
             }
             OpCode::Swap => FatOp::Swap,
             OpCode::Dup => FatOp::Dup,
@@ -675,11 +1073,8 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
                     .replace("<init>", "_init_")
                     .replace("<cinit>", "_cinit_")
                     .into();
-                if method_name.contains("_init_") || method_name.contains("_cinit_") {
-                    FatOp::InvokeSpecial(class_info, method_name, args.into(), ret)
-                } else {
-                    FatOp::InvokeVirtual(class_info, method_name, args.into(), ret)
-                }
+                // Constructors, private methods and `super` calls must not be dispatched virtually.
+                FatOp::InvokeSpecial(class_info, method_name, args.into(), ret)
             }
             OpCode::InvokeStatic(index) => {
                 let (method_class_name, name, args, ret) = methodref_to_name_and_sig(*index, class);
@@ -707,11 +1102,24 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
                     .lookup_method_handle(bootstrap_method.bootstrap_method_ref)
                     .unwrap();
                 let handle = DynamicMethodHandle::from_kind(reference_kind, reference_index, class);
-                println!("\nhandle:{handle:?},reference_index:{reference_index}\n");
-                //let (name, argc) = methodref_to_mangled_and_argc(bootstrap_method.bootstrap_method_ref, class);
                 let (name,args,ret) = nametype_to_sig(name_and_type_index, class);
-                FatOp::InvokeDynamic(handle,name,args.into(),ret)
-                //FatOp::InvokeDynamic(name, argc)
+                match &handle {
+                    DynamicMethodHandle::InvokeStatic(bootstrap_class, bootstrap_name, _, _)
+                        if bootstrap_class.cpp_class() == "java::lang::invoke::LambdaMetafactory"
+                            && (bootstrap_name.starts_with("metafactory")
+                                || bootstrap_name.starts_with("altMetafactory")) =>
+                    {
+                        expand_lambda(name_and_type_index, args, ret, &bootstrap_method.bootstrap_args, class)
+                    }
+                    DynamicMethodHandle::InvokeStatic(bootstrap_class, bootstrap_name, _, _)
+                        if bootstrap_class.cpp_class() == "java::lang::invoke::StringConcatFactory"
+                            && bootstrap_name.starts_with("makeConcat") =>
+                    {
+                        let with_constants = bootstrap_name.starts_with("makeConcatWithConstants");
+                        expand_string_concat(args, with_constants, &bootstrap_method.bootstrap_args, class)?
+                    }
+                    _ => FatOp::InvokeDynamic(handle,name,args.into(),ret),
+                }
             }
             OpCode::Return => FatOp::Return,
             OpCode::AReturn => FatOp::AReturn,
@@ -755,9 +1163,27 @@ pub(crate) fn expand_ops(ops: &[(OpCode, u16)], class: &ImportedJavaClass) -> Bo
                     pairs: pairs.into(),
                 }
             }
+            OpCode::TableSwitch(switch) => {
+                let default_offset: u16 = (op.1 as i32 + switch.default_offset) as u16;
+                let default = find_op_with_offset(ops, default_offset).unwrap();
+                let targets: Box<[usize]> = switch
+                    .offsets
+                    .iter()
+                    .map(|offset| {
+                        let offset: u16 = (op.1 as i32 + *offset) as u16;
+                        find_op_with_offset(ops, offset).unwrap()
+                    })
+                    .collect();
+                FatOp::TableSwitch {
+                    low: switch.low,
+                    high: switch.high,
+                    default,
+                    targets,
+                }
+            }
             _ => todo!("can't expand op {op:?}"),
         };
         fatops.push(cop);
     }
-    fatops.into()
+    Ok(fatops.into())
 }
@@ -3,18 +3,18 @@ use super::opcodes::{load_ops, OpCode};
 use super::{load_u16, load_u32, load_u8, AccessFlags, ConstantItem};
 #[derive(Debug)]
 pub(crate) struct LocalVariable {
-    start_pc: u16,
-    length: u16,
-    name_index: u16,
-    descriptor_index: u16,
-    index: u16,
+    pub(crate) start_pc: u16,
+    pub(crate) length: u16,
+    pub(crate) name_index: u16,
+    pub(crate) descriptor_index: u16,
+    pub(crate) index: u16,
 }
 #[derive(Debug)]
 pub(crate) struct CodeException {
-    start_pc: u16,
-    end_pc: u16,
-    handler_pc: u16,
-    catch_type: u16,
+    pub(crate) start_pc: u16,
+    pub(crate) end_pc: u16,
+    pub(crate) handler_pc: u16,
+    pub(crate) catch_type: u16,
 }
 #[derive(Debug)]
 pub(crate) struct MethodParameter {
@@ -26,6 +26,120 @@ pub(crate) struct BootstrapMethod {
     pub(crate) bootstrap_method_ref: u16,
     pub(crate) bootstrap_args: Box<[u16]>,
 }
+/// Type of a local or an operand stack value, as recorded in a `StackMapTable` frame.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum VerificationType {
+    Top,
+    Integer,
+    Float,
+    Double,
+    Long,
+    Null,
+    /// `this` in a constructor, before the constructor of the superclass is called.
+    UninitializedThis,
+    /// Reference to an object or array, of class at constant pool index `u16`.
+    Object(u16),
+    /// Object allocated by the `new` op at bytecode offset `u16`, whose constructor was not called yet.
+    Uninitialized(u16),
+}
+impl VerificationType {
+    fn read<R: std::io::Read>(src: &mut R) -> Result<Self, std::io::Error> {
+        match load_u8(src)? {
+            0 => Ok(Self::Top),
+            1 => Ok(Self::Integer),
+            2 => Ok(Self::Float),
+            3 => Ok(Self::Double),
+            4 => Ok(Self::Long),
+            5 => Ok(Self::Null),
+            6 => Ok(Self::UninitializedThis),
+            7 => Ok(Self::Object(load_u16(src)?)),
+            8 => Ok(Self::Uninitialized(load_u16(src)?)),
+            tag => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Invalid verification type tag {tag}!"),
+            )),
+        }
+    }
+    fn read_many<R: std::io::Read>(src: &mut R, count: usize) -> Result<Box<[Self]>, std::io::Error> {
+        (0..count).map(|_| Self::read(src)).collect()
+    }
+}
+/// Frame of a `StackMapTable`, describing types of locals and stack values at bytecode offset `offset_delta` past the previous frame(+1).
+/// All but full frames are described in terms of the previous frame.
+#[derive(Debug)]
+pub(crate) enum StackMapFrame {
+    /// Same locals as the previous frame, and an empty stack.
+    Same { offset_delta: u16 },
+    /// Same locals as the previous frame, and a single value on the stack.
+    SameLocals1StackItem { offset_delta: u16, stack: VerificationType },
+    /// Locals of the previous frame, without the last `chopped` ones, and an empty stack.
+    Chop { offset_delta: u16, chopped: u8 },
+    /// Locals of the previous frame, followed by `locals`, and an empty stack.
+    Append { offset_delta: u16, locals: Box<[VerificationType]> },
+    Full {
+        offset_delta: u16,
+        locals: Box<[VerificationType]>,
+        stack: Box<[VerificationType]>,
+    },
+}
+impl StackMapFrame {
+    fn read<R: std::io::Read>(src: &mut R) -> Result<Self, std::io::Error> {
+        let frame_type = load_u8(src)?;
+        match frame_type {
+            0..=63 => Ok(Self::Same {
+                offset_delta: frame_type as u16,
+            }),
+            64..=127 => Ok(Self::SameLocals1StackItem {
+                offset_delta: frame_type as u16 - 64,
+                stack: VerificationType::read(src)?,
+            }),
+            247 => Ok(Self::SameLocals1StackItem {
+                offset_delta: load_u16(src)?,
+                stack: VerificationType::read(src)?,
+            }),
+            248..=250 => Ok(Self::Chop {
+                offset_delta: load_u16(src)?,
+                chopped: 251 - frame_type,
+            }),
+            251 => Ok(Self::Same {
+                offset_delta: load_u16(src)?,
+            }),
+            252..=254 => {
+                let offset_delta = load_u16(src)?;
+                let locals = VerificationType::read_many(src, (frame_type - 251) as usize)?;
+                Ok(Self::Append {
+                    offset_delta,
+                    locals,
+                })
+            }
+            255 => {
+                let offset_delta = load_u16(src)?;
+                let local_count = load_u16(src)? as usize;
+                let locals = VerificationType::read_many(src, local_count)?;
+                let stack_count = load_u16(src)? as usize;
+                let stack = VerificationType::read_many(src, stack_count)?;
+                Ok(Self::Full {
+                    offset_delta,
+                    locals,
+                    stack,
+                })
+            }
+            128..=246 => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Reserved stack map frame type {frame_type}!"),
+            )),
+        }
+    }
+    pub(crate) fn offset_delta(&self) -> u16 {
+        match self {
+            Self::Same { offset_delta }
+            | Self::SameLocals1StackItem { offset_delta, .. }
+            | Self::Chop { offset_delta, .. }
+            | Self::Append { offset_delta, .. }
+            | Self::Full { offset_delta, .. } => *offset_delta,
+        }
+    }
+}
 #[derive(Debug)]
 pub(crate) enum Attribute {
     Unknown,
@@ -45,6 +159,9 @@ pub(crate) enum Attribute {
     LocalVariableTable {
         local_vars: Box<[LocalVariable]>,
     },
+    StackMapTable {
+        frames: Box<[StackMapFrame]>,
+    },
     NestHost {
         host_class_index: u16,
     },
@@ -129,7 +246,16 @@ impl Attribute {
             }
             "Deprecated" => Ok(Self::Deprecated),
             "Record" => Ok(Self::Unknown),        // IDK what it does.
-            "StackMapTable" => Ok(Self::Unknown), //Not worth the effort.
+            "StackMapTable" => {
+                let number_of_entries = load_u16(src)? as usize;
+                let mut frames = Vec::with_capacity(number_of_entries);
+                for _ in 0..number_of_entries {
+                    frames.push(StackMapFrame::read(src)?);
+                }
+                Ok(Self::StackMapTable {
+                    frames: frames.into(),
+                })
+            }
             "RuntimeVisibleAnnotations" => Ok(Self::Unknown), //TODO: Handle this at some point.
             "LocalVariableTypeTable" => Ok(Self::Unknown), //TODO: Not needed, but nice to have.
             "InnerClasses" => Ok(Self::Unknown),  //TODO: Handle inner classes!
@@ -283,3 +409,43 @@ impl Attribute {
         Self::decode_attribute(&mut &attibute_data[..], attribute_name, const_items)
     }
 }
+#[test]
+fn read_stack_map_frames() {
+    use VerificationType as V;
+    let data = [
+        0, 7,
+        // same, same_locals_1_stack_item(int)
+        3,
+        64 + 5, 1,
+        // same_frame_extended, chop 2
+        251, 0, 64,
+        249, 0, 2,
+        // append(long, object #9)
+        253, 0, 1, 4, 7, 0, 9,
+        // full(locals: top, uninitialized by `new` at offset 5; stack: null)
+        255, 0, 3, 0, 2, 0, 8, 0, 5, 0, 1, 5,
+        // same_locals_1_stack_item_extended(double)
+        247, 1, 0, 3,
+    ];
+    let Attribute::StackMapTable { frames } = Attribute::decode_attribute(&mut &data[..], "StackMapTable", &[]).unwrap()
+    else {
+        panic!("Expected a StackMapTable");
+    };
+    let offsets: Vec<u16> = frames.iter().map(StackMapFrame::offset_delta).collect();
+    assert_eq!(offsets, [3, 5, 64, 2, 1, 3, 256]);
+    assert!(matches!(frames[0], StackMapFrame::Same { .. }));
+    assert!(matches!(frames[1], StackMapFrame::SameLocals1StackItem { stack: V::Integer, .. }));
+    assert!(matches!(frames[2], StackMapFrame::Same { .. }));
+    assert!(matches!(frames[3], StackMapFrame::Chop { chopped: 2, .. }));
+    assert!(matches!(&frames[4], StackMapFrame::Append { locals, .. } if matches!(**locals, [V::Long, V::Object(9)])));
+    assert!(matches!(
+        &frames[5],
+        StackMapFrame::Full { locals, stack, .. } if matches!(**locals, [V::Top, V::Uninitialized(5)]) && matches!(**stack, [V::Null])
+    ));
+    assert!(matches!(frames[6], StackMapFrame::SameLocals1StackItem { stack: V::Double, .. }));
+}
+#[test]
+fn reject_reserved_frame_types() {
+    let data = [0, 1, 128];
+    assert!(Attribute::decode_attribute(&mut &data[..], "StackMapTable", &[]).is_err());
+}
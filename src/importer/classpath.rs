@@ -0,0 +1,49 @@
+use super::{load_class, BytecodeImportError, ImportedJavaClass};
+use std::path::{Path, PathBuf};
+/// A directory or a jar file classes may be loaded from.
+enum ClassPathEntry {
+    Dir(PathBuf),
+    Jar(zip::ZipArchive<std::fs::File>),
+}
+/// Set of directories and jars searched(in order) for classes referenced by translated code.
+pub(crate) struct ClassPath {
+    entries: Vec<ClassPathEntry>,
+}
+impl ClassPath {
+    pub(crate) fn new(paths: &[PathBuf]) -> Result<Self, BytecodeImportError> {
+        let mut entries = Vec::with_capacity(paths.len());
+        for path in paths {
+            if path.is_dir() {
+                entries.push(ClassPathEntry::Dir(path.clone()));
+            } else {
+                let jar = std::fs::File::open(path)?;
+                entries.push(ClassPathEntry::Jar(zip::ZipArchive::new(jar)?));
+            }
+        }
+        Ok(Self { entries })
+    }
+    /// Loads class with path `java_path`(e.g. `java/lang/Object`) from the first entry containing it.
+    pub(crate) fn load_class(
+        &mut self,
+        java_path: &str,
+    ) -> Result<Option<ImportedJavaClass>, BytecodeImportError> {
+        let file_name = format!("{java_path}.class");
+        for entry in self.entries.iter_mut() {
+            match entry {
+                ClassPathEntry::Dir(dir) => {
+                    let path = dir.join(Path::new(&file_name));
+                    if path.is_file() {
+                        let mut src = std::fs::File::open(path)?;
+                        return Ok(Some(load_class(&mut src)?));
+                    }
+                }
+                ClassPathEntry::Jar(jar) => match jar.by_name(&file_name) {
+                    Ok(mut src) => return Ok(Some(load_class(&mut src)?)),
+                    Err(zip::result::ZipError::FileNotFound) => (),
+                    Err(err) => return Err(err.into()),
+                },
+            }
+        }
+        Ok(None)
+    }
+}
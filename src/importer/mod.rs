@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 mod attribute;
+mod classpath;
 pub mod opcodes;
 use crate::importer::attribute::BootstrapMethod;
+pub(crate) use attribute::{CodeException, LocalVariable, StackMapFrame, VerificationType};
+pub(crate) use classpath::ClassPath;
 use crate::IString;
 use attribute::Attribute;
 use opcodes::OpCode;
@@ -32,6 +35,13 @@ pub(crate) struct Field {
     attributes: Box<[Attribute]>,
 }
 impl Field {
+    /// Returns the index of the constant this static field is initialized with, if it has a `ConstantValue` attribute.
+    pub(crate) fn constant_value(&self) -> Option<u16> {
+        self.attributes.iter().find_map(|attribute| match attribute {
+            Attribute::ConstantValue { value_index } => Some(*value_index),
+            _ => None,
+        })
+    }
     fn read<R: std::io::Read>(
         src: &mut R,
         const_items: &[ConstantItem],
@@ -68,6 +78,12 @@ impl Method {
     pub(crate) fn is_virtual(&self, class: &ImportedJavaClass) -> bool {
         !(self.access_flags.is_static() || self.name(class).contains("init"))
     }
+    pub(crate) fn is_abstract(&self) -> bool {
+        self.access_flags.is_abstract()
+    }
+    pub(crate) fn is_synchronized(&self) -> bool {
+        self.access_flags.is_synchronized()
+    }
     /*
     pub(crate) fn access_flags(&self) -> &AccessFlags {
         &self.access_flags
@@ -108,6 +124,62 @@ impl Method {
         }
         None
     }
+    pub(crate) fn exception_table(&self) -> &[CodeException] {
+        for attribute in self.attributes.iter() {
+            if let Attribute::Code {
+                ops: _,
+                max_stack: _,
+                max_locals: _,
+                attributes: _,
+                exceptions,
+            } = attribute
+            {
+                return exceptions;
+            };
+        }
+        &[]
+    }
+    /// Returns frames of the `StackMapTable` of this method's code, or an empty slice if it has none(e.g. in class files older than version 50).
+    pub(crate) fn stack_map(&self) -> &[StackMapFrame] {
+        for attribute in self.attributes.iter() {
+            if let Attribute::Code { attributes, .. } = attribute {
+                for attribute in attributes.iter() {
+                    if let Attribute::StackMapTable { frames } = attribute {
+                        return frames;
+                    }
+                }
+            }
+        }
+        &[]
+    }
+    /// Returns `(start_pc, line)` entries of all `LineNumberTable`s of this method's code, sorted by `start_pc`.
+    pub(crate) fn line_numbers(&self) -> Vec<(u16, u16)> {
+        let mut pc_lines = Vec::new();
+        for attribute in self.attributes.iter() {
+            if let Attribute::Code { attributes, .. } = attribute {
+                for attribute in attributes.iter() {
+                    if let Attribute::LineNumberTable { pc_lines: table } = attribute {
+                        pc_lines.extend(table.iter().copied());
+                    }
+                }
+            }
+        }
+        pc_lines.sort();
+        pc_lines
+    }
+    /// Returns the `LocalVariableTable` of this method's code, or an empty slice if it has none(e.g. when compiled without `-g`).
+    pub(crate) fn local_variable_table(&self) -> &[LocalVariable] {
+        for attribute in self.attributes.iter() {
+            if let Attribute::Code { attributes, .. } = attribute {
+                for attribute in attributes.iter() {
+                    if let Attribute::LocalVariableTable { local_vars } = attribute {
+                        return local_vars;
+                    }
+                }
+            }
+        }
+        &[]
+    }
     fn read<R: std::io::Read>(
         src: &mut R,
         const_items: &[ConstantItem],
@@ -180,6 +252,14 @@ impl ImportedJavaClass {
             None
         }
     }
+    pub(crate) fn lookup_method_type(&self, method_type: u16) -> Option<&str> {
+        let method_type = &self.const_items[method_type as usize - 1];
+        if let ConstantItem::MethodType { descriptor_index } = method_type {
+            self.lookup_utf8(*descriptor_index)
+        } else {
+            None
+        }
+    }
     pub(crate) fn lookup_bootstrap_method(&self, index: u16) -> Option<&BootstrapMethod> {
         for attribute in self.attributes.iter() {
             if let Attribute::BootstrapMethods { bootstrap_methods } = attribute {
@@ -191,6 +271,13 @@ impl ImportedJavaClass {
     pub(crate) fn name(&self) -> &str {
         self.lookup_class(self.this_class).unwrap()
     }
+    /// Returns the name of the source file this class was compiled from(e.g. `Foo.java`), if the class file records it.
+    pub(crate) fn source_file(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attribute| match attribute {
+            Attribute::SourceFile { sourcefile_index } => self.lookup_utf8(*sourcefile_index),
+            _ => None,
+        })
+    }
     pub(crate) fn this_class(&self) -> u16 {
         self.this_class
     }
@@ -374,6 +461,9 @@ impl AccessFlags {
     fn is_final(&self) -> bool {
         self.mask & 0x0010 != 0
     }
+    pub(crate) fn is_synchronized(&self) -> bool {
+        self.mask & 0x0020 != 0
+    }
     pub fn is_super(&self) -> bool {
         self.mask & 0x0020 != 0
     }
@@ -659,3 +749,36 @@ fn load_ident_class() {
     let mut file = std::fs::File::open("test/Identity.class").unwrap();
     let _class = load_class(&mut file).unwrap();
 }
+#[test]
+fn load_wide_ops() {
+    // wide iload 300, wide iinc 300 -200, wide astore 256
+    let code = [
+        0xc4, 0x15, 0x01, 0x2c, 0xc4, 0x84, 0x01, 0x2c, 0xff, 0x38, 0xc4, 0x3a, 0x01, 0x00,
+    ];
+    let ops = opcodes::load_ops(&mut &code[..], code.len() as u32).unwrap();
+    assert!(matches!(ops[0], (OpCode::ILoad(300), 0)));
+    assert!(matches!(ops[1], (OpCode::IInc(300, -200), 4)));
+    assert!(matches!(ops[2], (OpCode::AStore(256), 10)));
+}
+#[test]
+fn reject_subroutines() {
+    // ret 1, wide ret 257, jsr +3
+    for code in [&[0xa9, 0x01][..], &[0xc4, 0xa9, 0x01, 0x01], &[0xa8, 0x00, 0x03]] {
+        assert!(opcodes::load_ops(&mut &code[..], code.len() as u32).is_err());
+    }
+}
+#[test]
+fn load_table_switch() {
+    // iload_0, tableswitch(padded to 4 bytes) -1..=1, then ireturn as every target
+    let code = [
+        0x1a, 0xaa, 0, 0, 0, 0, 0, 27, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1, 0, 0, 0, 27, 0, 0, 0,
+        27, 0, 0, 0, 27, 0xac,
+    ];
+    let ops = opcodes::load_ops(&mut &code[..], code.len() as u32).unwrap();
+    let OpCode::TableSwitch(ref switch) = ops[1].0 else {
+        panic!("Expected a tableswitch, got {:?}", ops[1]);
+    };
+    assert_eq!((switch.low, switch.high), (-1, 1));
+    assert_eq!(&*switch.offsets, &[27, 27, 27]);
+    assert!(matches!(ops[2], (OpCode::IReturn, 28)));
+}
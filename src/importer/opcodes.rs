@@ -2,21 +2,21 @@ use super::{load_i16, load_i32, load_i8, load_u16, load_u8};
 #[derive(Debug, Clone)]
 pub(crate) enum OpCode {
     Nop,
-    ALoad(u8),
-    FLoad(u8),
-    DLoad(u8),
-    ILoad(u8),
-    LLoad(u8),
+    ALoad(u16),
+    FLoad(u16),
+    DLoad(u16),
+    ILoad(u16),
+    LLoad(u16),
     DConst(f64),
     FConst(f32),
     IConst(i32),
     LConst(i64),
     AConstNull,
-    AStore(u8),
-    DStore(u8),
-    FStore(u8),
-    IStore(u8),
-    LStore(u8),
+    AStore(u16),
+    DStore(u16),
+    FStore(u16),
+    IStore(u16),
+    LStore(u16),
     LAdd,
     IAdd,
     DAdd,
@@ -53,7 +53,7 @@ pub(crate) enum OpCode {
     INeg,
     LNeg,
     LShr,
-    IInc(u8, i8),
+    IInc(u16, i16),
     InvokeSpecial(u16),
     InvokeVirtual(u16),
     InvokeInterface(u16),
@@ -145,6 +145,7 @@ pub(crate) enum OpCode {
     MonitorEnter,
     MonitorExit,
     LookupSwitch(Box<LookupSwitch>),
+    TableSwitch(Box<TableSwitch>),
 }
 ///Separate to decrease footprint of individual OP.
 #[derive(Debug, Clone)]
@@ -152,6 +153,15 @@ pub(crate) struct LookupSwitch {
     pub(crate) default_offset: i32,
     pub(crate) pairs: Box<[(i32, i32)]>,
 }
+///Separate to decrease footprint of individual OP.
+#[derive(Debug, Clone)]
+pub(crate) struct TableSwitch {
+    pub(crate) default_offset: i32,
+    pub(crate) low: i32,
+    pub(crate) high: i32,
+    /// Jump offsets for keys `low..=high`.
+    pub(crate) offsets: Box<[i32]>,
+}
 pub(crate) fn load_ops<R: std::io::Read>(
     src: &mut R,
     code_length: u32,
@@ -198,33 +208,33 @@ pub(crate) fn load_ops<R: std::io::Read>(
             0x15 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::ILoad(index)
+                OpCode::ILoad(index as u16)
             }
             0x16 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::LLoad(index)
+                OpCode::LLoad(index as u16)
             }
             0x17 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::FLoad(index)
+                OpCode::FLoad(index as u16)
             }
             0x18 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::DLoad(index)
+                OpCode::DLoad(index as u16)
             }
             0x19 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::ALoad(index)
+                OpCode::ALoad(index as u16)
             }
-            0x1a..=0x1d => OpCode::ILoad(op - 0x1a),
-            0x1e..=0x21 => OpCode::LLoad(op - 0x1e),
-            0x22..=0x25 => OpCode::FLoad(op - 0x22),
-            0x26..=0x29 => OpCode::DLoad(op - 0x26),
-            0x2a..=0x2d => OpCode::ALoad(op - 0x2a),
+            0x1a..=0x1d => OpCode::ILoad((op - 0x1a) as u16),
+            0x1e..=0x21 => OpCode::LLoad((op - 0x1e) as u16),
+            0x22..=0x25 => OpCode::FLoad((op - 0x22) as u16),
+            0x26..=0x29 => OpCode::DLoad((op - 0x26) as u16),
+            0x2a..=0x2d => OpCode::ALoad((op - 0x2a) as u16),
             0x2e => OpCode::IALoad,
             0x2f => OpCode::LALoad,
             0x30 => OpCode::FALoad,
@@ -236,32 +246,32 @@ pub(crate) fn load_ops<R: std::io::Read>(
             0x3a => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::AStore(index)
+                OpCode::AStore(index as u16)
             }
             0x37 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::LStore(index)
+                OpCode::LStore(index as u16)
             }
             0x38 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::FStore(index)
+                OpCode::FStore(index as u16)
             }
             0x39 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::DStore(index)
+                OpCode::DStore(index as u16)
             }
-            0x3b..=0x3e => OpCode::IStore(op - 0x3b),
-            0x3f..=0x42 => OpCode::LStore(op - 0x3f),
-            0x43..=0x46 => OpCode::FStore(op - 0x43),
-            0x47..=0x4a => OpCode::DStore(op - 0x47),
-            0x4b..=0x4e => OpCode::AStore(op - 0x4b),
+            0x3b..=0x3e => OpCode::IStore((op - 0x3b) as u16),
+            0x3f..=0x42 => OpCode::LStore((op - 0x3f) as u16),
+            0x43..=0x46 => OpCode::FStore((op - 0x43) as u16),
+            0x47..=0x4a => OpCode::DStore((op - 0x47) as u16),
+            0x4b..=0x4e => OpCode::AStore((op - 0x4b) as u16),
             0x36 => {
                 let index = load_u8(src)?;
                 curr_offset += 1;
-                OpCode::IStore(index)
+                OpCode::IStore(index as u16)
             }
             0x4f => OpCode::IAStore,
             0x50 => OpCode::LAStore,
@@ -308,7 +318,7 @@ pub(crate) fn load_ops<R: std::io::Read>(
             0x79 => OpCode::LShl,
             0x7a => OpCode::IShr,
             0x7b => OpCode::LShr,
-            0x7c => OpCode::LUShr,
+            0x7c => OpCode::IUShr,
             0x7d => OpCode::LUShr,
             0x7e => OpCode::IAnd,
             0x7f => OpCode::LAnd,
@@ -320,7 +330,7 @@ pub(crate) fn load_ops<R: std::io::Read>(
                 let var = load_u8(src)?;
                 let incr = load_i8(src)?;
                 curr_offset += 2;
-                OpCode::IInc(var, incr)
+                OpCode::IInc(var as u16, incr as i16)
             }
             0x85 => OpCode::I2L,
             0x86 => OpCode::I2F,
@@ -449,24 +459,30 @@ pub(crate) fn load_ops<R: std::io::Read>(
                 curr_offset += to_next as u16;
                 assert_eq!(curr_offset % 4, 0);
                 let default_offset = load_i32(src)?;
-                curr_offset += 4 as u16;
+                curr_offset += 4;
                 let low = load_i32(src)?;
-                curr_offset += 4 as u16;
+                curr_offset += 4;
                 let high = load_i32(src)?;
-                curr_offset += 4 as u16;
-                let count = (high - low + 1);
-                let mut pairs = Vec::with_capacity(count as usize);
-                for key in 0..count{
-                    let curr_key:i32 = key - low;
-                    let offset = load_i32(src)?;
-                    curr_offset += 4 as u16;
-                    pairs.push((curr_key,offset));
+                curr_offset += 4;
+                if high < low {
+                    return Err(std::io::Error::other(format!(
+                        "Invalid tableswitch range {low}..={high}!"
+                    )));
                 }
-                OpCode::LookupSwitch(Box::new(LookupSwitch {
+                let count = (high as i64 - low as i64 + 1) as usize;
+                let mut offsets = Vec::with_capacity(count);
+                for _ in 0..count {
+                    offsets.push(load_i32(src)?);
+                    curr_offset += 4;
+                }
+                OpCode::TableSwitch(Box::new(TableSwitch {
                     default_offset,
-                    pairs: pairs.into(),
+                    low,
+                    high,
+                    offsets: offsets.into(),
                 }))
             }
+            0xa8 | 0xa9 | 0xc9 => return Err(std::io::Error::other("jsr/ret are not supported")),
             0xac => OpCode::IReturn,
             0xad => OpCode::LReturn,
             0xae => OpCode::FReturn,
@@ -554,10 +570,32 @@ pub(crate) fn load_ops<R: std::io::Read>(
             0xc2 => OpCode::MonitorEnter,
             0xc3 => OpCode::MonitorExit,
             0xc4 => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Wide ops not supported!",
-                ));
+                let modified = load_u8(src)?;
+                let index = load_u16(src)?;
+                curr_offset += 3;
+                match modified {
+                    0x15 => OpCode::ILoad(index),
+                    0x16 => OpCode::LLoad(index),
+                    0x17 => OpCode::FLoad(index),
+                    0x18 => OpCode::DLoad(index),
+                    0x19 => OpCode::ALoad(index),
+                    0x36 => OpCode::IStore(index),
+                    0x37 => OpCode::LStore(index),
+                    0x38 => OpCode::FStore(index),
+                    0x39 => OpCode::DStore(index),
+                    0x3a => OpCode::AStore(index),
+                    0xa9 => return Err(std::io::Error::other("jsr/ret are not supported")),
+                    0x84 => {
+                        let incr = load_i16(src)?;
+                        curr_offset += 2;
+                        OpCode::IInc(index, incr)
+                    }
+                    _ => {
+                        return Err(std::io::Error::other(format!(
+                            "Opcode 0x{modified:x} can't be modified by wide!"
+                        )))
+                    }
+                }
             }
             0xc5 => {
                 let constant_pool_index = load_u16(src)?;
@@ -576,10 +614,9 @@ pub(crate) fn load_ops<R: std::io::Read>(
                 OpCode::IfNotNull(offset)
             }
             0xcb..=0xfd => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Invalid(reserved) opcode 0x{op:x}!"),
-                ))
+                return Err(std::io::Error::other(format!(
+                    "Invalid(reserved) opcode 0x{op:x}!"
+                )))
             } //OpCode::Reserved,
             _ => todo!("Unhandled opcode:0x{op:x}!"),
         };
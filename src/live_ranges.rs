@@ -0,0 +1,359 @@
+//! Splits local slots into variables. javac reuses a slot for locals of different scopes, which may have unrelated types, so each
+//! group of stores reaching the same loads(a live range) gets its own C++ variable.
+use crate::fatops::{ExceptionHandler, FatOp};
+use crate::type_inference::{merge_types, TypeState};
+use crate::{IString, VariableType};
+use std::collections::HashMap;
+use std::ops::Range;
+/// Local variables of a method, with each live range of a local slot in a separate variable.
+#[derive(Debug, Default)]
+pub(crate) struct LocalVariables {
+    /// Variable accessed by each op loading, storing or incrementing a local. Variables are numbered separately for each slot and
+    /// kind, with arguments in variables numbered 0.
+    pub(crate) variables: Box<[Option<u16>]>,
+    /// Types of reference variables(by slot and number) recorded in `StackMapTable` frames. Those are the types of locals in Java
+    /// source, which all values stored to them are instances of.
+    pub(crate) frame_types: HashMap<(u16, u16), VariableType>,
+    /// Names of variables(by slot, kind postfix and number) in Java source, taken from the `LocalVariableTable`.
+    pub(crate) names: HashMap<(u16, char, u16), IString>,
+}
+/// Local of Java source described by an entry of the `LocalVariableTable`.
+pub(crate) struct SourceLocal {
+    pub(crate) slot: u16,
+    pub(crate) name: IString,
+    /// Bytecode offsets the local is in scope at.
+    pub(crate) scope: Range<u16>,
+}
+impl LocalVariables {
+    /// Names variables after `locals` of Java source held in them. `offsets` are the bytecode offsets of `ops`, and `entry` is the
+    /// state at method entry, with arguments in scope at offset 0.
+    pub(crate) fn name(&mut self, ops: &[FatOp], offsets: &[u16], entry: &TypeState, locals: &[SourceLocal]) {
+        let source_name = |slot: u16, offset: u16| {
+            locals
+                .iter()
+                .find(|local| local.slot == slot && local.scope.contains(&offset))
+                .map(|local| local.name.clone())
+        };
+        for (slot, vtype) in entry.locals.iter().enumerate() {
+            let Some(vtype) = vtype else {
+                continue;
+            };
+            let postfix = vtype.type_postifx().chars().next().unwrap();
+            if let Some(name) = source_name(slot as u16, 0) {
+                self.names.insert((slot as u16, postfix, 0), name);
+            }
+        }
+        for (index, op) in ops.iter().enumerate() {
+            let (Some((slot, is_store)), Some(Some(variable))) = (local_access(op), self.variables.get(index)) else {
+                continue;
+            };
+            let key = (slot, kind_postfix(op), *variable);
+            if self.names.contains_key(&key) {
+                continue;
+            }
+            // A local comes into scope right after the store initializing it.
+            let name = source_name(slot, offsets[index]).or_else(|| {
+                is_store
+                    .then(|| offsets.get(index + 1))
+                    .flatten()
+                    .and_then(|offset| source_name(slot, *offset))
+            });
+            if let Some(name) = name {
+                self.names.insert(key, name);
+            }
+        }
+    }
+}
+/// Returns the local slot an op accesses, if it loads, stores or increments a local, and whether it stores to it.
+pub(crate) fn local_access(op: &FatOp) -> Option<(u16, bool)> {
+    match op {
+        FatOp::ALoad(slot)
+        | FatOp::ILoad(slot)
+        | FatOp::LLoad(slot)
+        | FatOp::FLoad(slot)
+        | FatOp::DLoad(slot)
+        | FatOp::IInc(slot, _) => Some((*slot, false)),
+        FatOp::AStore(slot) | FatOp::IStore(slot) | FatOp::LStore(slot) | FatOp::FStore(slot) | FatOp::DStore(slot) => {
+            Some((*slot, true))
+        }
+        _ => None,
+    }
+}
+/// Postfix of names of locals accessed by `op`, which separates locals of different kinds stored in the same slot.
+fn kind_postfix(op: &FatOp) -> char {
+    match op {
+        FatOp::ALoad(_) | FatOp::AStore(_) => 'a',
+        FatOp::LLoad(_) | FatOp::LStore(_) => 'l',
+        FatOp::FLoad(_) | FatOp::FStore(_) => 'f',
+        FatOp::DLoad(_) | FatOp::DStore(_) => 'd',
+        _ => 'i',
+    }
+}
+fn find(parents: &mut [usize], def: usize) -> usize {
+    let mut root = def;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[def] = root;
+    root
+}
+/// Definitions(stores, or arguments at method entry) of each local slot, which may reach an op.
+type Reaching = Vec<Vec<usize>>;
+/// Merges `other` into `reaching`, returning `true` if it changed.
+fn merge(reaching: &mut Reaching, other: &Reaching) -> bool {
+    let mut changed = false;
+    if reaching.len() < other.len() {
+        reaching.resize(other.len(), Vec::new());
+    }
+    for (defs, other) in reaching.iter_mut().zip(other.iter()) {
+        for def in other {
+            if let Err(position) = defs.binary_search(def) {
+                defs.insert(position, *def);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+/// Splits local slots of a method into variables. `types` are the inferred types before each op, `entry` is the state at method
+/// entry and `frames` are the states described by the `StackMapTable`.
+pub(crate) fn split_locals(
+    ops: &[FatOp],
+    handlers: &[ExceptionHandler],
+    types: &[Option<TypeState>],
+    entry: &TypeState,
+    frames: &[(usize, TypeState)],
+) -> LocalVariables {
+    // Arguments are defined at method entry, and stores by ops.
+    let mut def_ops: Vec<Option<usize>> = Vec::new();
+    let mut entry_reaching: Reaching = vec![Vec::new(); entry.locals.len()];
+    let mut entry_postfixes = Vec::new();
+    for (slot, vtype) in entry.locals.iter().enumerate() {
+        if let Some(vtype) = vtype {
+            entry_reaching[slot].push(def_ops.len());
+            entry_postfixes.push((slot as u16, vtype.type_postifx()));
+            def_ops.push(None);
+        }
+    }
+    let mut op_defs: Vec<Option<usize>> = vec![None; ops.len()];
+    for (index, op) in ops.iter().enumerate() {
+        if let Some((_, true)) = local_access(op) {
+            op_defs[index] = Some(def_ops.len());
+            def_ops.push(Some(index));
+        }
+    }
+    let mut parents: Vec<usize> = (0..def_ops.len()).collect();
+    let mut reaching: Vec<Option<Reaching>> = vec![None; ops.len()];
+    if ops.is_empty() {
+        return LocalVariables::default();
+    }
+    reaching[0] = Some(entry_reaching);
+    let mut pending = vec![0];
+    while let Some(index) = pending.pop() {
+        let Some(mut state) = reaching[index].clone() else {
+            continue;
+        };
+        // Slots with no usable value(e.g. locals of a scope which ended) are no longer reached by their stores.
+        if let Some(types) = &types[index] {
+            for (slot, defs) in state.iter_mut().enumerate() {
+                if types.locals.get(slot).is_none_or(Option::is_none) {
+                    defs.clear();
+                }
+            }
+        }
+        let before = state.clone();
+        let op = &ops[index];
+        match local_access(op) {
+            // All stores reaching a load are in the same live range.
+            Some((slot, false)) => {
+                let defs = state.get(slot as usize).map(Vec::as_slice).unwrap_or_default();
+                if let Some((first, rest)) = defs.split_first() {
+                    let root = find(&mut parents, *first);
+                    for def in rest {
+                        let other = find(&mut parents, *def);
+                        parents[other] = root;
+                    }
+                }
+            }
+            Some((slot, true)) => {
+                let slot = slot as usize;
+                if state.len() <= slot {
+                    state.resize(slot + 1, Vec::new());
+                }
+                state[slot] = vec![op_defs[index].unwrap()];
+            }
+            None => (),
+        }
+        let mut flow = |target: usize, state: &Reaching, pending: &mut Vec<usize>| {
+            let changed = match &mut reaching[target] {
+                Some(target_state) => merge(target_state, state),
+                target_state @ None => {
+                    *target_state = Some(state.clone());
+                    true
+                }
+            };
+            if changed {
+                pending.push(target);
+            }
+        };
+        // Handlers may be entered before, or after any op in their range.
+        for handler in handlers.iter().filter(|handler| handler.covers(index)) {
+            flow(handler.handler, &before, &mut pending);
+            flow(handler.handler, &state, &mut pending);
+        }
+        for target in op.jump_target().unwrap_or_default() {
+            flow(target, &state, &mut pending);
+        }
+        if op.falls_through() && index + 1 < ops.len() {
+            flow(index + 1, &state, &mut pending);
+        }
+    }
+    // Live ranges are numbered in the order of their first definition, so arguments always get variable 0.
+    let mut numbers: HashMap<usize, u16> = HashMap::new();
+    let mut counters: HashMap<(u16, char), u16> = HashMap::new();
+    let mut number_range = |root: usize, slot: u16, postfix: char| -> u16 {
+        *numbers.entry(root).or_insert_with(|| {
+            let counter = counters.entry((slot, postfix)).or_insert(0);
+            *counter += 1;
+            *counter - 1
+        })
+    };
+    for (def, (slot, postfix)) in entry_postfixes.iter().enumerate() {
+        let root = find(&mut parents, def);
+        number_range(root, *slot, postfix.chars().next().unwrap());
+    }
+    let mut variables: Vec<Option<u16>> = vec![None; ops.len()];
+    for (index, op) in ops.iter().enumerate() {
+        let Some((slot, is_store)) = local_access(op) else {
+            continue;
+        };
+        let def = if is_store {
+            op_defs[index]
+        } else {
+            reaching[index]
+                .as_ref()
+                .and_then(|state| state.get(slot as usize)?.first().copied())
+        };
+        // Loads reached by no store(e.g. in unreachable code) use the first variable.
+        variables[index] = Some(match def {
+            Some(def) => {
+                let root = find(&mut parents, def);
+                number_range(root, slot, kind_postfix(op))
+            }
+            None => 0,
+        });
+    }
+    let mut frame_types: HashMap<(u16, u16), VariableType> = HashMap::new();
+    for (index, frame) in frames {
+        for (slot, vtype) in frame.locals.iter().enumerate() {
+            let Some(vtype) = vtype.as_ref().filter(|vtype| vtype.is_reference() && !vtype.is_unknown()) else {
+                continue;
+            };
+            let Some(def) = reaching[*index]
+                .as_ref()
+                .and_then(|state| state.get(slot).and_then(|defs| defs.first()))
+            else {
+                continue;
+            };
+            let root = find(&mut parents, *def);
+            let variable = number_range(root, slot as u16, 'a');
+            frame_types
+                .entry((slot as u16, variable))
+                .and_modify(|merged| *merged = merge_types(merged, vtype).unwrap_or_else(VariableType::unknown))
+                .or_insert_with(|| vtype.clone());
+        }
+    }
+    LocalVariables {
+        variables: variables.into(),
+        frame_types,
+        names: HashMap::new(),
+    }
+}
+/// Splits local slots of `ops` taking `entries` as arguments, with types inferred without a `StackMapTable`.
+#[cfg(test)]
+fn split_ops(ops: &[FatOp], entries: &[Option<VariableType>]) -> LocalVariables {
+    let entry = TypeState::from_entries(entries, Vec::new());
+    let types = crate::type_inference::infer_types(ops, &[], entry.clone(), &[]);
+    split_locals(ops, &[], &types, &entry, &[])
+}
+#[test]
+fn reused_slot() {
+    // A `String` local, and a `Class` local of a later scope, both in slot 1.
+    let ops = [
+        FatOp::StringConst("text".into()),
+        FatOp::AStore(1),
+        FatOp::ALoad(1),
+        FatOp::Pop,
+        FatOp::ClassConst(crate::fatops::ClassInfo::from_java_path("java/lang/Object")),
+        FatOp::AStore(1),
+        FatOp::ALoad(1),
+        FatOp::Pop,
+        FatOp::Return,
+    ];
+    let split = split_ops(&ops, &[]);
+    let expected = [None, Some(0), Some(0), None, None, Some(1), Some(1), None, None];
+    assert_eq!(&*split.variables, &expected);
+}
+#[test]
+fn joined_across_branches() {
+    // `String local = arg == 0 ? "b" : "a";`, stored on each branch and loaded after they join.
+    let ops = [
+        FatOp::ILoad(0),
+        FatOp::IfZero(5),
+        FatOp::StringConst("a".into()),
+        FatOp::AStore(1),
+        FatOp::GoTo(7),
+        FatOp::StringConst("b".into()),
+        FatOp::AStore(1),
+        FatOp::ALoad(1),
+        FatOp::Pop,
+        FatOp::StringConst("c".into()),
+        FatOp::AStore(1),
+        FatOp::ALoad(1),
+        FatOp::Pop,
+        FatOp::Return,
+    ];
+    let split = split_ops(&ops, &[Some(VariableType::Int)]);
+    assert_eq!(split.variables[3], Some(0));
+    assert_eq!(split.variables[6], Some(0));
+    assert_eq!(split.variables[7], Some(0));
+    // A store after the join starts a new live range.
+    assert_eq!(split.variables[10], Some(1));
+    assert_eq!(split.variables[11], Some(1));
+}
+#[test]
+fn wide_slots() {
+    // A `long` argument in slots 0 and 1, followed by an `int` argument in slot 2. Storing an `int` to slot 1 ends the `long`.
+    let ops = [
+        FatOp::LLoad(0),
+        FatOp::Pop2,
+        FatOp::ILoad(2),
+        FatOp::Pop,
+        FatOp::IConst(1),
+        FatOp::IStore(1),
+        FatOp::ILoad(1),
+        FatOp::Pop,
+        FatOp::LConst(2),
+        FatOp::LStore(0),
+        FatOp::LLoad(0),
+        FatOp::Pop2,
+        FatOp::Return,
+    ];
+    let split = split_ops(&ops, &[Some(VariableType::Long), Some(VariableType::Int)]);
+    let expected = [
+        Some(0),
+        None,
+        Some(0),
+        None,
+        None,
+        Some(0),
+        Some(0),
+        None,
+        None,
+        Some(1),
+        Some(1),
+        None,
+        None,
+    ];
+    assert_eq!(&*split.variables, &expected);
+}
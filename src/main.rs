@@ -2,12 +2,15 @@ mod class;
 mod cpp_codegen;
 mod fatops;
 mod importer;
+mod live_ranges;
 mod method;
+mod tree_shaking;
+mod type_inference;
 use crate::fatops::{ClassInfo, FatOp};
 use crate::importer::{BytecodeImportError, ImportedJavaClass};
 use clap::Parser;
 use include_dir::{include_dir, Dir};
-use std::{io::Write, path::PathBuf};
+use std::{collections::HashSet, io::Write, path::PathBuf};
 use {class::Class, method::Method};
 pub type IString = Box<str>;
 static STDLIB_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/stdlib");
@@ -46,13 +49,17 @@ fn class_path_to_class_mangled(class_path: &str) -> IString {
 #[test]
 fn desc_mangling() {
     assert_eq!(&*desc_to_mangled("(D)D"), "_D_D");
+    assert_eq!(
+        &*desc_to_mangled("(Ljava/lang/Object;)Ljava/util/List;"),
+        "_java_cs_lang_cs_Object__java_cs_util_cs_List_"
+    );
 }
 fn desc_to_mangled(desc: &str) -> IString {
     let mut classname_beg = 0;
     let mut within_class = false;
     let mut res = String::new();
     for (index, curr) in desc.chars().enumerate() {
-        if curr == 'L' {
+        if curr == 'L' && !within_class {
             within_class = true;
             classname_beg = index + 1;
         }
@@ -115,6 +122,9 @@ impl VariableType {
     fn is_array(&self) -> bool {
         matches!(self, Self::ArrayRef(_))
     }
+    fn is_reference(&self) -> bool {
+        matches!(self, Self::ObjectRef(_) | Self::ArrayRef(_))
+    }
     fn is_wide(&self) -> bool {
         matches!(self, Self::Long | Self::Double)
     }
@@ -137,7 +147,7 @@ impl VariableType {
         match self {
             Self::Float => "float".into(),
             Self::Double => "double".into(),
-            Self::Long => "long".into(),
+            Self::Long => "int64_t".into(),
             Self::Int => "int32_t".into(),
             Self::Bool => "bool".into(),
             Self::Byte => "int8_t".into(),
@@ -146,10 +156,18 @@ impl VariableType {
             Self::Void => "void".into(),
             Self::ObjectRef(info) => format!("ManagedPointer<{}>", info.cpp_class()).into(),
             Self::ArrayRef(atype) => {
-                format!("ManagedPointer<RuntimeArray<{}>>", atype.c_type()).into()
+                format!("ManagedPointer<RuntimeArray<{}>>", atype.array_element_c_type()).into()
             } //_=>todo!("Can't get ctype of {self:?}!"),
         }
     }
+    /// C++ type of elements of arrays of `self`. Arrays of references all store `java.lang.Object`s, so that they can be covariant.
+    fn array_element_c_type(&self) -> IString {
+        if self.is_reference() {
+            "ManagedPointer<java::lang::Object>".into()
+        } else {
+            self.c_type()
+        }
+    }
     fn unknown()->Self{
         Self::ObjectRef(ClassInfo::unknown()) 
     }
@@ -165,10 +183,8 @@ impl VariableType {
             Self::Float => "f".into(),
             Self::Double => "d".into(),
             Self::Long => "l".into(),
-            Self::Int => "i".into(),
-            Self::Bool => "z".into(),
-            Self::Byte => "b".into(),
-            Self::Short => "s".into(),
+            // Smaller integers are loaded and stored as `int`s.
+            Self::Int | Self::Bool | Self::Byte | Self::Short | Self::Char => "i".into(),
             Self::ObjectRef(_) => "a".into(),
             Self::ArrayRef(_atype) => "a".into(),
             _ => todo!("Can't get type postifx of {self:?}!"),
@@ -212,23 +228,47 @@ pub(crate) fn field_descriptor_to_ftype(
 ) -> VariableType {
     field_desc_str_to_ftype(class.lookup_utf8(descriptor).unwrap(), 0)
 }
+#[test]
+fn array_args_desc_to_args() {
+    let float_matrix =
+        VariableType::ArrayRef(Box::new(VariableType::ArrayRef(Box::new(VariableType::Float))));
+    assert_eq!(
+        method_desc_to_args("([[F[[FI)[[F"),
+        (
+            vec![float_matrix.clone(), float_matrix.clone(), VariableType::Int],
+            float_matrix
+        )
+    );
+    assert_eq!(
+        method_desc_to_args("([Ljava/lang/String;J)V"),
+        (
+            vec![
+                VariableType::ArrayRef(Box::new(VariableType::ObjectRef(
+                    ClassInfo::from_java_path("java/lang/String")
+                ))),
+                VariableType::Long
+            ],
+            VariableType::Void
+        )
+    );
+}
 fn method_desc_to_args(desc: &str) -> (Vec<VariableType>, VariableType) {
     let arg_beg = desc.chars().position(|c| c == '(').unwrap() + 1;
     let arg_end = desc.chars().position(|c| c == ')').unwrap();
     let arg_desc = &desc[arg_beg..arg_end];
     let ret_val = field_desc_str_to_ftype(desc, arg_end + 1);
-    let mut within_class = false;
     let mut args = Vec::new();
-    for (index, curr) in arg_desc.chars().enumerate() {
-        if !within_class {
-            args.push(field_desc_str_to_ftype(arg_desc, index));
+    let mut index = 0;
+    while index < arg_desc.len() {
+        args.push(field_desc_str_to_ftype(arg_desc, index));
+        // Skip to the end of this argument's descriptor: array prefixes, then either a class name or a primitive.
+        while arg_desc[index..].starts_with('[') {
+            index += 1;
         }
-        if curr == 'L' || curr == '[' {
-            within_class = true;
-        }
-        if curr == ';' {
-            within_class = false;
+        if arg_desc[index..].starts_with('L') {
+            index += arg_desc[index..].find(';').unwrap();
         }
+        index += 1;
     }
     (args, ret_val)
 }
@@ -241,6 +281,28 @@ struct ConvertionArgs {
     // Target directory
     #[arg(short, long)]
     out: PathBuf,
+    // Directories and jars searched for classes referenced by source files, separated by ':'.
+    #[arg(short, long, value_delimiter = ':')]
+    classpath: Vec<PathBuf>,
+    // Additional roots of tree shaking, in the form `pkg.Class` or `pkg.Class#method`. Methods named `main` are always roots.
+    #[arg(short, long)]
+    root: Vec<String>,
+    // Translate only classes and methods reachable from `main` and `--root`s. The whole program must be translated in one run, since
+    // methods only used by classes translated by other runs would be removed.
+    #[arg(long)]
+    tree_shaking: bool,
+    // Dereference null without checking, instead of throwing `NullPointerException`. Faster, but null dereferences crash the program.
+    #[arg(long)]
+    no_null_checks: bool,
+    // Access arrays without checking indices, instead of throwing `ArrayIndexOutOfBoundsException`. Meant for release builds of tested code.
+    #[arg(long)]
+    no_bounds_checks: bool,
+    // Do not record Java stack traces of exceptions. Saves keeping a shadow call stack, but uncaught exceptions are reported without traces.
+    #[arg(long)]
+    no_stack_traces: bool,
+    // Emit `#line` directives pointing to Java source, so compiler diagnostics and debuggers(e.g. gdb) show Java files and lines.
+    #[arg(long)]
+    line_directives: bool,
 }
 struct CompilationContext {}
 const ERR_NO_EXT: i32 = 1;
@@ -250,6 +312,7 @@ const ERR_THIS_INVALID: i32 = 4;
 const ERR_SUPER_INVALID: i32 = 5;
 const ERR_BAD_OUT: i32 = 6;
 const ERR_HEADER_IO_FAIL: i32 = 7;
+const ERR_NO_ROOTS: i32 = 8;
 const PROGRESS_BAR_SIZE: usize = 50;
 fn print_progress(curr: usize, whole: usize) {
     print!("\r{curr}/{whole} \t");
@@ -294,6 +357,80 @@ impl CompilationContext {
         write_cpp_file!(GCCPP_SO, build_path, "libgccpp.so");
         Ok(())
     }
+    /// Transitively imports classes referenced by `classes` from `classpath`. Classes provided by the standard library are not imported.
+    fn import_dependencies(
+        classes: &mut Vec<Class>,
+        classpath: &[PathBuf],
+    ) -> Result<(), BytecodeImportError> {
+        // Without a classpath, classes which are not source files are assumed to be translated separately.
+        if classpath.is_empty() {
+            return Ok(());
+        }
+        let mut classpath = importer::ClassPath::new(classpath)?;
+        let mut visited: HashSet<IString> =
+            classes.iter().map(|class| class.cpp_name().into()).collect();
+        let mut next = 0;
+        while next < classes.len() {
+            let referenced = classes[next].referenced_classes();
+            next += 1;
+            for class_info in referenced {
+                if !visited.insert(class_info.cpp_class().into())
+                    || STDLIB_DIR
+                        .get_file(format!("{}.hpp", class_info.class_path()))
+                        .is_some()
+                {
+                    continue;
+                }
+                let java_path = class_info.java_path();
+                match classpath.load_class(&java_path)? {
+                    Some(class) => {
+                        println!("\rImported dependency {java_path} from the classpath!");
+                        classes.push(Class::from_java_class(&class)?);
+                    }
+                    None => eprintln!(
+                        "\nClass {java_path} is not a source file, and is not present in the classpath or the standard library!"
+                    ),
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Returns identifiers used in headers of the standard library.
+    fn stdlib_identifiers() -> HashSet<String> {
+        STDLIB_DIR
+            .files()
+            .filter(|file| file.path().extension().is_some_and(|ext| ext == "hpp"))
+            .filter_map(|file| file.contents_utf8())
+            .flat_map(|header| header.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')))
+            .filter(|identifier| !identifier.is_empty())
+            .map(String::from)
+            .collect()
+    }
+    /// Removes classes and methods unreachable from the roots of tree shaking, and writes a report about them to `tree_shaking.txt` in `out`.
+    /// Classes are translated in one run when shaking them, so the report replaces the one of an earlier run. Without any roots(like when
+    /// translating a library), all classes are kept, and no report is written.
+    fn shake(classes: Vec<Class>, ca: &ConvertionArgs) -> std::io::Result<Vec<Class>> {
+        if ca.root.is_empty() && !tree_shaking::has_main(&classes) {
+            println!("\rNo translated class has a `main` method, and no --root was given, so tree shaking is skipped.");
+            return Ok(classes);
+        }
+        let roots: Vec<_> = ca.root.iter().map(|root| tree_shaking::Root::parse(root)).collect();
+        let (classes, report) =
+            tree_shaking::shake(classes, &roots, &Self::stdlib_identifiers());
+        if classes.is_empty() {
+            eprintln!("\nNo translated class or method matches any --root, so nothing would be translated! Pass other roots, or translate without --tree-shaking.");
+            std::process::exit(ERR_NO_ROOTS);
+        }
+        let mut path = ca.out.clone();
+        path.push("tree_shaking.txt");
+        report.write(&mut std::fs::File::create(path)?)?;
+        println!(
+            "\rTree shaking removed {classes} classes and {methods} methods!",
+            classes = report.classes.len(),
+            methods = report.methods.len()
+        );
+        Ok(classes)
+    }
     fn new(ca: &ConvertionArgs) -> Result<(), BytecodeImportError> {
         let mut loaded_classes = Vec::new();
         for (index, path) in ca.source_files.iter().enumerate() {
@@ -343,11 +480,17 @@ impl CompilationContext {
         let mut classes = Vec::with_capacity(loaded_classes.len());
         for (index, class) in loaded_classes.iter().enumerate() {
             print_progress(index, loaded_classes.len());
-            classes.push(Class::from_java_class(class));
+            classes.push(Class::from_java_class(class)?);
         }
+        Self::import_dependencies(&mut classes, &ca.classpath)?;
         println!("\r Finished stage 2(Conversion) of JVM bytecode to C++ translation.");
         std::fs::create_dir_all(&ca.out).unwrap();
         Self::write_stdlib(&ca.out).unwrap();
+        if ca.tree_shaking {
+            classes = Self::shake(classes, ca)?;
+        } else if !ca.root.is_empty() {
+            eprintln!("\n--root has no effect without --tree-shaking!");
+        }
         for (index, class) in classes.iter().enumerate() {
             print_progress(index, classes.len());
             let mut path = ca.out.clone();
@@ -361,7 +504,7 @@ impl CompilationContext {
                     std::process::exit(ERR_BAD_OUT);
                 }
             };
-            match cpp_codegen::create_header(&mut hout, class) {
+            match cpp_codegen::create_header(&mut hout, class, &classes) {
                 Ok(_) => (),
                 Err(err) => {
                     eprintln!(
@@ -377,18 +520,28 @@ impl CompilationContext {
             );
         }
         println!("\r Finished stage 3(Generating headers) of JVM bytecode to C++ translation.");
+        let options = cpp_codegen::CodegenOptions {
+            null_checks: !ca.no_null_checks,
+            bounds_checks: !ca.no_bounds_checks,
+            stack_traces: !ca.no_stack_traces,
+            line_directives: ca.line_directives,
+        };
         for (index, class) in classes.iter().enumerate() {
             print_progress(index, classes.len());
             let mut path = ca.out.clone();
             path.push(&format!("{}", class.path()));
             path.set_extension("cpp");
-            let mut class_cpp_out = std::fs::File::create(path)?;
+            let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let mut class_cpp_out = cpp_codegen::SourceWriter::new(std::fs::File::create(&path)?, &file_name);
             for (_, smethod) in class.static_methods() {
-                cpp_codegen::create_method_impl(&mut class_cpp_out, smethod)?;
+                cpp_codegen::create_method_impl(&mut class_cpp_out, smethod, options)?;
                 writeln!(class_cpp_out)?;
             }
             for (_, smethod) in class.virtual_methods() {
-                cpp_codegen::create_method_impl(&mut class_cpp_out, smethod)?;
+                if smethod.is_abstract() {
+                    continue;
+                }
+                cpp_codegen::create_method_impl(&mut class_cpp_out, smethod, options)?;
                 writeln!(class_cpp_out)?;
             }
             write!(
@@ -396,14 +549,12 @@ impl CompilationContext {
                 "\n#include \"{class_path}.hpp\"\n",
                 class_path = class.path()
             )?;
-            for (static_name, static_type) in class.static_fields() {
-                write!(
-                    class_cpp_out,
-                    "{ctype} {class_path}::{static_name};",
-                    ctype = static_type.c_type(),
-                    class_path = class.path()
-                )?;
-            }
+            cpp_codegen::create_statics(&mut class_cpp_out, class)?;
+            // Each generated file has its own source map, so that files translated by separate runs keep theirs.
+            path.set_extension("cpp.map");
+            let mut source_map = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            class_cpp_out.write_map(&mut source_map)?;
+            source_map.flush()?;
         }
         println!(
             "\r Finished stage 4(Generating Source files) of JVM bytecode to C++ translation."
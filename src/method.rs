@@ -1,18 +1,48 @@
 use crate::{
-    fatops::ClassInfo, method_desc_to_args, FatOp, IString, ImportedJavaClass, VariableType,
+    fatops::{ClassInfo, ExceptionHandler},
+    live_ranges::{split_locals, LocalVariables, SourceLocal},
+    method_desc_to_args,
+    type_inference::{decode_stack_map, infer_types, TypeState},
+    FatOp, IString, ImportedJavaClass, VariableType,
 };
 pub(crate) struct Method {
     is_virtual: bool,
+    /// Whether this is a constructor(`<init>`), taking the constructed object as its first argument.
+    is_constructor: bool,
+    is_abstract: bool,
+    is_synchronized: bool,
     class_name: IString,
     name: IString,
+    java_name: IString,
     ops: Box<[FatOp]>,
+    exceptions: Box<[ExceptionHandler]>,
     args: Vec<VariableType>,
     ret_val: VariableType,
+    /// Types of locals and stack values before each op, or `None` for unreachable ops.
+    types: Box<[Option<TypeState>]>,
+    /// Variables reused local slots are split into.
+    variables: LocalVariables,
+    /// Bytecode offset of each op.
+    pcs: Box<[u16]>,
+    /// Line of Java source each op was compiled from, taken from the `LineNumberTable`.
+    lines: Box<[Option<u16>]>,
+    /// Name of the source file of the class of this method(e.g. `Foo.java`).
+    source_file: Option<IString>,
 }
 impl Method {
     pub(crate) fn is_virtual(&self) -> bool {
         self.is_virtual
     }
+    /// Whether local slot 0 holds `this`, which it does in instance methods and constructors(taking it as their first argument).
+    pub(crate) fn has_this(&self) -> bool {
+        self.is_virtual || self.is_constructor
+    }
+    pub(crate) fn is_abstract(&self) -> bool {
+        self.is_abstract
+    }
+    pub(crate) fn is_synchronized(&self) -> bool {
+        self.is_synchronized
+    }
     pub(crate) fn class_name(&self) -> &str {
         &self.class_name
     }
@@ -22,37 +52,139 @@ impl Method {
     pub(crate) fn name(&self) -> &str {
         &self.name
     }
+    /// Returns the name of this method in Java, qualified with its class(e.g. `java.lang.Object.toString`).
+    pub(crate) fn java_name(&self) -> &str {
+        &self.java_name
+    }
     pub(crate) fn args(&self) -> &[VariableType] {
         &self.args
     }
     pub(crate) fn ops(&self) -> &[FatOp] {
         &self.ops
     }
+    pub(crate) fn exceptions(&self) -> &[ExceptionHandler] {
+        &self.exceptions
+    }
+    pub(crate) fn types(&self) -> &[Option<TypeState>] {
+        &self.types
+    }
+    pub(crate) fn variables(&self) -> &LocalVariables {
+        &self.variables
+    }
+    pub(crate) fn pcs(&self) -> &[u16] {
+        &self.pcs
+    }
+    pub(crate) fn lines(&self) -> &[Option<u16>] {
+        &self.lines
+    }
+    pub(crate) fn source_file(&self) -> Option<&str> {
+        self.source_file.as_deref()
+    }
+    /// Creates a method of `class_name` taking and returning nothing, made of `ops`, for tests which need no bytecode.
+    #[cfg(test)]
+    pub(crate) fn from_ops(class_name: &str, name: &str, is_virtual: bool, ops: Vec<FatOp>) -> Method {
+        Method {
+            is_virtual,
+            is_constructor: name.starts_with("_init__"),
+            is_abstract: false,
+            is_synchronized: false,
+            class_name: class_name.into(),
+            name: name.into(),
+            java_name: format!("{class_name}.{name}").into(),
+            ops: ops.into(),
+            exceptions: [].into(),
+            args: Vec::new(),
+            ret_val: VariableType::Void,
+            types: [].into(),
+            variables: LocalVariables::default(),
+            pcs: [].into(),
+            lines: [].into(),
+            source_file: None,
+        }
+    }
     pub(crate) fn from_raw_method(
         method: &crate::importer::Method,
         name: &str,
         jc: &ImportedJavaClass,
-    ) -> Method {
+    ) -> std::io::Result<Method> {
         let name: IString = name.into();
         let (mut args, ret_val) = method_desc_to_args(method.descriptor(jc));
         let is_virtual = method.is_virtual(jc);
-        let ops = match method.bytecode() {
-            Some(ops) => crate::fatops::expand_ops(ops, jc),
-            None => [].into(),
+        let is_constructor = method.name(jc) == "<init>";
+        let (ops, exceptions) = match method.bytecode() {
+            Some(ops) => (
+                crate::fatops::expand_ops(ops, jc)?,
+                crate::fatops::expand_exceptions(method.exception_table(), ops, jc),
+            ),
+            None => ([].into(), [].into()),
         };
-        if name.contains("_init_") {
+        if is_constructor {
             args.insert(
                 0,
                 VariableType::ObjectRef(ClassInfo::from_java_path(jc.name())),
             )
         }
-        Method {
-            class_name: jc.lookup_class(jc.this_class()).unwrap().into(),
+        let class_name = jc.lookup_class(jc.this_class()).unwrap();
+        // `this` of instance methods is in local slot 0, followed by arguments. Constructors take `this` as their first argument.
+        let entries: Vec<Option<VariableType>> = is_virtual
+            .then(|| VariableType::ObjectRef(ClassInfo::from_java_path(class_name)))
+            .into_iter()
+            .chain(args.iter().cloned())
+            .map(Some)
+            .collect();
+        let entry = TypeState::from_entries(&entries, Vec::new());
+        let frames = match method.bytecode() {
+            Some(bytecode) => decode_stack_map(method.stack_map(), &entries, bytecode, &ops, jc),
+            None => Vec::new(),
+        };
+        let types = infer_types(&ops, &exceptions, entry.clone(), &frames);
+        let mut variables = split_locals(&ops, &exceptions, &types, &entry, &frames);
+        let pcs: Box<[u16]> = method.bytecode().unwrap_or_default().iter().map(|(_, pc)| *pc).collect();
+        // Each op is on the line of the last `LineNumberTable` entry starting at, or before it.
+        let line_numbers = method.line_numbers();
+        let lines = pcs
+            .iter()
+            .map(|pc| {
+                let entries = line_numbers.partition_point(|(start_pc, _)| start_pc <= pc);
+                entries.checked_sub(1).map(|entry| line_numbers[entry].1)
+            })
+            .collect();
+        if method.bytecode().is_some() {
+            let locals: Vec<SourceLocal> = method
+                .local_variable_table()
+                .iter()
+                .filter_map(|local| {
+                    Some(SourceLocal {
+                        slot: local.index,
+                        name: jc.lookup_utf8(local.name_index)?.into(),
+                        scope: local.start_pc..local.start_pc.saturating_add(local.length),
+                    })
+                })
+                .collect();
+            variables.name(&ops, &pcs, &entry, &locals);
+        }
+        Ok(Method {
+            java_name: format!(
+                "{class}.{method}",
+                class = class_name.replace('/', "."),
+                method = method.name(jc)
+            )
+            .into(),
+            class_name: class_name.into(),
             is_virtual,
+            is_constructor,
+            is_abstract: method.is_abstract(),
+            is_synchronized: method.is_synchronized(),
             name,
             args,
             ret_val,
             ops,
-        }
+            exceptions,
+            types,
+            variables,
+            pcs,
+            lines,
+            source_file: jc.source_file().map(IString::from),
+        })
     }
 }
@@ -0,0 +1,370 @@
+use crate::class::{java_class_to_cpp_class, Class};
+use crate::fatops::{ClassInfo, DynamicMethodHandle, FatOp};
+use crate::{IString, Method};
+use std::collections::{HashMap, HashSet};
+/// Mangled name of the entry point of a translated program.
+const MAIN_METHOD: &str = "main__arr_java_cs_lang_cs_String__V";
+/// A method(or all methods of a class) which must be translated, even if nothing calls it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Root {
+    class: IString,
+    method: Option<IString>,
+}
+impl Root {
+    /// Parses a root in the form `pkg/Class`(or `pkg.Class`), optionally followed by `#method`. `method` is the Java name of the method,
+    /// and selects all of its overloads.
+    pub(crate) fn parse(root: &str) -> Self {
+        let (class, method) = match root.split_once('#') {
+            Some((class, method)) => (class, Some(crate::method_name_to_c_name(method))),
+            None => (root, None),
+        };
+        Self {
+            class: java_class_to_cpp_class(&class.replace('.', "/")),
+            method,
+        }
+    }
+    fn selects(&self, method: &str) -> bool {
+        match &self.method {
+            Some(name) => method
+                .strip_prefix(&**name)
+                .is_some_and(|desc| desc.starts_with('_')),
+            None => true,
+        }
+    }
+}
+#[test]
+fn parse_roots() {
+    let root = Root::parse("com.example.Main#run");
+    assert_eq!(&*root.class, "com::example::Main");
+    assert!(root.selects("run__V"));
+    assert!(root.selects("run_I_V"));
+    assert!(!root.selects("runAll__V"));
+    let root = Root::parse("Main");
+    assert_eq!(&*root.class, "Main");
+    assert!(root.method.is_none());
+    assert!(root.selects("anything__V"));
+}
+/// Classes and methods removed by [`shake`], because they are unreachable.
+#[derive(Debug, Default)]
+pub(crate) struct Report {
+    pub(crate) classes: Vec<IString>,
+    pub(crate) methods: Vec<(IString, IString)>,
+}
+impl Report {
+    /// Writes a human-readable list of removed classes and methods to `out`.
+    pub(crate) fn write(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+        writeln!(out, "Removed {} unreachable classes:", self.classes.len())?;
+        for class in &self.classes {
+            writeln!(out, "\t{class}")?;
+        }
+        writeln!(
+            out,
+            "Removed {} unreachable methods of reachable classes:",
+            self.methods.len()
+        )?;
+        for (class, method) in &self.methods {
+            writeln!(out, "\t{class}::{method}")?;
+        }
+        Ok(())
+    }
+}
+/// Finds classes and methods reachable from `roots`.
+struct Reachability<'a> {
+    classes: &'a [Class],
+    /// Indices of `classes`, by their C++ names.
+    indices: HashMap<&'a str, usize>,
+    /// Identifiers used by the standard library. Methods with such names may be called from C++ code jtcpp does not see(e.g. `toString`).
+    external_names: &'a HashSet<String>,
+    kept_classes: HashSet<usize>,
+    kept_methods: HashSet<(usize, &'a str)>,
+    /// Names of methods invoked virtually. All overrides of such methods are reachable in every kept class.
+    virtual_calls: HashSet<&'a str>,
+    worklist: Vec<&'a Method>,
+}
+impl<'a> Reachability<'a> {
+    fn class_index(&self, cpp_name: &str) -> Option<usize> {
+        self.indices.get(cpp_name).copied()
+    }
+    fn keep_class(&mut self, cpp_name: &str) {
+        let Some(index) = self.class_index(cpp_name) else {
+            return;
+        };
+        if !self.kept_classes.insert(index) {
+            return;
+        }
+        let class = &self.classes[index];
+        if let Some(clinit) = class.clinit() {
+            self.keep_method(index, clinit);
+        }
+        self.keep_class(class.parrent_cpp_name());
+        for iface in class.interfaces() {
+            self.keep_class(iface.cpp_class());
+        }
+        let mut field_classes = Vec::new();
+        for (_, ftype) in class.fields().iter().chain(class.static_fields()) {
+            crate::fatops::push_vtype_class(ftype, &mut field_classes);
+        }
+        for field_class in field_classes {
+            self.keep_class(field_class.cpp_class());
+        }
+        for (name, _) in class.virtual_methods() {
+            if self.virtual_calls.contains(&**name) || self.external_names.contains(&**name) {
+                self.keep_method(index, name);
+            }
+        }
+    }
+    fn keep_method(&mut self, class: usize, name: &'a str) {
+        if !self.kept_methods.insert((class, name)) {
+            return;
+        }
+        let class = &self.classes[class];
+        let method = class
+            .static_methods()
+            .iter()
+            .chain(class.virtual_methods())
+            .find(|(method_name, _)| &**method_name == name)
+            .map(|(_, method)| method);
+        if let Some(method) = method {
+            self.worklist.push(method);
+        }
+    }
+    /// Marks the method `name` called on `class` as reachable. If `class` does not declare it, the method is looked up in its superclasses.
+    fn call_exact(&mut self, class: &str, name: &'a str) {
+        let mut class = class;
+        while let Some(index) = self.class_index(class) {
+            let declared = &self.classes[index];
+            let declares = declared
+                .static_methods()
+                .iter()
+                .chain(declared.virtual_methods())
+                .any(|(method_name, _)| &**method_name == name);
+            if declares {
+                self.keep_method(index, name);
+                return;
+            }
+            class = declared.parrent_cpp_name();
+        }
+    }
+    fn call_virtual(&mut self, name: &'a str) {
+        if !self.virtual_calls.insert(name) {
+            return;
+        }
+        let kept: Vec<usize> = self.kept_classes.iter().copied().collect();
+        for index in kept {
+            if self.classes[index].virtual_method(name).is_some() {
+                self.keep_method(index, name);
+            }
+        }
+    }
+    fn visit_handle(&mut self, handle: &'a DynamicMethodHandle) {
+        match handle {
+            DynamicMethodHandle::InvokeStatic(class_info, name, _, _)
+            | DynamicMethodHandle::InvokeSpecial(class_info, name, _, _)
+            | DynamicMethodHandle::NewInvokeSpecial(class_info, name, _) => {
+                self.call_exact(class_info.cpp_class(), name)
+            }
+            DynamicMethodHandle::InvokeVirtual(_, name, _, _)
+            | DynamicMethodHandle::InvokeInterface(_, name, _, _) => self.call_virtual(name),
+            DynamicMethodHandle::GetField(_)
+            | DynamicMethodHandle::GetStatic(_)
+            | DynamicMethodHandle::PutField(_)
+            | DynamicMethodHandle::PutStatic(_) => (),
+        }
+    }
+    fn visit_method(&mut self, method: &'a Method) {
+        let mut classes = Vec::new();
+        for vtype in method
+            .args()
+            .iter()
+            .chain(std::iter::once(method.ret_val()))
+        {
+            crate::fatops::push_vtype_class(vtype, &mut classes);
+        }
+        classes.extend(
+            method
+                .exceptions()
+                .iter()
+                .filter_map(|handler| handler.catch_type.clone()),
+        );
+        for op in method.ops() {
+            op.referenced_classes(&mut classes);
+        }
+        for class in classes.iter().map(ClassInfo::cpp_class) {
+            self.keep_class(class);
+        }
+        for op in method.ops() {
+            match op {
+                FatOp::InvokeStatic(class_info, name, _, _)
+                | FatOp::InvokeSpecial(class_info, name, _, _) => {
+                    self.call_exact(class_info.cpp_class(), name)
+                }
+                FatOp::InvokeVirtual(_, name, _, _) | FatOp::InvokeInterface(_, name, _, _) => {
+                    self.call_virtual(name)
+                }
+                FatOp::InvokeDynamic(handle, _, _, _) | FatOp::Lambda { target: handle, .. } => {
+                    self.visit_handle(handle)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+fn is_main(name: &str, method: &Method) -> bool {
+    !method.is_virtual() && name == MAIN_METHOD
+}
+/// Checks if any of `classes` has a `main` method, which is a root of tree shaking.
+pub(crate) fn has_main(classes: &[Class]) -> bool {
+    classes
+        .iter()
+        .flat_map(|class| class.static_methods().iter().chain(class.virtual_methods()))
+        .any(|(name, method)| is_main(name, method))
+}
+/// Removes classes and methods not reachable from `roots` or the `main` methods of `classes`. Reachability follows calls(including all
+/// overrides of virtually called methods), field accesses and type references. Virtual methods whose names appear in `external_names`
+/// are kept in every reachable class, since they may be called by the standard library.
+pub(crate) fn shake(
+    classes: Vec<Class>,
+    roots: &[Root],
+    external_names: &HashSet<String>,
+) -> (Vec<Class>, Report) {
+    let mut reachability = Reachability {
+        classes: &classes,
+        indices: classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.cpp_name(), index))
+            .collect(),
+        external_names,
+        kept_classes: HashSet::new(),
+        kept_methods: HashSet::new(),
+        virtual_calls: HashSet::new(),
+        worklist: Vec::new(),
+    };
+    for (index, class) in classes.iter().enumerate() {
+        let rooted: Vec<&Root> = roots
+            .iter()
+            .filter(|root| &*root.class == class.cpp_name())
+            .collect();
+        let mut is_root = !rooted.is_empty();
+        for (name, method) in class.static_methods().iter().chain(class.virtual_methods()) {
+            if is_main(name, method) || rooted.iter().any(|root| root.selects(name)) {
+                reachability.keep_method(index, name);
+                if method.is_virtual() {
+                    reachability.call_virtual(name);
+                }
+                is_root = true;
+            }
+        }
+        if is_root {
+            reachability.keep_class(class.cpp_name());
+        }
+    }
+    for root in roots {
+        if reachability.class_index(&root.class).is_none() {
+            eprintln!(
+                "\nTree shaking root {class} is not a translated class!",
+                class = root.class
+            );
+        }
+    }
+    while let Some(method) = reachability.worklist.pop() {
+        reachability.visit_method(method);
+    }
+    let Reachability {
+        kept_classes,
+        kept_methods,
+        ..
+    } = reachability;
+    let kept_methods: HashSet<(usize, IString)> = kept_methods
+        .into_iter()
+        .map(|(class, name)| (class, name.into()))
+        .collect();
+    let mut report = Report::default();
+    let mut shaken = Vec::with_capacity(kept_classes.len());
+    for (index, mut class) in classes.into_iter().enumerate() {
+        if !kept_classes.contains(&index) {
+            report.classes.push(class.cpp_name().into());
+            continue;
+        }
+        let removed = class.retain_methods(|name| kept_methods.contains(&(index, name.into())));
+        report.methods.extend(
+            removed
+                .into_iter()
+                .map(|method| (class.cpp_name().into(), method)),
+        );
+        shaken.push(class);
+    }
+    (shaken, report)
+}
+#[cfg(test)]
+fn method_names(classes: &[Class], class: &str) -> Vec<IString> {
+    let class = crate::class::find_class(classes, class).unwrap();
+    let mut names: Vec<IString> = class
+        .static_methods()
+        .iter()
+        .chain(class.virtual_methods())
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+#[cfg(test)]
+fn call(class: &str, name: &str) -> FatOp {
+    FatOp::InvokeVirtual(ClassInfo::from_java_path(class), name.into(), [].into(), crate::VariableType::Void)
+}
+#[cfg(test)]
+fn main_class(ops: Vec<FatOp>) -> Class {
+    Class::from_methods("Main", "java/lang/Object", &[], vec![Method::from_ops("Main", MAIN_METHOD, false, ops)])
+}
+#[test]
+fn virtual_overrides() {
+    let methods = |class: &str| {
+        ["run__V", "stop__V"]
+            .into_iter()
+            .map(|name| Method::from_ops(class, name, true, vec![FatOp::Return]))
+            .collect()
+    };
+    let classes = vec![
+        Class::from_methods("Base", "java/lang/Object", &[], methods("Base")),
+        Class::from_methods("Derived", "Base", &[], methods("Derived")),
+        // Overrides `run`, but is never created.
+        Class::from_methods("Unused", "Base", &[], methods("Unused")),
+        main_class(vec![FatOp::New(ClassInfo::from_java_path("Derived")), call("Base", "run__V"), FatOp::Return]),
+    ];
+    let (classes, report) = shake(classes, &[], &HashSet::new());
+    // Overrides of `run` are kept in every kept class, while `stop`, which is never called, is removed.
+    assert_eq!(method_names(&classes, "Base"), ["run__V".into()] as [IString; 1]);
+    assert_eq!(method_names(&classes, "Derived"), ["run__V".into()] as [IString; 1]);
+    assert!(crate::class::find_class(&classes, "Unused").is_none());
+    assert_eq!(report.classes, ["Unused".into()] as [IString; 1]);
+    assert_eq!(report.methods.len(), 2);
+}
+#[test]
+fn interface_methods() {
+    let classes = vec![
+        Class::from_methods("Shape", "java/lang/Object", &[], vec![Method::from_ops("Shape", "describe__V", true, Vec::new())]),
+        Class::from_methods(
+            "Square",
+            "java/lang/Object",
+            &["Shape"],
+            vec![
+                Method::from_ops("Square", "describe__V", true, vec![FatOp::Return]),
+                Method::from_ops("Square", "area__V", true, vec![FatOp::Return]),
+                Method::from_ops("Square", "toString__java_cs_lang_cs_String_", true, vec![FatOp::Return]),
+            ],
+        ),
+        main_class(vec![
+            FatOp::New(ClassInfo::from_java_path("Square")),
+            FatOp::InvokeInterface(ClassInfo::from_java_path("Shape"), "describe__V".into(), [].into(), crate::VariableType::Void),
+            FatOp::Return,
+        ]),
+    ];
+    let external_names = HashSet::from(["toString__java_cs_lang_cs_String_".to_owned()]);
+    let (classes, _) = shake(classes, &[], &external_names);
+    // Implementations of interface methods are kept, and so are methods the standard library may call.
+    assert_eq!(method_names(&classes, "Shape"), ["describe__V".into()] as [IString; 1]);
+    assert_eq!(
+        method_names(&classes, "Square"),
+        ["describe__V".into(), "toString__java_cs_lang_cs_String_".into()] as [IString; 2]
+    );
+}
@@ -0,0 +1,743 @@
+//! Infers types of locals and operand stack values before each op of a method. Frames of the `StackMapTable` of a method give exact
+//! types at jump targets and exception handlers, so types only need to be propagated through straight-line code between them. Class
+//! files without a `StackMapTable` are handled by merging types of all paths reaching an op, until they no longer change.
+use crate::fatops::{class_name_to_ftype, find_op_with_offset, ClassInfo, ConcatPart, ExceptionHandler, FatOp};
+use crate::importer::opcodes::OpCode;
+use crate::importer::{StackMapFrame, VerificationType};
+use crate::{ImportedJavaClass, VariableType};
+/// Types of locals and operand stack values at some point of a method.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypeState {
+    /// Types of values in local slots. Slots holding no usable value(including the second slot of a `long` or `double`) are `None`.
+    pub(crate) locals: Vec<Option<VariableType>>,
+    pub(crate) stack: Vec<VariableType>,
+}
+fn object() -> VariableType {
+    VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/Object"))
+}
+fn is_int(vtype: &VariableType) -> bool {
+    matches!(
+        vtype,
+        VariableType::Int | VariableType::Bool | VariableType::Byte | VariableType::Short | VariableType::Char
+    )
+}
+/// Returns the type of a value which may be either of type `a` or `b`, depending on the path taken to an op, or `None` if such a
+/// value is unusable. Different classes are merged to `java.lang.Object`, since their common superclass is not known here.
+pub(crate) fn merge_types(a: &VariableType, b: &VariableType) -> Option<VariableType> {
+    if a == b {
+        Some(a.clone())
+    } else if is_int(a) && is_int(b) {
+        Some(VariableType::Int)
+    } else if a.is_unknown() && b.is_reference() {
+        Some(b.clone())
+    } else if b.is_unknown() && a.is_reference() {
+        Some(a.clone())
+    } else if a.is_reference() && b.is_reference() {
+        match (a, b) {
+            (VariableType::ArrayRef(a), VariableType::ArrayRef(b)) if a.is_reference() && b.is_reference() => {
+                merge_types(a, b).map(|element| VariableType::ArrayRef(Box::new(element)))
+            }
+            _ => Some(object()),
+        }
+    } else {
+        None
+    }
+}
+impl TypeState {
+    /// Creates the state at the start of a method, with `entries` in consecutive local slots. `long`s and `double`s take up two slots.
+    pub(crate) fn from_entries(entries: &[Option<VariableType>], stack: Vec<VariableType>) -> Self {
+        let mut locals = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let is_wide = entry.as_ref().is_some_and(VariableType::is_wide);
+            locals.push(entry.clone());
+            if is_wide {
+                locals.push(None);
+            }
+        }
+        Self { locals, stack }
+    }
+    /// Merges `other` into `self`, returning `true` if `self` changed.
+    fn merge(&mut self, other: &Self) -> bool {
+        let mut changed = false;
+        if self.locals.len() > other.locals.len() {
+            changed |= self.locals[other.locals.len()..].iter().any(Option::is_some);
+            self.locals.truncate(other.locals.len());
+        }
+        for (local, other) in self.locals.iter_mut().zip(other.locals.iter()) {
+            let merged = match (&*local, other) {
+                (Some(local), Some(other)) => merge_types(local, other),
+                _ => None,
+            };
+            changed |= merged != *local;
+            *local = merged;
+        }
+        for (value, other) in self.stack.iter_mut().zip(other.stack.iter()) {
+            if let Some(merged) = merge_types(value, other) {
+                changed |= merged != *value;
+                *value = merged;
+            }
+        }
+        changed
+    }
+    fn push(&mut self, vtype: VariableType) {
+        self.stack.push(vtype);
+    }
+    fn pop(&mut self) -> VariableType {
+        self.stack.pop().unwrap_or_else(VariableType::unknown)
+    }
+    fn pop_n(&mut self, count: usize) {
+        for _ in 0..count {
+            self.pop();
+        }
+    }
+    fn load(&mut self, slot: u16) {
+        let vtype = self
+            .locals
+            .get(slot as usize)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(VariableType::unknown);
+        self.push(vtype);
+    }
+    fn store(&mut self, slot: u16) {
+        let vtype = self.pop();
+        let slot = slot as usize;
+        let end = if vtype.is_wide() { slot + 2 } else { slot + 1 };
+        if self.locals.len() < end {
+            self.locals.resize(end, None);
+        }
+        // Overwriting the second half of a `long` or `double` makes the whole value unusable.
+        if slot > 0 && self.locals[slot - 1].as_ref().is_some_and(VariableType::is_wide) {
+            self.locals[slot - 1] = None;
+        }
+        self.locals[slot] = Some(vtype);
+        if end == slot + 2 {
+            self.locals[slot + 1] = None;
+        }
+    }
+    /// Changes the state to the one after executing `op`.
+    fn step(&mut self, op: &FatOp) {
+        match op {
+            FatOp::AConstNull => self.push(VariableType::unknown()),
+            FatOp::IConst(_) | FatOp::BConst(_) | FatOp::SConst(_) => self.push(VariableType::Int),
+            FatOp::LConst(_) => self.push(VariableType::Long),
+            FatOp::FConst(_) => self.push(VariableType::Float),
+            FatOp::DConst(_) => self.push(VariableType::Double),
+            FatOp::StringConst(_) => self.push(VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/String"))),
+            FatOp::ClassConst(_) => self.push(VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/Class"))),
+            FatOp::ALoad(slot) => self.load(*slot),
+            FatOp::ILoad(_) => self.push(VariableType::Int),
+            FatOp::LLoad(_) => self.push(VariableType::Long),
+            FatOp::FLoad(_) => self.push(VariableType::Float),
+            FatOp::DLoad(_) => self.push(VariableType::Double),
+            FatOp::AStore(slot) | FatOp::IStore(slot) | FatOp::LStore(slot) | FatOp::FStore(slot) | FatOp::DStore(slot) => {
+                self.store(*slot)
+            }
+            FatOp::IInc(..) | FatOp::GoTo(_) | FatOp::Return => (),
+            FatOp::IAdd
+            | FatOp::ISub
+            | FatOp::IMul
+            | FatOp::IDiv
+            | FatOp::IRem
+            | FatOp::IShl
+            | FatOp::IShr
+            | FatOp::IUShr
+            | FatOp::IAnd
+            | FatOp::IOr
+            | FatOp::IXOr
+            | FatOp::LCmp
+            | FatOp::FCmpL
+            | FatOp::FCmpG
+            | FatOp::DCmpL
+            | FatOp::DCmpG => {
+                self.pop_n(2);
+                self.push(VariableType::Int);
+            }
+            FatOp::LAdd
+            | FatOp::LSub
+            | FatOp::LMul
+            | FatOp::LDiv
+            | FatOp::LRem
+            | FatOp::LShl
+            | FatOp::LShr
+            | FatOp::LUShr
+            | FatOp::LAnd
+            | FatOp::LOr
+            | FatOp::LXOr => {
+                self.pop_n(2);
+                self.push(VariableType::Long);
+            }
+            FatOp::FAdd | FatOp::FSub | FatOp::FMul | FatOp::FDiv | FatOp::FRem => {
+                self.pop_n(2);
+                self.push(VariableType::Float);
+            }
+            FatOp::DAdd | FatOp::DSub | FatOp::DMul | FatOp::DDiv | FatOp::DRem => {
+                self.pop_n(2);
+                self.push(VariableType::Double);
+            }
+            FatOp::INeg | FatOp::LNeg | FatOp::FNeg | FatOp::DNeg => {
+                let value = self.pop();
+                self.push(value);
+            }
+            FatOp::L2I | FatOp::F2I | FatOp::D2I | FatOp::ArrayLength | FatOp::InstanceOf(_) => {
+                self.pop();
+                self.push(VariableType::Int);
+            }
+            FatOp::I2L | FatOp::F2L | FatOp::D2L => {
+                self.pop();
+                self.push(VariableType::Long);
+            }
+            FatOp::I2F | FatOp::L2F | FatOp::D2F => {
+                self.pop();
+                self.push(VariableType::Float);
+            }
+            FatOp::I2D | FatOp::L2D | FatOp::F2D => {
+                self.pop();
+                self.push(VariableType::Double);
+            }
+            FatOp::I2B => {
+                self.pop();
+                self.push(VariableType::Byte);
+            }
+            FatOp::I2C => {
+                self.pop();
+                self.push(VariableType::Char);
+            }
+            FatOp::I2S => {
+                self.pop();
+                self.push(VariableType::Short);
+            }
+            FatOp::InvokeVirtual(_, _, args, ret)
+            | FatOp::InvokeInterface(_, _, args, ret)
+            | FatOp::InvokeSpecial(_, _, args, ret) => {
+                self.pop_n(args.len() + 1);
+                if *ret != VariableType::Void {
+                    self.push(ret.clone());
+                }
+            }
+            FatOp::InvokeStatic(_, _, args, ret) | FatOp::InvokeDynamic(_, _, args, ret) => {
+                self.pop_n(args.len());
+                if *ret != VariableType::Void {
+                    self.push(ret.clone());
+                }
+            }
+            FatOp::StringConcat(parts) => {
+                self.pop_n(parts.iter().filter(|part| matches!(part, ConcatPart::Arg(_))).count());
+                self.push(VariableType::ObjectRef(ClassInfo::from_java_path("java/lang/String")));
+            }
+            FatOp::Lambda {
+                interface, captured, ..
+            } => {
+                self.pop_n(captured.len());
+                self.push(VariableType::ObjectRef(interface.clone()));
+            }
+            FatOp::ZGetStatic(..) => self.push(VariableType::Bool),
+            FatOp::BGetStatic(..) => self.push(VariableType::Byte),
+            FatOp::SGetStatic(..) => self.push(VariableType::Short),
+            FatOp::CGetStatic(..) => self.push(VariableType::Char),
+            FatOp::IGetStatic(..) => self.push(VariableType::Int),
+            FatOp::LGetStatic(..) => self.push(VariableType::Long),
+            FatOp::FGetStatic(..) => self.push(VariableType::Float),
+            FatOp::DGetStatic(..) => self.push(VariableType::Double),
+            FatOp::AGetStatic { type_info, .. } => self.push(VariableType::ObjectRef(type_info.clone())),
+            FatOp::AAGetStatic { atype, .. } => self.push(VariableType::ArrayRef(Box::new(atype.clone()))),
+            FatOp::ZGetField(..)
+            | FatOp::BGetField(..)
+            | FatOp::SGetField(..)
+            | FatOp::CGetField(..)
+            | FatOp::IGetField(..)
+            | FatOp::LGetField(..)
+            | FatOp::FGetField(..)
+            | FatOp::DGetField(..)
+            | FatOp::AGetField { .. }
+            | FatOp::AAGetField { .. } => {
+                self.pop();
+                let field_type = match op {
+                    FatOp::ZGetField(..) => VariableType::Bool,
+                    FatOp::BGetField(..) => VariableType::Byte,
+                    FatOp::SGetField(..) => VariableType::Short,
+                    FatOp::CGetField(..) => VariableType::Char,
+                    FatOp::IGetField(..) => VariableType::Int,
+                    FatOp::LGetField(..) => VariableType::Long,
+                    FatOp::FGetField(..) => VariableType::Float,
+                    FatOp::DGetField(..) => VariableType::Double,
+                    FatOp::AGetField { type_info, .. } => VariableType::ObjectRef(type_info.clone()),
+                    FatOp::AAGetField { atype, .. } => VariableType::ArrayRef(Box::new(atype.clone())),
+                    _ => unreachable!(),
+                };
+                self.push(field_type);
+            }
+            FatOp::ZPutStatic(..)
+            | FatOp::BPutStatic(..)
+            | FatOp::SPutStatic(..)
+            | FatOp::CPutStatic(..)
+            | FatOp::IPutStatic(..)
+            | FatOp::LPutStatic(..)
+            | FatOp::FPutStatic(..)
+            | FatOp::DPutStatic(..)
+            | FatOp::APutStatic { .. }
+            | FatOp::AAPutStatic { .. }
+            | FatOp::AReturn
+            | FatOp::IReturn
+            | FatOp::LReturn
+            | FatOp::FReturn
+            | FatOp::DReturn
+            | FatOp::Throw
+            | FatOp::MonitorEnter
+            | FatOp::MonitorExit
+            | FatOp::Pop
+            | FatOp::IfGreterEqualZero(_)
+            | FatOp::IfGreterZero(_)
+            | FatOp::IfLessZero(_)
+            | FatOp::IfLessEqualZero(_)
+            | FatOp::IfNull(_)
+            | FatOp::IfNotNull(_)
+            | FatOp::IfZero(_)
+            | FatOp::IfNotZero(_)
+            | FatOp::LookupSwitch { .. }
+            | FatOp::TableSwitch { .. } => {
+                self.pop();
+            }
+            FatOp::ZPutField(..)
+            | FatOp::BPutField(..)
+            | FatOp::SPutField(..)
+            | FatOp::CPutField(..)
+            | FatOp::IPutField(..)
+            | FatOp::LPutField(..)
+            | FatOp::FPutField(..)
+            | FatOp::DPutField(..)
+            | FatOp::APutField { .. }
+            | FatOp::AAPutField { .. }
+            | FatOp::IfACmpEq(_)
+            | FatOp::IfACmpNe(_)
+            | FatOp::IfICmpEq(_)
+            | FatOp::IfICmpNe(_)
+            | FatOp::IfICmpGreater(_)
+            | FatOp::IfIGreterEqual(_)
+            | FatOp::IfICmpLess(_)
+            | FatOp::IfICmpLessEqual(_) => self.pop_n(2),
+            FatOp::AAStore
+            | FatOp::BAStore
+            | FatOp::CAStore
+            | FatOp::DAStore
+            | FatOp::FAStore
+            | FatOp::IAStore
+            | FatOp::LAStore
+            | FatOp::SAStore => self.pop_n(3),
+            FatOp::AALoad => {
+                self.pop();
+                let element = match self.pop() {
+                    VariableType::ArrayRef(element) => *element,
+                    _ => VariableType::unknown(),
+                };
+                self.push(element);
+            }
+            FatOp::BALoad
+            | FatOp::CALoad
+            | FatOp::SALoad
+            | FatOp::IALoad
+            | FatOp::LALoad
+            | FatOp::FALoad
+            | FatOp::DALoad => {
+                self.pop_n(2);
+                self.push(match op {
+                    FatOp::BALoad => VariableType::Byte,
+                    FatOp::CALoad => VariableType::Char,
+                    FatOp::SALoad => VariableType::Short,
+                    FatOp::IALoad => VariableType::Int,
+                    FatOp::LALoad => VariableType::Long,
+                    FatOp::FALoad => VariableType::Float,
+                    _ => VariableType::Double,
+                });
+            }
+            FatOp::New(class_info) => self.push(VariableType::ObjectRef(class_info.clone())),
+            FatOp::CheckedCast(vtype) => {
+                self.pop();
+                self.push(vtype.clone());
+            }
+            FatOp::ANewArray(class_info) => {
+                self.pop();
+                self.push(VariableType::ArrayRef(Box::new(VariableType::ObjectRef(class_info.clone()))));
+            }
+            FatOp::BNewArray
+            | FatOp::CNewArray
+            | FatOp::DNewArray
+            | FatOp::FNewArray
+            | FatOp::INewArray
+            | FatOp::LNewArray
+            | FatOp::SNewArray
+            | FatOp::ZNewArray => {
+                self.pop();
+                let element = match op {
+                    FatOp::BNewArray => VariableType::Byte,
+                    FatOp::CNewArray => VariableType::Char,
+                    FatOp::DNewArray => VariableType::Double,
+                    FatOp::FNewArray => VariableType::Float,
+                    FatOp::INewArray => VariableType::Int,
+                    FatOp::LNewArray => VariableType::Long,
+                    FatOp::SNewArray => VariableType::Short,
+                    _ => VariableType::Bool,
+                };
+                self.push(VariableType::ArrayRef(Box::new(element)));
+            }
+            FatOp::MultiANewArray(atype, dimensions) => {
+                self.pop_n(*dimensions as usize);
+                self.push(atype.clone());
+            }
+            FatOp::Dup => {
+                let value = self.pop();
+                self.push(value.clone());
+                self.push(value);
+            }
+            FatOp::DupX1 => {
+                let value = self.pop();
+                let value_2 = self.pop();
+                self.stack.extend([value.clone(), value_2, value]);
+            }
+            FatOp::DupX2 => {
+                let value = self.pop();
+                let value_2 = self.pop();
+                if value_2.is_wide() {
+                    self.stack.extend([value.clone(), value_2, value]);
+                } else {
+                    let value_3 = self.pop();
+                    self.stack.extend([value.clone(), value_3, value_2, value]);
+                }
+            }
+            FatOp::Dup2 => {
+                let value = self.pop();
+                if value.is_wide() {
+                    self.stack.extend([value.clone(), value]);
+                } else {
+                    let value_2 = self.pop();
+                    self.stack.extend([value_2.clone(), value.clone(), value_2, value]);
+                }
+            }
+            FatOp::Dup2X1 => {
+                let value = self.pop();
+                let value_2 = self.pop();
+                if value.is_wide() {
+                    self.stack.extend([value.clone(), value_2, value]);
+                } else {
+                    let value_3 = self.pop();
+                    self.stack.extend([value_2.clone(), value.clone(), value_3, value_2, value]);
+                }
+            }
+            FatOp::Dup2X2 => {
+                // Values taking up two stack words are kept together, and duplicated below the next two words.
+                let mut top = vec![self.pop()];
+                if !top[0].is_wide() {
+                    top.insert(0, self.pop());
+                }
+                let mut below = vec![self.pop()];
+                if !below[0].is_wide() {
+                    below.insert(0, self.pop());
+                }
+                self.stack.extend(top.iter().cloned());
+                self.stack.extend(below);
+                self.stack.extend(top);
+            }
+            FatOp::Swap => {
+                let value = self.pop();
+                let value_2 = self.pop();
+                self.stack.extend([value, value_2]);
+            }
+            FatOp::Pop2 => {
+                if !self.pop().is_wide() {
+                    self.pop();
+                }
+            }
+        }
+    }
+}
+/// Returns the type of the exception on the stack at the start of the handler at op `handler`. If a handler catches multiple
+/// exception types(`catch(A | B e)`), it only knows the exception is a `Throwable`.
+pub(crate) fn handler_catch_type(handler: usize, handlers: &[ExceptionHandler]) -> ClassInfo {
+    let mut catch_types = handlers
+        .iter()
+        .filter(|other| other.handler == handler)
+        .map(|other| &other.catch_type);
+    let first = catch_types.next().cloned().flatten();
+    match first {
+        Some(first) if catch_types.all(|other| other.as_ref() == Some(&first)) => first,
+        _ => ClassInfo::from_java_path("java/lang/Throwable"),
+    }
+}
+/// Converts a type from a `StackMapTable` frame into a `VariableType`. `Top` has no usable value, and is converted to `None`.
+fn verification_type(
+    vtype: &VerificationType,
+    ops: &[(OpCode, u16)],
+    fatops: &[FatOp],
+    jc: &ImportedJavaClass,
+) -> Option<VariableType> {
+    match vtype {
+        VerificationType::Top => None,
+        VerificationType::Integer => Some(VariableType::Int),
+        VerificationType::Float => Some(VariableType::Float),
+        VerificationType::Long => Some(VariableType::Long),
+        VerificationType::Double => Some(VariableType::Double),
+        VerificationType::Null => Some(VariableType::unknown()),
+        VerificationType::UninitializedThis => Some(VariableType::ObjectRef(ClassInfo::from_java_path(jc.name()))),
+        VerificationType::Object(index) => Some(class_name_to_ftype(jc.lookup_class(*index)?)),
+        VerificationType::Uninitialized(offset) => match fatops.get(find_op_with_offset(ops, *offset)?) {
+            Some(FatOp::New(class_info)) => Some(VariableType::ObjectRef(class_info.clone())),
+            _ => None,
+        },
+    }
+}
+/// Decodes `frames` of a `StackMapTable` into states at ops they describe. `entries` are the types of arguments(and `this`) of the method.
+pub(crate) fn decode_stack_map(
+    frames: &[StackMapFrame],
+    entries: &[Option<VariableType>],
+    ops: &[(OpCode, u16)],
+    fatops: &[FatOp],
+    jc: &ImportedJavaClass,
+) -> Vec<(usize, TypeState)> {
+    let convert = |vtypes: &[VerificationType]| -> Vec<Option<VariableType>> {
+        vtypes
+            .iter()
+            .map(|vtype| verification_type(vtype, ops, fatops, jc))
+            .collect()
+    };
+    let mut locals = entries.to_vec();
+    let mut offset: Option<u16> = None;
+    let mut states = Vec::with_capacity(frames.len());
+    for frame in frames {
+        // Each frame but the first one is at least one byte past the previous frame.
+        let frame_offset = match offset {
+            Some(offset) => offset + frame.offset_delta() + 1,
+            None => frame.offset_delta(),
+        };
+        offset = Some(frame_offset);
+        let stack = match frame {
+            StackMapFrame::Same { .. } => Vec::new(),
+            StackMapFrame::SameLocals1StackItem { stack, .. } => convert(std::slice::from_ref(stack)),
+            StackMapFrame::Chop { chopped, .. } => {
+                locals.truncate(locals.len().saturating_sub(*chopped as usize));
+                Vec::new()
+            }
+            StackMapFrame::Append { locals: appended, .. } => {
+                locals.extend(convert(appended));
+                Vec::new()
+            }
+            StackMapFrame::Full {
+                locals: full_locals,
+                stack,
+                ..
+            } => {
+                locals = convert(full_locals);
+                convert(stack)
+            }
+        };
+        let stack = stack
+            .into_iter()
+            .map(|vtype| vtype.unwrap_or_else(VariableType::unknown))
+            .collect();
+        if let Some(index) = find_op_with_offset(ops, frame_offset) {
+            states.push((index, TypeState::from_entries(&locals, stack)));
+        }
+    }
+    states
+}
+/// Infers the state before each op in `ops`, starting with `entry`. States at ops described by `frames` are taken from them as-is.
+/// Unreachable ops have no state.
+pub(crate) fn infer_types(
+    ops: &[FatOp],
+    handlers: &[ExceptionHandler],
+    entry: TypeState,
+    frames: &[(usize, TypeState)],
+) -> Box<[Option<TypeState>]> {
+    let mut states: Vec<Option<TypeState>> = vec![None; ops.len()];
+    let mut from_frame = vec![false; ops.len()];
+    for (index, state) in frames {
+        states[*index] = Some(state.clone());
+        from_frame[*index] = true;
+    }
+    if ops.is_empty() {
+        return states.into();
+    }
+    let mut visited = vec![false; ops.len()];
+    let mut pending = vec![0];
+    if !from_frame[0] {
+        states[0] = Some(entry);
+    }
+    visited[0] = true;
+    // Passes `state` to op `target`, marking it as pending if its state changed.
+    let flow = |target: usize,
+                state: &TypeState,
+                states: &mut Vec<Option<TypeState>>,
+                visited: &mut Vec<bool>,
+                pending: &mut Vec<usize>| {
+        let changed = match (&mut states[target], from_frame[target]) {
+            (_, true) => false,
+            (Some(target_state), false) => target_state.merge(state),
+            (target_state @ None, false) => {
+                *target_state = Some(state.clone());
+                true
+            }
+        };
+        if changed || !visited[target] {
+            visited[target] = true;
+            pending.push(target);
+        }
+    };
+    while let Some(index) = pending.pop() {
+        let Some(mut state) = states[index].clone() else {
+            continue;
+        };
+        // Handlers may be entered before, or after any op in their range, with the exception being the only value on the stack.
+        let covering: Vec<&ExceptionHandler> = handlers.iter().filter(|handler| handler.covers(index)).collect();
+        let handler_state = |state: &TypeState, handler: &ExceptionHandler| TypeState {
+            locals: state.locals.clone(),
+            stack: vec![VariableType::ObjectRef(handler_catch_type(handler.handler, handlers))],
+        };
+        for handler in &covering {
+            flow(handler.handler, &handler_state(&state, handler), &mut states, &mut visited, &mut pending);
+        }
+        let op = &ops[index];
+        state.step(op);
+        for handler in &covering {
+            flow(handler.handler, &handler_state(&state, handler), &mut states, &mut visited, &mut pending);
+        }
+        for target in op.jump_target().unwrap_or_default() {
+            flow(target, &state, &mut states, &mut visited, &mut pending);
+        }
+        if op.falls_through() && index + 1 < ops.len() {
+            flow(index + 1, &state, &mut states, &mut visited, &mut pending);
+        }
+    }
+    states.into()
+}
+#[cfg(test)]
+fn class_type(name: &str) -> VariableType {
+    VariableType::ObjectRef(ClassInfo::from_java_path(name))
+}
+/// Loads a class named `Test` with no members, which has `java/lang/String` at constant pool index 4.
+#[cfg(test)]
+fn test_class() -> ImportedJavaClass {
+    let mut data = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 61, 0, 5, 1, 0, 4];
+    data.extend_from_slice(b"Test");
+    data.extend_from_slice(&[7, 0, 1, 1, 0, 16]);
+    data.extend_from_slice(b"java/lang/String");
+    // Class #4, access flags, this and super class, and no interfaces, fields, methods or attributes.
+    data.extend_from_slice(&[7, 0, 3, 0, 0x21, 0, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0]);
+    crate::importer::load_class(&mut &data[..]).unwrap()
+}
+#[test]
+fn merging() {
+    let string = class_type("java/lang/String");
+    let class = class_type("java/lang/Class");
+    assert_eq!(merge_types(&string, &string), Some(string.clone()));
+    assert_eq!(merge_types(&VariableType::Byte, &VariableType::Char), Some(VariableType::Int));
+    assert_eq!(merge_types(&string, &class), Some(object()));
+    assert_eq!(merge_types(&VariableType::unknown(), &string), Some(string.clone()));
+    let strings = VariableType::ArrayRef(Box::new(string.clone()));
+    let classes = VariableType::ArrayRef(Box::new(class));
+    let objects = VariableType::ArrayRef(Box::new(object()));
+    assert_eq!(merge_types(&strings, &classes), Some(objects));
+    let ints = VariableType::ArrayRef(Box::new(VariableType::Int));
+    assert_eq!(merge_types(&strings, &ints), Some(object()));
+    assert_eq!(merge_types(&string, &VariableType::Int), None);
+    assert_eq!(merge_types(&VariableType::Long, &VariableType::Double), None);
+}
+#[test]
+fn merge_at_join() {
+    // Slot 1 holds a `String` or a `Class`, slot 2 an `int` or a `String`, and slot 3 a `byte` or a `char` after the branches join.
+    let ops = [
+        FatOp::ILoad(0),
+        FatOp::IfZero(9),
+        FatOp::StringConst("a".into()),
+        FatOp::AStore(1),
+        FatOp::IConst(1),
+        FatOp::IStore(2),
+        FatOp::IConst(1),
+        FatOp::I2B,
+        FatOp::GoTo(15),
+        FatOp::ClassConst(ClassInfo::from_java_path("java/lang/Object")),
+        FatOp::AStore(1),
+        FatOp::StringConst("b".into()),
+        FatOp::AStore(2),
+        FatOp::IConst(1),
+        FatOp::I2C,
+        FatOp::IStore(3),
+        FatOp::Return,
+    ];
+    let entry = TypeState::from_entries(&[Some(VariableType::Int)], Vec::new());
+    let types = infer_types(&ops, &[], entry, &[]);
+    let join = types[15].as_ref().unwrap();
+    assert_eq!(join.locals, [Some(VariableType::Int), Some(object()), None]);
+    assert_eq!(join.stack, [VariableType::Int]);
+    assert_eq!(types[16].as_ref().unwrap().locals[3], Some(VariableType::Int));
+}
+#[test]
+fn frames_take_precedence() {
+    // The frame at the join gives the exact type of the local, instead of the merged `Object`.
+    let ops = [
+        FatOp::ILoad(0),
+        FatOp::IfZero(5),
+        FatOp::StringConst("a".into()),
+        FatOp::AStore(1),
+        FatOp::GoTo(7),
+        FatOp::AConstNull,
+        FatOp::AStore(1),
+        FatOp::ALoad(1),
+        FatOp::AReturn,
+    ];
+    let entry = TypeState::from_entries(&[Some(VariableType::Int)], Vec::new());
+    let frame = TypeState::from_entries(&[Some(VariableType::Int), Some(class_type("java/lang/CharSequence"))], Vec::new());
+    let types = infer_types(&ops, &[], entry, &[(7, frame.clone())]);
+    assert_eq!(types[7].as_ref(), Some(&frame));
+    assert_eq!(types[8].as_ref().unwrap().stack, [class_type("java/lang/CharSequence")]);
+    // Without frames, a `null` merged with a `String` is a `String`.
+    let entry = TypeState::from_entries(&[Some(VariableType::Int)], Vec::new());
+    let types = infer_types(&ops, &[], entry, &[]);
+    assert_eq!(types[8].as_ref().unwrap().stack, [class_type("java/lang/String")]);
+}
+#[test]
+fn decode_frames() {
+    use crate::importer::{StackMapFrame as Frame, VerificationType as V};
+    let jc = test_class();
+    // One op at each offset, with a `new` at offset 0.
+    let ops: Vec<(OpCode, u16)> = (0..10).map(|offset| (if offset == 0 { OpCode::New(4) } else { OpCode::Nop }, offset)).collect();
+    let mut fatops = vec![FatOp::New(ClassInfo::from_java_path("java/lang/String"))];
+    fatops.resize(10, FatOp::Return);
+    let frames = [
+        Frame::Same { offset_delta: 2 },
+        Frame::SameLocals1StackItem {
+            offset_delta: 0,
+            stack: V::Integer,
+        },
+        Frame::Append {
+            offset_delta: 1,
+            locals: [V::Long, V::Object(4)].into(),
+        },
+        Frame::Chop {
+            offset_delta: 0,
+            chopped: 1,
+        },
+        Frame::Full {
+            offset_delta: 1,
+            locals: [V::UninitializedThis, V::Top, V::Uninitialized(0)].into(),
+            stack: [V::Null].into(),
+        },
+        Frame::Same { offset_delta: 0 },
+    ];
+    let this = class_type("Test");
+    let string = class_type("java/lang/String");
+    let entries = [Some(this.clone()), Some(VariableType::Int)];
+    let states = decode_stack_map(&frames, &entries, &ops, &fatops, &jc);
+    // Each frame but the first one is `offset_delta + 1` bytes past the previous one.
+    let offsets: Vec<usize> = states.iter().map(|(index, _)| *index).collect();
+    assert_eq!(offsets, [2, 3, 5, 6, 8, 9]);
+    let locals: Vec<&[Option<VariableType>]> = states.iter().map(|(_, state)| &state.locals[..]).collect();
+    let (int, long) = (Some(VariableType::Int), Some(VariableType::Long));
+    assert_eq!(locals[0], [Some(this.clone()), int.clone()]);
+    assert_eq!(states[1].1.stack, [VariableType::Int]);
+    // The second slot of a `long` holds no usable value.
+    assert_eq!(locals[2], [Some(this.clone()), int.clone(), long.clone(), None, Some(string.clone())]);
+    assert_eq!(locals[3], [Some(this.clone()), int, long, None]);
+    assert_eq!(locals[4], [Some(this.clone()), None, Some(string)]);
+    assert_eq!(states[4].1.stack, [VariableType::unknown()]);
+    assert_eq!(locals[5], locals[4]);
+    assert!(states[5].1.stack.is_empty());
+}
@@ -1,4 +1,5 @@
 #include "java_cs_io_cs_OutputStream.hpp"
+#include "java_cs_lang_cs_IndexOutOfBoundsException.hpp"
 #define min(a, b) (((a) < (b)) ? (a) : (b))
 #include <stdio.h>
 #include <cstring>
@@ -32,7 +33,11 @@ void java::io::OutputStream::close__V(){
     this->out_stream->close();
 }
 void java::io::OutputStream::write__arr_BII_V(ManagedPointer<RuntimeArray<int8_t>> arr,int off, int len){
-    assert(off + len < arr->GetLength());
+    if(off < 0 || len < 0 || len > arr->GetLength() - off){
+        ManagedPointer<java::lang::IndexOutOfBoundsException> exception = new_managed(java::lang::IndexOutOfBoundsException,);
+        java::lang::Throwable::_init___V(exception);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
     int8_t *buffer = arr->GetPtr(off);
     this->out_stream->write(buffer,len);
 }
@@ -0,0 +1,26 @@
+#pragma once
+#include "java_cs_lang_cs_RuntimeException.hpp"
+namespace java{namespace lang{class ArithmeticException;};};
+class java::lang::ArithmeticException: public java::lang::RuntimeException{
+    public:
+    virtual ~ArithmeticException() = default;
+};
+// Throws `java::lang::ArithmeticException` reporting an integer division by zero.
+[[noreturn]] inline void throw_division_by_zero(){
+    ManagedPointer<java::lang::ArithmeticException> exception = managed_from_raw(new java::lang::ArithmeticException());
+    ManagedPointer<java::lang::String> message = managed_from_raw(new java::lang::String(u"/ by zero"));
+    java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,message);
+    throw ManagedPointer<java::lang::Throwable>(exception);
+}
+// Divides `a` by `b`, throwing `java::lang::ArithmeticException` if `b` is 0. Dividing the minimal value of `T` by -1 overflows back to it.
+template<typename T> inline T java_div(T a,T b){
+    if(b == 0)throw_division_by_zero();
+    if(b == -1)return java_neg<T>(a);
+    return a / b;
+}
+// Remainder of dividing `a` by `b`, throwing `java::lang::ArithmeticException` if `b` is 0.
+template<typename T> inline T java_rem(T a,T b){
+    if(b == 0)throw_division_by_zero();
+    if(b == -1)return 0;
+    return a % b;
+}
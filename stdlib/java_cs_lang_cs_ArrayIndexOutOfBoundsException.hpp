@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_IndexOutOfBoundsException.hpp"
+namespace java{namespace lang{class ArrayIndexOutOfBoundsException;};};
+class java::lang::ArrayIndexOutOfBoundsException: public java::lang::IndexOutOfBoundsException{
+    public:
+    virtual ~ArrayIndexOutOfBoundsException() = default;
+};
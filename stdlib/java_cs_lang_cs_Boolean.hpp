@@ -0,0 +1,17 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Boolean;};};
+class java::lang::Boolean: public java::lang::Object{
+    bool value;
+    public:
+    Boolean(bool value):value(value){}
+    virtual ~Boolean() = default;
+    static ManagedPointer<Boolean> valueOf_Z_java_cs_lang_cs_Boolean_(bool value){return managed_from_raw(new Boolean(value));}
+    virtual bool booleanValue__Z(){return value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_Z_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Boolean> box = managed_cast<Boolean>(other);
+        return box != nullptr && value == box->value;
+    }
+};
@@ -0,0 +1,21 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Byte;};};
+class java::lang::Byte: public java::lang::Number{
+    int8_t value;
+    public:
+    Byte(int8_t value):value(value){}
+    virtual ~Byte() = default;
+    static ManagedPointer<Byte> valueOf_B_java_cs_lang_cs_Byte_(int8_t value){return managed_from_raw(new Byte(value));}
+    virtual int32_t intValue__I(){return (int32_t)value;}
+    virtual int64_t longValue__J(){return (int64_t)value;}
+    virtual float floatValue__F(){return (float)value;}
+    virtual double doubleValue__D(){return (double)value;}
+    virtual int8_t byteValue__B(){return value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_I_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Byte> box = managed_cast<Byte>(other);
+        return box != nullptr && value == box->value;
+    }
+};
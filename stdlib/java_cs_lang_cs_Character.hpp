@@ -0,0 +1,17 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Character;};};
+class java::lang::Character: public java::lang::Object{
+    char16_t value;
+    public:
+    Character(char16_t value):value(value){}
+    virtual ~Character() = default;
+    static ManagedPointer<Character> valueOf_C_java_cs_lang_cs_Character_(char16_t value){return managed_from_raw(new Character(value));}
+    virtual char16_t charValue__C(){return value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_C_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Character> box = managed_cast<Character>(other);
+        return box != nullptr && value == box->value;
+    }
+};
@@ -0,0 +1,21 @@
+#include "java_cs_lang_cs_Class.hpp"
+#include <map>
+java::lang::Class::Class(std::u16string name):name(name){}
+ManagedPointer<java::lang::Class> java::lang::Class::for_name(const char16_t* name){
+    static std::mutex classes_lock;
+    static std::map<std::u16string,ManagedPointer<java::lang::Class>> classes;
+    std::lock_guard<std::mutex> guard(classes_lock);
+    auto found = classes.find(name);
+    if(found != classes.end())return found->second;
+#ifdef GC_OBJS
+    // `classes` is not scanned by the GC, so class objects must not be collected.
+    ManagedPointer<java::lang::Class> class_obj = managed_from_raw(new (NoGC) java::lang::Class(name));
+#else
+    ManagedPointer<java::lang::Class> class_obj = managed_from_raw(new java::lang::Class(name));
+#endif
+    classes.emplace(name,class_obj);
+    return class_obj;
+}
+ManagedPointer<java::lang::String> java::lang::Class::getName__java_cs_lang_cs_String_(){
+    return managed_from_raw(new java::lang::String(name));
+}
@@ -1,7 +1,13 @@
 #pragma once
 #include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_String.hpp"
+#include <string>
 namespace java{namespace lang{class Class;};};
 class java::lang::Class: public java::lang::Object{
+        std::u16string name;
+        Class(std::u16string name);
     public:
-        Class(char16_t* name);
-};
\ No newline at end of file
+        // Returns the only `Class` object representing class `name`, so that `synchronized` static methods and `synchronized(X.class)` share a monitor.
+        static ManagedPointer<java::lang::Class> for_name(const char16_t* name);
+        ManagedPointer<java::lang::String> getName__java_cs_lang_cs_String_();
+};
@@ -0,0 +1,44 @@
+#pragma once
+#include "java_cs_lang_cs_RuntimeException.hpp"
+namespace java{namespace lang{class ClassCastException;};};
+class java::lang::ClassCastException: public java::lang::RuntimeException{
+    public:
+    virtual ~ClassCastException() = default;
+};
+// Describes the module of class `name`, like the JVM does in messages: classes of the standard library(and arrays of primitives) are
+// in `java.base`, and translated classes in the unnamed module.
+inline std::string class_module(const std::string& name){
+    size_t element = name.find_first_not_of('[');
+    bool is_primitive_array = element > 0 && name.size() == element + 1;
+    if(is_primitive_array || name.compare(element,5,"java.") == 0 || name.compare(element,6,"Ljava.") == 0){
+        return "module java.base of loader 'bootstrap'";
+    }
+    return "unnamed module of loader 'app'";
+}
+// Throws `java::lang::ClassCastException` for a failed cast of `obj` to class `target_name`.
+[[noreturn]] inline void throw_class_cast(java::lang::Object* obj,const std::string& target_name){
+    std::string source_name = java_class_name(obj);
+    std::string source_module = class_module(source_name);
+    std::string target_module = class_module(target_name);
+    std::string modules = source_module == target_module ?
+        source_name + " and " + target_name + " are in " + source_module :
+        source_name + " is in " + source_module + "; " + target_name + " is in " + target_module;
+    std::string text = "class " + source_name + " cannot be cast to class " + target_name + " (" + modules + ")";
+    ManagedPointer<java::lang::ClassCastException> exception = managed_from_raw(new java::lang::ClassCastException());
+    ManagedPointer<java::lang::String> message = managed_from_raw(new java::lang::String(std::u16string(text.begin(),text.end())));
+    java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,message);
+    throw ManagedPointer<java::lang::Throwable>(exception);
+}
+// Casts `obj` to `Target`, throwing `java::lang::ClassCastException` if `obj` is neither null nor an instance of `Target`.
+template<typename Target, typename Source> ManagedPointer<Target> checked_cast(ManagedPointer<Source> obj,const char* target_name){
+    if(obj == nullptr)return nullptr;
+    ManagedPointer<Target> res = managed_cast<Target>(obj);
+    if(res == nullptr)throw_class_cast(&*obj,target_name);
+    return res;
+}
+// Casts `obj` to array type `Target`, checking that it is an array assignable to arrays of type `array_class`.
+template<typename Target, typename Source> ManagedPointer<Target> checked_array_cast(ManagedPointer<Source> obj,const ArrayClass* array_class){
+    if(obj == nullptr)return nullptr;
+    if(!is_array_instance(&*obj,array_class))throw_class_cast(&*obj,array_class->name);
+    return managed_cast<Target>(obj);
+}
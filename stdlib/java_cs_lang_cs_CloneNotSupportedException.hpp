@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Exception.hpp"
+namespace java{namespace lang{class CloneNotSupportedException;};};
+class java::lang::CloneNotSupportedException: public java::lang::Exception{
+    public:
+    virtual ~CloneNotSupportedException() = default;
+};
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace lang{struct Comparable;};};
+struct java::lang::Comparable: public virtual java::lang::Object{
+    virtual ~Comparable() = default;
+    virtual int32_t compareTo_java_cs_lang_cs_Object__I(ManagedPointer<java::lang::Object> other) = 0;
+};
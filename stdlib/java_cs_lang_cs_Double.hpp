@@ -0,0 +1,21 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Double;};};
+class java::lang::Double: public java::lang::Number{
+    double value;
+    public:
+    Double(double value):value(value){}
+    virtual ~Double() = default;
+    static ManagedPointer<Double> valueOf_D_java_cs_lang_cs_Double_(double value){return managed_from_raw(new Double(value));}
+    virtual int32_t intValue__I(){return java_fp_to_int<int32_t>(value);}
+    virtual int64_t longValue__J(){return java_fp_to_int<int64_t>(value);}
+    virtual float floatValue__F(){return (float)value;}
+    virtual double doubleValue__D(){return value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_D_java_cs_lang_cs_String_(value);}
+    // Boxes are equal if their values have the same bits, so NaN equals itself, but 0.0 does not equal -0.0.
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Double> box = managed_cast<Double>(other);
+        return box != nullptr && ((value != value && box->value != box->value) || std::bit_cast<int64_t>(value) == std::bit_cast<int64_t>(box->value));
+    }
+};
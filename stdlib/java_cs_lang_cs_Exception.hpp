@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Throwable.hpp"
+namespace java{namespace lang{class Exception;};};
+class java::lang::Exception: public java::lang::Throwable{
+    public:
+    virtual ~Exception() = default;
+};
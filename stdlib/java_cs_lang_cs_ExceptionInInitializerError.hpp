@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_LinkageError.hpp"
+namespace java{namespace lang{class ExceptionInInitializerError;};};
+class java::lang::ExceptionInInitializerError: public java::lang::LinkageError{
+    public:
+    virtual ~ExceptionInInitializerError() = default;
+};
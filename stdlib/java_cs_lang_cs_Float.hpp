@@ -0,0 +1,21 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Float;};};
+class java::lang::Float: public java::lang::Number{
+    float value;
+    public:
+    Float(float value):value(value){}
+    virtual ~Float() = default;
+    static ManagedPointer<Float> valueOf_F_java_cs_lang_cs_Float_(float value){return managed_from_raw(new Float(value));}
+    virtual int32_t intValue__I(){return java_fp_to_int<int32_t>(value);}
+    virtual int64_t longValue__J(){return java_fp_to_int<int64_t>(value);}
+    virtual float floatValue__F(){return value;}
+    virtual double doubleValue__D(){return (double)value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_F_java_cs_lang_cs_String_(value);}
+    // Boxes are equal if their values have the same bits, so NaN equals itself, but 0.0 does not equal -0.0.
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Float> box = managed_cast<Float>(other);
+        return box != nullptr && ((value != value && box->value != box->value) || std::bit_cast<int32_t>(value) == std::bit_cast<int32_t>(box->value));
+    }
+};
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_RuntimeException.hpp"
+namespace java{namespace lang{class IllegalArgumentException;};};
+class java::lang::IllegalArgumentException: public java::lang::RuntimeException{
+    public:
+    virtual ~IllegalArgumentException() = default;
+};
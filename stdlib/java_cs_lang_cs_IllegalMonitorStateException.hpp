@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_RuntimeException.hpp"
+namespace java{namespace lang{class IllegalMonitorStateException;};};
+class java::lang::IllegalMonitorStateException: public java::lang::RuntimeException{
+    public:
+    virtual ~IllegalMonitorStateException() = default;
+};
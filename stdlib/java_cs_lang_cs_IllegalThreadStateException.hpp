@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_IllegalArgumentException.hpp"
+namespace java{namespace lang{class IllegalThreadStateException;};};
+class java::lang::IllegalThreadStateException: public java::lang::IllegalArgumentException{
+    public:
+    virtual ~IllegalThreadStateException() = default;
+};
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_LinkageError.hpp"
+namespace java{namespace lang{class IncompatibleClassChangeError;};};
+class java::lang::IncompatibleClassChangeError: public java::lang::LinkageError{
+    public:
+    virtual ~IncompatibleClassChangeError() = default;
+};
@@ -0,0 +1,20 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Integer;};};
+class java::lang::Integer: public java::lang::Number{
+    int32_t value;
+    public:
+    Integer(int32_t value):value(value){}
+    virtual ~Integer() = default;
+    static ManagedPointer<Integer> valueOf_I_java_cs_lang_cs_Integer_(int32_t value){return managed_from_raw(new Integer(value));}
+    virtual int32_t intValue__I(){return value;}
+    virtual int64_t longValue__J(){return (int64_t)value;}
+    virtual float floatValue__F(){return (float)value;}
+    virtual double doubleValue__D(){return (double)value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_I_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Integer> box = managed_cast<Integer>(other);
+        return box != nullptr && value == box->value;
+    }
+};
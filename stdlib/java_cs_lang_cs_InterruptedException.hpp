@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Exception.hpp"
+namespace java{namespace lang{class InterruptedException;};};
+class java::lang::InterruptedException: public java::lang::Exception{
+    public:
+    virtual ~InterruptedException() = default;
+};
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Error.hpp"
+namespace java{namespace lang{class LinkageError;};};
+class java::lang::LinkageError: public java::lang::Error{
+    public:
+    virtual ~LinkageError() = default;
+};
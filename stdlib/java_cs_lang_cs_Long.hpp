@@ -0,0 +1,20 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Long;};};
+class java::lang::Long: public java::lang::Number{
+    int64_t value;
+    public:
+    Long(int64_t value):value(value){}
+    virtual ~Long() = default;
+    static ManagedPointer<Long> valueOf_J_java_cs_lang_cs_Long_(int64_t value){return managed_from_raw(new Long(value));}
+    virtual int32_t intValue__I(){return (int32_t)value;}
+    virtual int64_t longValue__J(){return value;}
+    virtual float floatValue__F(){return (float)value;}
+    virtual double doubleValue__D(){return (double)value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_J_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Long> box = managed_cast<Long>(other);
+        return box != nullptr && value == box->value;
+    }
+};
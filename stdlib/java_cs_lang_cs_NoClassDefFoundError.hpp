@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_LinkageError.hpp"
+namespace java{namespace lang{class NoClassDefFoundError;};};
+class java::lang::NoClassDefFoundError: public java::lang::LinkageError{
+    public:
+    virtual ~NoClassDefFoundError() = default;
+};
@@ -0,0 +1,18 @@
+#pragma once
+#include "java_cs_lang_cs_RuntimeException.hpp"
+namespace java{namespace lang{class NullPointerException;};};
+class java::lang::NullPointerException: public java::lang::RuntimeException{
+    public:
+    virtual ~NullPointerException() = default;
+};
+// Throws `java::lang::NullPointerException` with `message`.
+[[noreturn]] inline void throw_null_pointer(const char16_t* message){
+    ManagedPointer<java::lang::NullPointerException> exception = managed_from_raw(new java::lang::NullPointerException());
+    java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,managed_from_raw(new java::lang::String(message)));
+    throw ManagedPointer<java::lang::Throwable>(exception);
+}
+// Returns `obj`, throwing `java::lang::NullPointerException` with `message` if it is null.
+template<typename T> inline const T& null_check(const T& obj,const char16_t* message){
+    if(obj == nullptr)throw_null_pointer(message);
+    return obj;
+}
@@ -0,0 +1,14 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace lang{class Number;};};
+// Base of boxes of numeric primitives, which convert their values to any numeric type.
+class java::lang::Number: public java::lang::Object{
+    public:
+    virtual ~Number() = default;
+    virtual int8_t byteValue__B(){return (int8_t)intValue__I();}
+    virtual int16_t shortValue__S(){return (int16_t)intValue__I();}
+    virtual int32_t intValue__I() = 0;
+    virtual int64_t longValue__J() = 0;
+    virtual float floatValue__F() = 0;
+    virtual double doubleValue__D() = 0;
+};
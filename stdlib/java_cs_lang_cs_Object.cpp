@@ -1,2 +1,230 @@
 #include "java_cs_lang_cs_Object.hpp"
-void java::lang::Object::_init___V(ManagedPointer<java::lang::Object> obj){}
\ No newline at end of file
+#include "java_cs_lang_cs_String.hpp"
+#include "java_cs_lang_cs_Thread.hpp"
+#include "java_cs_lang_cs_IllegalArgumentException.hpp"
+#include "java_cs_lang_cs_IllegalMonitorStateException.hpp"
+#include "java_cs_lang_cs_ArrayIndexOutOfBoundsException.hpp"
+#include "java_cs_lang_cs_NegativeArraySizeException.hpp"
+#include "java_cs_lang_cs_ArrayStoreException.hpp"
+#include "java_cs_lang_cs_CloneNotSupportedException.hpp"
+#include "java_cs_lang_cs_IncompatibleClassChangeError.hpp"
+#include "java_cs_lang_cs_ExceptionInInitializerError.hpp"
+#include "java_cs_lang_cs_NoClassDefFoundError.hpp"
+#include "java_cs_lang_cs_Class.hpp"
+#include <chrono>
+#include <climits>
+#include <cxxabi.h>
+#include <cstdio>
+#include <typeinfo>
+void java::lang::Object::_init___V(ManagedPointer<java::lang::Object> obj){}
+std::string java_class_name(java::lang::Object* obj){
+    const ArrayClass* array_class = obj->get_array_class();
+    if(array_class != nullptr)return array_class->name;
+    return java_class_name(typeid(*obj));
+}
+std::string java_class_name(const std::type_info& type){
+    int status = 0;
+    char* demangled = abi::__cxa_demangle(type.name(),nullptr,nullptr,&status);
+    std::string name = status == 0 ? demangled : type.name();
+    std::free(demangled);
+    for(size_t pos = name.find("::"); pos != std::string::npos; pos = name.find("::")){
+        name.replace(pos,2,".");
+    }
+    return name;
+}
+ManagedPointer<java::lang::String> java::lang::Object::toString__java_cs_lang_cs_String_(){
+    std::string name = java_class_name(this);
+    char address[32];
+    snprintf(address,sizeof(address),"@%zx",(size_t)this);
+    name += address;
+    return managed_from_raw(new java::lang::String(std::u16string(name.begin(),name.end())));
+}
+void Monitor::enter(){
+    std::unique_lock<std::mutex> guard(lock);
+    std::thread::id current = std::this_thread::get_id();
+    if(entry_count > 0 && owner == current){
+        entry_count++;
+        return;
+    }
+    released.wait(guard,[this]{return entry_count == 0;});
+    owner = current;
+    entry_count = 1;
+}
+namespace{
+    template<typename Exception> [[noreturn]] void throw_new(){
+        ManagedPointer<Exception> exception = new_managed(Exception,);
+        java::lang::Throwable::_init___V(exception);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+    template<typename Exception> [[noreturn]] void throw_new(const std::string& message){
+        ManagedPointer<Exception> exception = new_managed(Exception,);
+        ManagedPointer<java::lang::String> jmessage = managed_from_raw(new java::lang::String(std::u16string(message.begin(),message.end())));
+        java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,jmessage);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+}
+bool is_subclass_of(const std::type_info& type,const std::type_info& base){
+    if(type == base)return true;
+    if(auto single = dynamic_cast<const abi::__si_class_type_info*>(&type))return is_subclass_of(*single->__base_type,base);
+    if(auto multiple = dynamic_cast<const abi::__vmi_class_type_info*>(&type)){
+        for(unsigned int index = 0; index < multiple->__base_count; index++){
+            if(is_subclass_of(*multiple->__base_info[index].__base_type,base))return true;
+        }
+    }
+    return false;
+}
+void ClassInitializer::run(void (*init)()){
+    std::thread::id current = std::this_thread::get_id();
+    {
+        std::unique_lock<std::mutex> guard(lock);
+        if(initializer == current)return;
+        finished.wait(guard,[this]{return initializer == std::thread::id();});
+        if(done.load(std::memory_order_relaxed))return;
+        if(erroneous)throw_new<java::lang::NoClassDefFoundError>("Could not initialize class " + java_class_name(type));
+        initializer = current;
+    }
+    // A class whose initializer throws is marked as erroneous, so that it is not initialized again(JVMS §5.5).
+    auto finish = [this](bool failed){
+        std::lock_guard<std::mutex> guard(lock);
+        initializer = std::thread::id();
+        if(failed)erroneous = true;
+        else done.store(true,std::memory_order_release);
+        finished.notify_all();
+    };
+    try{
+        init();
+    }
+    catch(ManagedPointer<java::lang::Throwable> exception){
+        finish(true);
+        if(dynamic_cast<java::lang::Error*>(&*exception) != nullptr)throw;
+        ManagedPointer<java::lang::ExceptionInInitializerError> error = new_managed(java::lang::ExceptionInInitializerError,);
+        java::lang::Throwable::_init___V(error);
+        error->set_cause(exception);
+        throw ManagedPointer<java::lang::Throwable>(error);
+    }
+    catch(...){
+        finish(true);
+        throw;
+    }
+    finish(false);
+}
+void throw_array_index_out_of_bounds(int index,int length){
+    throw_new<java::lang::ArrayIndexOutOfBoundsException>("Index " + std::to_string(index) + " out of bounds for length " + std::to_string(length));
+}
+void throw_negative_array_size(int length){
+    throw_new<java::lang::NegativeArraySizeException>(std::to_string(length));
+}
+void throw_array_store(java::lang::Object* value){
+    throw_new<java::lang::ArrayStoreException>(java_class_name(value));
+}
+void throw_incompatible_class_change(java::lang::Object* obj,const std::type_info& target){
+    throw_new<java::lang::IncompatibleClassChangeError>("Class " + java_class_name(obj) + " does not implement the requested interface " + java_class_name(target));
+}
+bool java::lang::Object::equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+    return this == &*other;
+}
+ManagedPointer<java::lang::Class> java::lang::Object::getClass__java_cs_lang_cs_Class_(){
+    std::string name = java_class_name(this);
+    return java::lang::Class::for_name(std::u16string(name.begin(),name.end()).c_str());
+}
+ManagedPointer<java::lang::Object> java::lang::Object::clone__java_cs_lang_cs_Object_(){
+    throw_new<java::lang::CloneNotSupportedException>(java_class_name(this));
+}
+void Monitor::exit(){
+    std::unique_lock<std::mutex> guard(lock);
+    if(entry_count == 0 || owner != std::this_thread::get_id()){
+        guard.unlock();
+        throw_new<java::lang::IllegalMonitorStateException>();
+    }
+    entry_count--;
+    if(entry_count == 0){
+        owner = std::thread::id();
+        released.notify_one();
+    }
+}
+bool Monitor::held_by_current_thread(){
+    std::unique_lock<std::mutex> guard(lock);
+    return entry_count > 0 && owner == std::this_thread::get_id();
+}
+bool Monitor::wait(int64_t millis, std::atomic<bool>& interrupted){
+    std::unique_lock<std::mutex> guard(lock);
+    if(interrupted.exchange(false))return true;
+    // Monitor may be entered multiple times, but `wait` releases it fully.
+    std::thread::id current = owner;
+    int count = entry_count;
+    owner = std::thread::id();
+    entry_count = 0;
+    released.notify_one();
+    // Java allows spurious wakeups, so they need not be filtered out.
+    if(millis == 0)notified.wait(guard);
+    else notified.wait_for(guard,std::chrono::milliseconds(millis));
+    released.wait(guard,[this]{return entry_count == 0;});
+    owner = current;
+    entry_count = count;
+    return interrupted.exchange(false);
+}
+void Monitor::notify(bool all){
+    std::unique_lock<std::mutex> guard(lock);
+    if(entry_count == 0 || owner != std::this_thread::get_id()){
+        guard.unlock();
+        throw_new<java::lang::IllegalMonitorStateException>();
+    }
+    if(all)notified.notify_all();
+    else notified.notify_one();
+}
+void Monitor::wake_all(){
+    std::unique_lock<std::mutex> guard(lock);
+    notified.notify_all();
+}
+Monitor* java::lang::Object::get_monitor(){
+    Monitor* curr = monitor.load();
+    if(curr != nullptr)return curr;
+#ifdef GC_OBJS
+    Monitor* created = is_pointer_free() ? new (NoGC) Monitor() : new Monitor();
+#else
+    Monitor* created = new Monitor();
+#endif
+    // Another thread may have allocated the monitor in the meantime, in which case its monitor is used.
+    if(!monitor.compare_exchange_strong(curr,created)){
+        delete created;
+        return curr;
+    }
+#ifdef GC_OBJS
+    // The GC runs no destructors of collected objects, so the uncollectable monitor is freed by a finalizer instead.
+    if(is_pointer_free()){
+        GC_register_finalizer_no_order(GC_base(this),[](void*,void* monitor){delete static_cast<Monitor*>(monitor);},created,nullptr,nullptr);
+    }
+#endif
+    return created;
+}
+void java::lang::Object::monitor_enter(){
+    get_monitor()->enter();
+}
+void java::lang::Object::monitor_exit(){
+    get_monitor()->exit();
+}
+bool java::lang::Object::holds_monitor(){
+    Monitor* curr = monitor.load();
+    return curr != nullptr && curr->held_by_current_thread();
+}
+void java::lang::Object::wait__V(){
+    wait_J_V(0);
+}
+void java::lang::Object::wait_J_V(int64_t timeout){
+    if(timeout < 0)throw_new<java::lang::IllegalArgumentException>();
+    if(!holds_monitor())throw_new<java::lang::IllegalMonitorStateException>();
+    java::lang::Thread::wait_on(monitor.load(),timeout);
+}
+void java::lang::Object::wait_JI_V(int64_t timeout,int32_t nanos){
+    if(nanos < 0 || nanos > 999999)throw_new<java::lang::IllegalArgumentException>();
+    if(nanos > 0 && timeout < LONG_MAX)timeout++;
+    wait_J_V(timeout);
+}
+void java::lang::Object::notify__V(){
+    if(!holds_monitor())throw_new<java::lang::IllegalMonitorStateException>();
+    monitor.load()->notify(false);
+}
+void java::lang::Object::notifyAll__V(){
+    if(!holds_monitor())throw_new<java::lang::IllegalMonitorStateException>();
+    monitor.load()->notify(true);
+}
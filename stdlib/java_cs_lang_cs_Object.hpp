@@ -5,11 +5,21 @@
 #include <cmath>  
 #include <assert.h>
 #include <cstring>
+#include <algorithm>
+#include <type_traits>
+#include <limits>
+#include <cstdint>
+#include <atomic>
+#include <mutex>
+#include <condition_variable>
+#include <thread>
+#include <string>
+#include <typeinfo>
 
 #ifdef GC_OBJS
 #include "gc_cpp.h"
 #else
-struct gc{}
+struct gc{};
 #endif
 #ifdef ARC_OBJS
 
@@ -27,7 +37,10 @@ struct gc{}
       template<typename T> using ManagedPointer = std::shared_ptr<T>;
       template<typename T> inline ManagedPointer<T> managed_from_raw(T* ptr){return std::shared_ptr<T>(ptr);}
       #define new_managed(TYPE,ARGS) std::make_shared<TYPE>(ARGS)
-      #define managed_from_this(TYPE) (std::static_pointer_cast<TYPE>(this->java::lang::Object::shared_from_this()))
+      // `java::lang::Object` is a virtual base, so a `dynamic_pointer_cast` is required.
+      #define managed_from_this(TYPE) (std::dynamic_pointer_cast<TYPE>(this->java::lang::Object::shared_from_this()))
+      // Casts `obj` to `Target`, returning nullptr if `obj` is not an instance of `Target`.
+      template<typename Target, typename Source> inline ManagedPointer<Target> managed_cast(ManagedPointer<Source> obj){return std::dynamic_pointer_cast<Target>(obj);}
       // NOTE: Not finished
       //#define managed_from_this(TYPE) (smart_cast<java::lang::Object,TYPE>(this->java::lang::Object::shared_from_this()))
      
@@ -36,64 +49,295 @@ struct gc{}
       template<typename T> inline ManagedPointer<T> managed_from_raw(T* ptr){return ptr;}
       #define managed_from_this(TYPE) this
       #define new_managed(TYPE,ARGS) new TYPE(ARGS)
+      // Casts `obj` to `Target`, returning nullptr if `obj` is not an instance of `Target`.
+      template<typename Target, typename Source> inline ManagedPointer<Target> managed_cast(ManagedPointer<Source> obj){return dynamic_cast<Target*>(obj);}
 #endif
+// Checks if `obj` is an instance of `Target`, or any of its subclasses.
+template<typename Target, typename Source> inline bool instance_of(ManagedPointer<Source> obj){return managed_cast<Target>(obj) != nullptr;}
 namespace java{namespace lang{class Object;};};
+// Throws `java::lang::IncompatibleClassChangeError` for `obj`, which does not implement `target`.
+[[noreturn]] void throw_incompatible_class_change(java::lang::Object* obj,const std::type_info& target);
+// Converts `obj` to an interface it implements. If `Source` is not statically known to implement `Target`, the check is done at runtime,
+// throwing `java::lang::IncompatibleClassChangeError` if it fails.
+template<typename Target, typename Source> inline ManagedPointer<Target> interface_cast(ManagedPointer<Source> obj){
+      if constexpr (std::is_base_of<Target,Source>::value) return obj;
+      else{
+            ManagedPointer<Target> res = managed_cast<Target>(obj);
+            if(res == nullptr && obj != nullptr)throw_incompatible_class_change(&*obj,typeid(Target));
+            return res;
+      }
+}
+// Java integer arithmetic wraps around on overflow, which is undefined behaviour for signed C++ integers, so it is done on unsigned ones.
+template<typename T> inline T java_add(T a,T b){return (T)((std::make_unsigned_t<T>)a + (std::make_unsigned_t<T>)b);}
+template<typename T> inline T java_sub(T a,T b){return (T)((std::make_unsigned_t<T>)a - (std::make_unsigned_t<T>)b);}
+template<typename T> inline T java_mul(T a,T b){return (T)((std::make_unsigned_t<T>)a * (std::make_unsigned_t<T>)b);}
+template<typename T> inline T java_neg(T a){return (T)(0 - (std::make_unsigned_t<T>)a);}
+// Only the lowest 5(or 6, for `long`) bits of the shift distance are used.
+template<typename T> inline T java_shl(T a,int32_t b){return (T)((std::make_unsigned_t<T>)a << (b & (sizeof(T) * 8 - 1)));}
+template<typename T> inline T java_shr(T a,int32_t b){return a >> (b & (sizeof(T) * 8 - 1));}
+template<typename T> inline T java_ushr(T a,int32_t b){return (T)((std::make_unsigned_t<T>)a >> (b & (sizeof(T) * 8 - 1)));}
+// Converts a floating-point value to an integer, saturating values out of range of `T`, and mapping NaN to 0.
+template<typename T, typename F> inline T java_fp_to_int(F value){
+      if(value != value)return 0;
+      if(value >= (F)std::numeric_limits<T>::max())return std::numeric_limits<T>::max();
+      if(value <= (F)std::numeric_limits<T>::min())return std::numeric_limits<T>::min();
+      return (T)value;
+}
+namespace java{namespace lang{class Object;class String;class Class;};};
+struct ArrayClass;
+// Reentrant monitor, backing `synchronized` blocks and methods.
+class Monitor: public gc{
+      std::mutex lock;
+      std::condition_variable released;
+      // Notified by `notify` and `notifyAll`.
+      std::condition_variable notified;
+      std::thread::id owner;
+      int entry_count = 0;
+public:
+      void enter();
+      void exit();
+      bool held_by_current_thread();
+      // Releases the monitor until notified, `millis` elapse(0 means forever) or `interrupted` is set, and then reacquires it. Returns true if the wait was interrupted, clearing `interrupted`.
+      bool wait(int64_t millis, std::atomic<bool>& interrupted);
+      void notify(bool all);
+      // Wakes up all waiting threads, so that they can check if they were interrupted.
+      void wake_all();
+};
 class java::lang::Object: public gc
 #ifdef ARC_OBJS
 ,public std::enable_shared_from_this<java::lang::Object>
 #endif
 {
 public:
+      virtual ~Object(){delete monitor.load();}
       static void _init___V(ManagedPointer<java::lang::Object> obj);
+      // Runs the static initializer of a class on its first use. Standard library classes need no initialization, so they inherit this no-op.
+      static void _ensure_clinit_(){}
+      virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_();
+      virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other);
+      ManagedPointer<java::lang::Class> getClass__java_cs_lang_cs_Class_();
+      // Only arrays can be cloned, other objects throw `java::lang::CloneNotSupportedException`.
+      virtual ManagedPointer<java::lang::Object> clone__java_cs_lang_cs_Object_();
+      // Runtime type of this object, if it is an array.
+      virtual const ArrayClass* get_array_class(){return nullptr;}
+      void wait__V();
+      void wait_J_V(int64_t timeout);
+      void wait_JI_V(int64_t timeout,int32_t nanos);
+      void notify__V();
+      void notifyAll__V();
+      void monitor_enter();
+      void monitor_exit();
+      // Checks if the current thread holds the monitor of this object.
+      bool holds_monitor();
+protected:
+      // Pointer-free objects(arrays of primitives) are not scanned by the GC, so their monitors must not be collectable, and are freed
+      // when the object is collected.
+      virtual bool is_pointer_free(){return false;}
+private:
+      // Allocated on first use, since most objects are never used as monitors.
+      std::atomic<Monitor*> monitor{nullptr};
+      Monitor* get_monitor();
+};
+// Name of the Java class of `obj`, recovered from the demangled name of its C++ class("java::lang::Object" -> "java.lang.Object").
+// For arrays, this is the name of the array class(like `[Ljava.lang.String;`).
+std::string java_class_name(java::lang::Object* obj);
+// Name of the Java class `type` is the C++ type of.
+std::string java_class_name(const std::type_info& type);
+// Holds the monitor of `object` while in scope, so that it is released on both normal and exceptional exit of a `synchronized` method.
+class MonitorGuard{
+      ManagedPointer<java::lang::Object> object;
+public:
+      MonitorGuard(ManagedPointer<java::lang::Object> object):object(object){object->monitor_enter();}
+      ~MonitorGuard(){object->monitor_exit();}
 };
+// Initialization state of a class with a static initializer(JVMS §5.5). The thread initializing the class may re-enter it(recursive
+// initialization), while other threads wait until initialization is finished.
+class ClassInitializer{
+      std::atomic<bool> done{false};
+      // Set if the initializer threw, after which the class can't be used.
+      bool erroneous = false;
+      std::mutex lock;
+      std::condition_variable finished;
+      // Thread running the initializer, default if none is.
+      std::thread::id initializer;
+      // Class being initialized, named by `NoClassDefFoundError` when it is erroneous.
+      const std::type_info& type;
+      void run(void (*init)());
+public:
+      ClassInitializer(const std::type_info& type):type(type){}
+      // Runs `init` unless the class is initialized or is being initialized by the current thread. If `init` throws an exception
+      // which is not an `Error`, it is wrapped in `ExceptionInInitializerError`. Once it threw, `NoClassDefFoundError` is thrown.
+      void ensure(void (*init)()){
+            if(!done.load(std::memory_order_acquire))run(init);
+      }
+};
+// Throw `java::lang::ArrayIndexOutOfBoundsException`, `java::lang::NegativeArraySizeException` and `java::lang::ArrayStoreException`,
+// which can't be included here.
+[[noreturn]] void throw_array_index_out_of_bounds(int index,int length);
+[[noreturn]] void throw_negative_array_size(int length);
+[[noreturn]] void throw_array_store(java::lang::Object* value);
+// Checks if C++ class `type` is `base` or derives from it, walking the base classes recorded in the RTTI of `type`.
+bool is_subclass_of(const std::type_info& type,const std::type_info& base);
+// Runtime type of an array. Arrays of references all share one C++ type, so it is used to check casts to array types and stores
+// to arrays of references.
+struct ArrayClass{
+      // Name returned by `Class.getName`(like `[Ljava.lang.String;` or `[[I`).
+      std::string name;
+      // Type of the elements, if they are arrays too.
+      const ArrayClass* component;
+      // Checks if `obj` is an instance of the element class. Null, unless the elements are objects.
+      bool (*is_element_instance)(java::lang::Object* obj);
+      // C++ type of the element class. Null, unless the elements are objects.
+      const std::type_info* element;
+      // Checks if `value` can be stored in arrays of this type.
+      bool accepts(java::lang::Object* value) const{
+            if(component != nullptr){
+                  const ArrayClass* value_class = value->get_array_class();
+                  return value_class != nullptr && component->is_assignable_from(value_class);
+            }
+            return is_element_instance != nullptr && is_element_instance(value);
+      }
+      // Checks if arrays of type `other` are instances of this type(a `String[]` is an `Object[]`, but not the other way around).
+      bool is_assignable_from(const ArrayClass* other) const{
+            if(other == this || other->name == name)return true;
+            if(component != nullptr){
+                  return other->component != nullptr && component->is_assignable_from(other->component);
+            }
+            if(element == nullptr)return false;
+            // Arrays are `java.lang.Object`s, so only arrays of `java.lang.Object` may contain them.
+            if(other->component != nullptr)return *element == typeid(java::lang::Object);
+            return other->element != nullptr && is_subclass_of(*other->element,*element);
+      }
+};
+// Descriptor of primitive type `T`, used in names of array classes.
+template<typename T> constexpr char primitive_descriptor(){
+      if constexpr (std::is_same<T,bool>::value)return 'Z';
+      else if constexpr (std::is_same<T,int8_t>::value)return 'B';
+      else if constexpr (std::is_same<T,char16_t>::value)return 'C';
+      else if constexpr (std::is_same<T,int16_t>::value)return 'S';
+      else if constexpr (std::is_same<T,int32_t>::value)return 'I';
+      else if constexpr (std::is_same<T,int64_t>::value)return 'J';
+      else if constexpr (std::is_same<T,float>::value)return 'F';
+      else return 'D';
+}
+// Returns the runtime type of arrays with `Dimensions` dimensions, whose innermost elements are of type `Element`(a primitive type or a class).
+template<typename Element, int Dimensions> const ArrayClass* array_class_of(){
+      static const ArrayClass array_class = []{
+            if constexpr (Dimensions > 1){
+                  const ArrayClass* component = array_class_of<Element,Dimensions - 1>();
+                  return ArrayClass{"[" + component->name,component,nullptr,nullptr};
+            }
+            else if constexpr (std::is_arithmetic<Element>::value){
+                  return ArrayClass{std::string("[") + primitive_descriptor<Element>(),nullptr,nullptr,nullptr};
+            }
+            else{
+                  bool (*is_instance)(java::lang::Object*) = [](java::lang::Object* obj){return dynamic_cast<Element*>(obj) != nullptr;};
+                  return ArrayClass{"[L" + java_class_name(typeid(Element)) + ";",nullptr,is_instance,&typeid(Element)};
+            }
+      }();
+      return &array_class;
+}
+// Checks if `obj` is an array assignable to arrays of type `array_class`.
+inline bool is_array_instance(java::lang::Object* obj,const ArrayClass* array_class){
+      const ArrayClass* obj_class = obj->get_array_class();
+      return obj_class != nullptr && array_class->is_assignable_from(obj_class);
+}
+template<typename Source> inline bool instance_of_array(ManagedPointer<Source> obj,const ArrayClass* array_class){
+      return obj != nullptr && is_array_instance(&*obj,array_class);
+}
+// Converts an element loaded from an array of references to the static element type. Stores are type checked, so this can't fail.
+template<typename Element> inline ManagedPointer<Element> element_cast(ManagedPointer<java::lang::Object> obj){
+      if constexpr (std::is_same<Element,java::lang::Object>::value)return obj;
+      else return managed_cast<Element>(obj);
+}
+// Java array. Elements are stored inline, right after the length, in a single allocation made by `New`. Under GC_OBJS, arrays of
+// primitives are allocated as pointer-free memory, which the GC does not scan, while arrays of references are scanned like other objects.
+// Arrays of references are always `RuntimeArray<ManagedPointer<java::lang::Object>>`, with their Java type kept in `array_class`.
 template <typename T> class RuntimeArray : public java::lang::Object{
-      T* data;
+      const ArrayClass* array_class;
       int length;
-public:
-      RuntimeArray(int length){
-            this->data = new T[length];
-            this->length = length;
+      RuntimeArray(int length,const ArrayClass* array_class):array_class(array_class),length(length){
+            T* data = this->data();
+            for(int index = 0; index < length; index++)new (&data[index]) T();
       }
-      T Get(int index){
-            return this->data[index];
+      // Offset of the first element from the start of the array.
+      static constexpr size_t data_offset(){
+            return (sizeof(RuntimeArray) + alignof(T) - 1) / alignof(T) * alignof(T);
       }
-      void Set(int index,T value){
-            this->data[index] = value;
+      T* data(){
+            return reinterpret_cast<T*>(reinterpret_cast<char*>(this) + data_offset());
       }
-      T* GetPtr(int index){
-            return &(this->data[index]);
+      void check_store(const T& value){
+            if constexpr (!std::is_arithmetic<T>::value){
+                  if(value != nullptr && !this->array_class->accepts(&*value))throw_array_store(&*value);
+            }
       }
-      int GetLength(){
-            return this->length;
+      static const ArrayClass* default_class(){
+            if constexpr (std::is_arithmetic<T>::value)return array_class_of<T,1>();
+            else return array_class_of<java::lang::Object,1>();
+      }
+protected:
+      bool is_pointer_free() override{
+            return std::is_arithmetic<T>::value;
       }
-};
-template <> class RuntimeArray<bool>{
-      bool* data;
-      int length;
 public:
-      RuntimeArray(int length){
-            this->data = new bool[length];
-            this->length = length;
+      RuntimeArray(const RuntimeArray&) = delete;
+      RuntimeArray& operator=(const RuntimeArray&) = delete;
+      // Allocates an array of `length` zero-initialized elements. `array_class` is the type of arrays of references, `Object[]` by default.
+      static RuntimeArray* New(int length,const ArrayClass* array_class = default_class()){
+            if(length < 0)throw_negative_array_size(length);
+            size_t size = data_offset() + sizeof(T) * (size_t)length;
+#ifdef GC_OBJS
+            void* memory = std::is_arithmetic<T>::value ? GC_MALLOC_ATOMIC(size) : GC_MALLOC(size);
+#else
+            void* memory = malloc(size);
+#endif
+            if(memory == nullptr)throw std::bad_alloc();
+            return ::new (memory) RuntimeArray(length,array_class);
       }
-      bool Get(int index){
-            return this->data[index];
+      static void operator delete(void* ptr){
+#ifdef GC_OBJS
+            GC_FREE(ptr);
+#else
+            free(ptr);
+#endif
       }
-      void Set(int index,bool value){
-            this->data[index] = value;
+      ~RuntimeArray(){
+            if constexpr (!std::is_trivially_destructible<T>::value){
+                  T* data = this->data();
+                  for(int index = 0; index < this->length; index++)data[index].~T();
+            }
       }
-      bool* GetPtr(int index){
-            return &(this->data[index]);
+      T Get(int index){
+            if((unsigned int)index >= (unsigned int)this->length)throw_array_index_out_of_bounds(index,this->length);
+            return this->data()[index];
+      }
+      void Set(int index,T value){
+            if((unsigned int)index >= (unsigned int)this->length)throw_array_index_out_of_bounds(index,this->length);
+            this->check_store(value);
+            this->data()[index] = value;
+      }
+      // Variants of `Get` and `Set` used when bounds checks are disabled.
+      T GetUnchecked(int index){
+            return this->data()[index];
+      }
+      // Stores to arrays of references are still type checked.
+      void SetUnchecked(int index,T value){
+            this->check_store(value);
+            this->data()[index] = value;
+      }
+      T* GetPtr(int index){
+            return &(this->data()[index]);
       }
       int GetLength(){
             return this->length;
       }
-      void Set(int index, uint8_t value){
-            this->data[index] = (bool)value;
-      }
-      void Set(int index, uint16_t value){
-            this->data[index] = (bool)value;
+      const ArrayClass* get_array_class() override{
+            return this->array_class;
       }
-      void Set(int index, int value){
-            this->data[index] = (bool)value;
+      ManagedPointer<java::lang::Object> clone__java_cs_lang_cs_Object_() override{
+            RuntimeArray* copy = New(this->length,this->array_class);
+            std::copy(this->data(),this->data() + this->length,copy->data());
+            return managed_from_raw<java::lang::Object>(copy);
       }
 };
\ No newline at end of file
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace lang{struct Runnable;};};
+struct java::lang::Runnable: public virtual java::lang::Object{
+    virtual ~Runnable() = default;
+    virtual void run__V() = 0;
+};
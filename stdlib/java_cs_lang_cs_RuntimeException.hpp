@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Exception.hpp"
+namespace java{namespace lang{class RuntimeException;};};
+class java::lang::RuntimeException: public java::lang::Exception{
+    public:
+    virtual ~RuntimeException() = default;
+};
@@ -0,0 +1,21 @@
+#pragma once
+#include "java_cs_lang_cs_Number.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Short;};};
+class java::lang::Short: public java::lang::Number{
+    int16_t value;
+    public:
+    Short(int16_t value):value(value){}
+    virtual ~Short() = default;
+    static ManagedPointer<Short> valueOf_S_java_cs_lang_cs_Short_(int16_t value){return managed_from_raw(new Short(value));}
+    virtual int32_t intValue__I(){return (int32_t)value;}
+    virtual int64_t longValue__J(){return (int64_t)value;}
+    virtual float floatValue__F(){return (float)value;}
+    virtual double doubleValue__D(){return (double)value;}
+    virtual int16_t shortValue__S(){return value;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_(){return java::lang::String::valueOf_I_java_cs_lang_cs_String_(value);}
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+        ManagedPointer<Short> box = managed_cast<Short>(other);
+        return box != nullptr && value == box->value;
+    }
+};
@@ -59,3 +59,41 @@ ManagedPointer<java::lang::String> java::lang::String::from_cstring(char* cstrin
     throw new std::runtime_error("Can't yet translate UTF-8 strings, such as CLI args, to UTF-16. Converting UTF-8 to UTF16 in C++ is, sadly, very confusing. :(");
     //return managed_from_raw(new String(u16.data()));
 }
+int32_t java::lang::String::length__I(){
+    size_t length = this->data.size();
+    // Strings created from a buffer with explicit length may be null-terminated.
+    if(length > 0 && this->data[length - 1] == 0)length -= 1;
+    return (int32_t)length;
+}
+ManagedPointer<java::lang::String> java::lang::String::toString__java_cs_lang_cs_String_(){
+    return managed_from_this(java::lang::String);
+}
+bool java::lang::String::equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other){
+    ManagedPointer<java::lang::String> string = managed_cast<java::lang::String>(other);
+    if(string == nullptr)return false;
+    int32_t length = this->length__I();
+    return length == string->length__I() && this->data.compare(0,length,string->data,0,length) == 0;
+}
+#include "java_cs_lang_cs_StringBuilder.hpp"
+ManagedPointer<java::lang::String> java::lang::String::valueOf_java_cs_lang_cs_Object__java_cs_lang_cs_String_(ManagedPointer<java::lang::Object> obj){
+    if(obj == nullptr)return managed_from_raw(new java::lang::String(u"null"));
+    return obj->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_I_java_cs_lang_cs_String_(int32_t value){
+    return new_managed(java::lang::StringBuilder,)->append_I_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_J_java_cs_lang_cs_String_(int64_t value){
+    return new_managed(java::lang::StringBuilder,)->append_J_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_F_java_cs_lang_cs_String_(float value){
+    return new_managed(java::lang::StringBuilder,)->append_F_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_D_java_cs_lang_cs_String_(double value){
+    return new_managed(java::lang::StringBuilder,)->append_D_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_C_java_cs_lang_cs_String_(char16_t value){
+    return new_managed(java::lang::StringBuilder,)->append_C_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
+ManagedPointer<java::lang::String> java::lang::String::valueOf_Z_java_cs_lang_cs_String_(bool value){
+    return new_managed(java::lang::StringBuilder,)->append_Z_java_cs_lang_cs_StringBuilder_(value)->toString__java_cs_lang_cs_String_();
+}
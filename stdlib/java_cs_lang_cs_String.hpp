@@ -8,6 +8,16 @@ class java::lang::String : public java::lang::Object{
     public:
     const char16_t* GetBuffer();
     size_t GetBufferLength();
+    virtual int32_t length__I();
+    virtual ManagedPointer<String> toString__java_cs_lang_cs_String_();
+    virtual bool equals_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> other);
+    static ManagedPointer<String> valueOf_java_cs_lang_cs_Object__java_cs_lang_cs_String_(ManagedPointer<java::lang::Object> obj);
+    static ManagedPointer<String> valueOf_I_java_cs_lang_cs_String_(int32_t value);
+    static ManagedPointer<String> valueOf_J_java_cs_lang_cs_String_(int64_t value);
+    static ManagedPointer<String> valueOf_F_java_cs_lang_cs_String_(float value);
+    static ManagedPointer<String> valueOf_D_java_cs_lang_cs_String_(double value);
+    static ManagedPointer<String> valueOf_C_java_cs_lang_cs_String_(char16_t value);
+    static ManagedPointer<String> valueOf_Z_java_cs_lang_cs_String_(bool value);
     String(const char16_t* buffer,size_t length);
     String(const char16_t* null_terminated_buffer);
     String(std::u16string data);
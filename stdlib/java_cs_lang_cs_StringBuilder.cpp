@@ -0,0 +1,79 @@
+#include "java_cs_lang_cs_StringBuilder.hpp"
+#include <charconv>
+#include <cmath>
+// Converts ASCII text to UTF-16.
+static std::u16string ascii_to_u16(const char* begin,const char* end){
+    return std::u16string(begin,end);
+}
+// Formats a floating-point value the way `Float.toString` and `Double.toString` do.
+template<typename T> static std::u16string java_float_to_string(T value){
+    if(std::isnan(value))return u"NaN";
+    if(std::isinf(value))return value > 0 ? u"Infinity" : u"-Infinity";
+    if(value == 0)return std::signbit(value) ? u"-0.0" : u"0.0";
+    char buffer[64];
+    T abs = std::fabs(value);
+    if(abs >= (T)1e-3 && abs < (T)1e7){
+        char* end = std::to_chars(buffer,buffer + sizeof(buffer),value,std::chars_format::fixed).ptr;
+        std::u16string res = ascii_to_u16(buffer,end);
+        if(res.find(u'.') == std::u16string::npos)res += u".0";
+        return res;
+    }
+    // Scientific notation, with Java-style exponent("1.0E10" instead of "1e+10").
+    char* end = std::to_chars(buffer,buffer + sizeof(buffer),value,std::chars_format::scientific).ptr;
+    std::string text(buffer,end);
+    size_t exponent_pos = text.find('e');
+    std::string mantissa = text.substr(0,exponent_pos);
+    int exponent = std::stoi(text.substr(exponent_pos + 1));
+    if(mantissa.find('.') == std::string::npos)mantissa += ".0";
+    std::string res = mantissa + "E" + std::to_string(exponent);
+    return ascii_to_u16(res.data(),res.data() + res.size());
+}
+void java::lang::StringBuilder::_init___V(ManagedPointer<java::lang::StringBuilder> builder){}
+void java::lang::StringBuilder::_init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::StringBuilder> builder,ManagedPointer<java::lang::String> str){
+    builder->append_java_cs_lang_cs_String__java_cs_lang_cs_StringBuilder_(str);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_java_cs_lang_cs_String__java_cs_lang_cs_StringBuilder_(ManagedPointer<java::lang::String> str){
+    if(str == nullptr)this->data += u"null";
+    else this->data.append(str->GetBuffer(),str->length__I());
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_java_cs_lang_cs_Object__java_cs_lang_cs_StringBuilder_(ManagedPointer<java::lang::Object> obj){
+    if(obj == nullptr)this->data += u"null";
+    else this->append_java_cs_lang_cs_String__java_cs_lang_cs_StringBuilder_(obj->toString__java_cs_lang_cs_String_());
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_I_java_cs_lang_cs_StringBuilder_(int32_t value){
+    std::string text = std::to_string(value);
+    this->data += ascii_to_u16(text.data(),text.data() + text.size());
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_J_java_cs_lang_cs_StringBuilder_(int64_t value){
+    std::string text = std::to_string(value);
+    this->data += ascii_to_u16(text.data(),text.data() + text.size());
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_F_java_cs_lang_cs_StringBuilder_(float value){
+    this->data += java_float_to_string(value);
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_D_java_cs_lang_cs_StringBuilder_(double value){
+    this->data += java_float_to_string(value);
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_C_java_cs_lang_cs_StringBuilder_(char16_t value){
+    this->data.push_back(value);
+    return managed_from_this(java::lang::StringBuilder);
+}
+ManagedPointer<java::lang::StringBuilder> java::lang::StringBuilder::append_Z_java_cs_lang_cs_StringBuilder_(bool value){
+    this->data += value ? u"true" : u"false";
+    return managed_from_this(java::lang::StringBuilder);
+}
+int32_t java::lang::StringBuilder::length__I(){
+    return (int32_t)this->data.size();
+}
+ManagedPointer<java::lang::String> java::lang::StringBuilder::toString__java_cs_lang_cs_String_(){
+    return managed_from_raw(new java::lang::String(this->data));
+}
+void java::lang::StringBuilder::append(const char16_t* literal){
+    this->data += literal;
+}
@@ -0,0 +1,24 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_String.hpp"
+#include <string>
+namespace java{namespace lang{class StringBuilder;};};
+class java::lang::StringBuilder: public java::lang::Object{
+    std::u16string data;
+    public:
+    virtual ~StringBuilder() = default;
+    static void _init___V(ManagedPointer<java::lang::StringBuilder> builder);
+    static void _init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::StringBuilder> builder,ManagedPointer<java::lang::String> str);
+    virtual ManagedPointer<java::lang::StringBuilder> append_java_cs_lang_cs_String__java_cs_lang_cs_StringBuilder_(ManagedPointer<java::lang::String> str);
+    virtual ManagedPointer<java::lang::StringBuilder> append_java_cs_lang_cs_Object__java_cs_lang_cs_StringBuilder_(ManagedPointer<java::lang::Object> obj);
+    virtual ManagedPointer<java::lang::StringBuilder> append_I_java_cs_lang_cs_StringBuilder_(int32_t value);
+    virtual ManagedPointer<java::lang::StringBuilder> append_J_java_cs_lang_cs_StringBuilder_(int64_t value);
+    virtual ManagedPointer<java::lang::StringBuilder> append_F_java_cs_lang_cs_StringBuilder_(float value);
+    virtual ManagedPointer<java::lang::StringBuilder> append_D_java_cs_lang_cs_StringBuilder_(double value);
+    virtual ManagedPointer<java::lang::StringBuilder> append_C_java_cs_lang_cs_StringBuilder_(char16_t value);
+    virtual ManagedPointer<java::lang::StringBuilder> append_Z_java_cs_lang_cs_StringBuilder_(bool value);
+    virtual int32_t length__I();
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_();
+    // Appends a string literal, used by translated string concatenation.
+    void append(const char16_t* literal);
+};
@@ -1,3 +1,8 @@
 #include "java_cs_lang_cs_System.hpp"
 #include <iostream>
-ManagedPointer<java::io::PrintStream> java::lang::System::out = managed_from_raw(new java::io::PrintStream(std::unique_ptr<StdOut>(new StdOut())));
\ No newline at end of file
+ManagedPointer<java::io::PrintStream> java::lang::System::out = managed_from_raw(new java::io::PrintStream(std::unique_ptr<StdOut>(new StdOut())));
+void java::lang::System::gc__V(){
+#ifdef GC_OBJS
+    GC_gcollect();
+#endif
+}
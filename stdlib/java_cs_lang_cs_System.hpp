@@ -5,4 +5,6 @@ namespace java{namespace lang{class System;};};
 class java::lang::System:public java::lang::Object{
     public:
         static ManagedPointer<java::io::PrintStream> out;
+        // Runs a full collection, if the GC is enabled.
+        static void gc__V();
 };
\ No newline at end of file
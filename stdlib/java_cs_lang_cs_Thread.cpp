@@ -0,0 +1,204 @@
+#include "java_cs_lang_cs_Thread.hpp"
+#include "java_cs_lang_cs_NullPointerException.hpp"
+#include "java_cs_lang_cs_InterruptedException.hpp"
+#include "java_cs_lang_cs_IllegalThreadStateException.hpp"
+#include "java_cs_lang_cs_IllegalArgumentException.hpp"
+#include <chrono>
+#include <codecvt>
+#include <cstdio>
+#include <locale>
+namespace{
+    // Guards the state of all threads, and is notified whenever a thread finishes or gets interrupted. Both are never destroyed, since
+    // daemon threads may still wait on them while the program exits.
+    std::mutex& thread_state_lock = *new std::mutex();
+    std::condition_variable& thread_state_changed = *new std::condition_variable();
+    // Head of the list of running threads. It lives in static storage, which is scanned by the GC.
+    java::lang::Thread* running_threads = nullptr;
+    ManagedPointer<java::lang::Thread> main_thread;
+    thread_local ManagedPointer<java::lang::Thread> current_thread;
+    std::atomic<int> thread_number{0};
+    ManagedPointer<java::lang::String> next_thread_name(){
+        std::string name = "Thread-" + std::to_string(thread_number++);
+        return managed_from_raw(new java::lang::String(std::u16string(name.begin(),name.end())));
+    }
+    std::string to_utf8(ManagedPointer<java::lang::String> string){
+        return std::wstring_convert<std::codecvt_utf8_utf16<char16_t>, char16_t>{}.to_bytes(string->GetBuffer());
+    }
+    template<typename Exception> [[noreturn]] void throw_new(){
+        ManagedPointer<Exception> exception = new_managed(Exception,);
+        java::lang::Throwable::_init___V(exception);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+    template<typename Exception> [[noreturn]] void throw_new(const std::u16string& message){
+        ManagedPointer<Exception> exception = new_managed(Exception,);
+        java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,managed_from_raw(new java::lang::String(message)));
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+}
+void java::lang::Thread::_init___V(ManagedPointer<java::lang::Thread> obj){
+    _init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(obj,nullptr,next_thread_name());
+}
+void java::lang::Thread::_init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::String> name){
+    _init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(obj,nullptr,name);
+}
+void java::lang::Thread::_init__java_cs_lang_cs_Runnable__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::Runnable> target){
+    _init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(obj,target,next_thread_name());
+}
+void java::lang::Thread::_init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::Runnable> target,ManagedPointer<java::lang::String> name){
+    if(name == nullptr)throw_new<java::lang::NullPointerException>();
+    obj->target = target;
+    obj->name = name;
+    // New threads are daemon threads if the thread creating them is one. The main thread is created with no current thread.
+    if(current_thread != nullptr)obj->daemon = current_thread->daemon;
+}
+void java::lang::Thread::run__V(){
+    if(this->target != nullptr)this->target->run__V();
+}
+void java::lang::Thread::start__V(){
+    ManagedPointer<java::lang::Thread> self = managed_from_this(java::lang::Thread);
+    {
+        std::lock_guard<std::mutex> guard(thread_state_lock);
+        if(this->started)throw_new<java::lang::IllegalThreadStateException>();
+        this->started = true;
+        this->next_running = running_threads;
+        running_threads = this;
+    }
+#ifdef GC_OBJS
+    // Threads not created by the GC must be registered, which first needs to be allowed by an already registered thread.
+    static std::once_flag allow_registration;
+    std::call_once(allow_registration,[]{GC_allow_register_threads();});
+#endif
+    std::thread(run_native,self).detach();
+}
+void java::lang::Thread::run_native(ManagedPointer<java::lang::Thread> thread){
+#ifdef GC_OBJS
+    GC_stack_base stack_base;
+    GC_get_stack_base(&stack_base);
+    GC_register_my_thread(&stack_base);
+#endif
+    current_thread = thread;
+    try{
+        thread->run__V();
+    }
+    catch(ManagedPointer<java::lang::Throwable> exception){
+        report_uncaught(to_utf8(thread->name).c_str(),exception);
+    }
+    {
+        std::lock_guard<std::mutex> guard(thread_state_lock);
+        thread->finished = true;
+        java::lang::Thread** curr = &running_threads;
+        while(*curr != &*thread){
+            curr = &(*curr)->next_running;
+        }
+        *curr = thread->next_running;
+        thread->next_running = nullptr;
+        thread_state_changed.notify_all();
+    }
+    current_thread = nullptr;
+#ifdef GC_OBJS
+    GC_unregister_my_thread();
+#endif
+}
+void java::lang::Thread::join__V(){
+    ManagedPointer<java::lang::Thread> current = currentThread__java_cs_lang_cs_Thread_();
+    std::unique_lock<std::mutex> guard(thread_state_lock);
+    thread_state_changed.wait(guard,[&]{return !(this->started && !this->finished) || current->interrupted.load();});
+    if(this->started && !this->finished){
+        current->interrupted = false;
+        guard.unlock();
+        throw_new<java::lang::InterruptedException>();
+    }
+}
+void java::lang::Thread::interrupt__V(){
+    Monitor* waiting_on;
+    {
+        std::lock_guard<std::mutex> guard(thread_state_lock);
+        this->interrupted = true;
+        waiting_on = this->waiting_on;
+        thread_state_changed.notify_all();
+    }
+    // `thread_state_lock` must not be held while waiting for the lock of a monitor, since `wait_on` acquires them in the opposite order.
+    if(waiting_on != nullptr)waiting_on->wake_all();
+}
+bool java::lang::Thread::isInterrupted__Z(){
+    std::lock_guard<std::mutex> guard(thread_state_lock);
+    return this->interrupted;
+}
+bool java::lang::Thread::isAlive__Z(){
+    std::lock_guard<std::mutex> guard(thread_state_lock);
+    return this->started && !this->finished;
+}
+bool java::lang::Thread::isDaemon__Z(){
+    std::lock_guard<std::mutex> guard(thread_state_lock);
+    return this->daemon;
+}
+void java::lang::Thread::setDaemon_Z_V(bool on){
+    std::unique_lock<std::mutex> guard(thread_state_lock);
+    if(this->started && !this->finished){
+        guard.unlock();
+        throw_new<java::lang::IllegalThreadStateException>();
+    }
+    this->daemon = on;
+}
+ManagedPointer<java::lang::String> java::lang::Thread::getName__java_cs_lang_cs_String_(){
+    return this->name;
+}
+void java::lang::Thread::setName_java_cs_lang_cs_String__V(ManagedPointer<java::lang::String> name){
+    if(name == nullptr)throw_new<java::lang::NullPointerException>();
+    this->name = name;
+}
+ManagedPointer<java::lang::Thread> java::lang::Thread::currentThread__java_cs_lang_cs_Thread_(){
+    if(current_thread == nullptr){
+        // Only the main thread is not started by `java::lang::Thread`.
+        ManagedPointer<java::lang::Thread> thread = new_managed(java::lang::Thread,);
+        _init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(thread,nullptr,managed_from_raw(new java::lang::String(u"main")));
+        thread->started = true;
+        main_thread = thread;
+        current_thread = thread;
+    }
+    return current_thread;
+}
+bool java::lang::Thread::interrupted__Z(){
+    ManagedPointer<java::lang::Thread> current = currentThread__java_cs_lang_cs_Thread_();
+    std::lock_guard<std::mutex> guard(thread_state_lock);
+    bool interrupted = current->interrupted;
+    current->interrupted = false;
+    return interrupted;
+}
+void java::lang::Thread::sleep_J_V(int64_t millis){
+    if(millis < 0)throw_new<java::lang::IllegalArgumentException>(u"timeout value is negative");
+    ManagedPointer<java::lang::Thread> current = currentThread__java_cs_lang_cs_Thread_();
+    std::unique_lock<std::mutex> guard(thread_state_lock);
+    if(thread_state_changed.wait_for(guard,std::chrono::milliseconds(millis),[&]{return current->interrupted.load();})){
+        current->interrupted = false;
+        guard.unlock();
+        throw_new<java::lang::InterruptedException>();
+    }
+}
+bool java::lang::Thread::holdsLock_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> obj){
+    if(obj == nullptr)throw_new<java::lang::NullPointerException>();
+    return obj->holds_monitor();
+}
+void java::lang::Thread::wait_on(Monitor* monitor,int64_t millis){
+    ManagedPointer<java::lang::Thread> current = currentThread__java_cs_lang_cs_Thread_();
+    // `waiting_on` is set before `Monitor::wait` checks the interrupt flag, so an interrupt is either seen by that check or wakes up the waiting thread.
+    {
+        std::lock_guard<std::mutex> guard(thread_state_lock);
+        current->waiting_on = monitor;
+    }
+    bool interrupted = monitor->wait(millis,current->interrupted);
+    {
+        std::lock_guard<std::mutex> guard(thread_state_lock);
+        current->waiting_on = nullptr;
+    }
+    if(interrupted)throw_new<java::lang::InterruptedException>();
+}
+void java::lang::Thread::join_non_daemon(){
+    std::unique_lock<std::mutex> guard(thread_state_lock);
+    thread_state_changed.wait(guard,[]{
+        for(java::lang::Thread* curr = running_threads; curr != nullptr; curr = curr->next_running){
+            if(!curr->daemon)return false;
+        }
+        return true;
+    });
+}
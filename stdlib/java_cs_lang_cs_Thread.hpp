@@ -0,0 +1,46 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_Runnable.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Thread;};};
+// Java thread, running on a detached `std::thread`.
+class java::lang::Thread: public virtual java::lang::Runnable{
+        ManagedPointer<java::lang::Runnable> target;
+        ManagedPointer<java::lang::String> name;
+        // State below is guarded by `thread_state_lock` in `java_cs_lang_cs_Thread.cpp`.
+        bool started = false;
+        bool finished = false;
+        // Daemon threads don't keep the program running after `main` returns.
+        bool daemon = false;
+        // Also read by `Monitor::wait`, without holding `thread_state_lock`.
+        std::atomic<bool> interrupted{false};
+        // Monitor this thread waits on in `Object.wait`, woken up on interrupt.
+        Monitor* waiting_on = nullptr;
+        // Next running thread. Running threads form a list rooted in static storage, so that the GC does not collect them.
+        java::lang::Thread* next_running = nullptr;
+        static void run_native(ManagedPointer<java::lang::Thread> thread);
+    public:
+        virtual ~Thread() = default;
+        static void _init___V(ManagedPointer<java::lang::Thread> obj);
+        static void _init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::String> name);
+        static void _init__java_cs_lang_cs_Runnable__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::Runnable> target);
+        static void _init__java_cs_lang_cs_Runnable_java_cs_lang_cs_String__V(ManagedPointer<java::lang::Thread> obj,ManagedPointer<java::lang::Runnable> target,ManagedPointer<java::lang::String> name);
+        virtual void run__V();
+        virtual void start__V();
+        virtual void join__V();
+        virtual void interrupt__V();
+        virtual bool isInterrupted__Z();
+        virtual bool isAlive__Z();
+        virtual bool isDaemon__Z();
+        virtual void setDaemon_Z_V(bool on);
+        virtual ManagedPointer<java::lang::String> getName__java_cs_lang_cs_String_();
+        virtual void setName_java_cs_lang_cs_String__V(ManagedPointer<java::lang::String> name);
+        static ManagedPointer<java::lang::Thread> currentThread__java_cs_lang_cs_Thread_();
+        static bool interrupted__Z();
+        static void sleep_J_V(int64_t millis);
+        static bool holdsLock_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> obj);
+        // Waits on `monitor`, held by the current thread, throwing `InterruptedException` if the current thread is interrupted.
+        static void wait_on(Monitor* monitor,int64_t millis);
+        // Waits until all non-daemon threads finish, which is done before the program exits.
+        static void join_non_daemon();
+};
@@ -0,0 +1,89 @@
+#include "java_cs_lang_cs_Throwable.hpp"
+#include "java_cs_lang_cs_System.hpp"
+#include <codecvt>
+#include <cstdio>
+#include <locale>
+#include <vector>
+namespace{
+    std::string to_utf8(const std::u16string& string){
+        return std::wstring_convert<std::codecvt_utf8_utf16<char16_t>, char16_t>{}.to_bytes(string);
+    }
+    // Describes `frame` like a line of a Java stack trace(`Class.method(File.java:line)`).
+    std::string describe(TraceFrame* frame){
+        std::string location = frame->file == nullptr ? "Unknown Source" : to_utf8(frame->file);
+        if(frame->file != nullptr && frame->line >= 0)location += ":" + std::to_string(frame->line);
+        return to_utf8(frame->class_name) + "." + to_utf8(frame->method) + "(" + location + ")";
+    }
+}
+void java::lang::Throwable::fill_in_stack_trace(){
+    java::lang::Object* self = this;
+    ShadowFrame* frame = ShadowFrame::top;
+    // Constructors of the exception itself are skipped, like by `fillInStackTrace` of the JVM.
+    while(frame != nullptr && frame->constructed == self)frame = frame->caller;
+    TraceFrame** next = &this->stack_trace;
+    for(; frame != nullptr; frame = frame->caller){
+        TraceFrame* recorded = new TraceFrame();
+        recorded->class_name = frame->class_name;
+        recorded->method = frame->method;
+        recorded->file = frame->file;
+        recorded->line = frame->line;
+        recorded->caller = nullptr;
+        *next = recorded;
+        next = &recorded->caller;
+    }
+}
+void java::lang::Throwable::_init___V(ManagedPointer<java::lang::Throwable> obj){
+    obj->fill_in_stack_trace();
+}
+void java::lang::Throwable::_init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::Throwable> obj,ManagedPointer<java::lang::String> message){
+    obj->message = message;
+    obj->fill_in_stack_trace();
+}
+ManagedPointer<java::lang::String> java::lang::Throwable::getMessage__java_cs_lang_cs_String_(){
+    return this->message;
+}
+ManagedPointer<java::lang::String> java::lang::Throwable::toString__java_cs_lang_cs_String_(){
+    std::string name = java_class_name(this);
+    std::u16string text(name.begin(),name.end());
+    ManagedPointer<java::lang::String> message = this->getMessage__java_cs_lang_cs_String_();
+    if(message != nullptr){
+        text += u": ";
+        text.append(message->GetBuffer(),message->length__I());
+    }
+    return managed_from_raw(new java::lang::String(text));
+}
+ManagedPointer<java::lang::Throwable> java::lang::Throwable::getCause__java_cs_lang_cs_Throwable_(){
+    return this->cause;
+}
+void java::lang::Throwable::print_trace(TraceFrame* enclosing){
+    std::vector<TraceFrame*> frames;
+    for(TraceFrame* frame = this->stack_trace; frame != nullptr; frame = frame->caller)frames.push_back(frame);
+    std::vector<TraceFrame*> enclosing_frames;
+    for(TraceFrame* frame = enclosing; frame != nullptr; frame = frame->caller)enclosing_frames.push_back(frame);
+    // Outermost frames shared with the enclosing trace are only counted, like by the JVM.
+    size_t shown = frames.size();
+    for(size_t other = enclosing_frames.size(); shown > 0 && other > 0; shown--, other--){
+        if(describe(frames[shown - 1]) != describe(enclosing_frames[other - 1]))break;
+    }
+    for(size_t index = 0; index < shown; index++){
+        fprintf(stderr,"\tat %s\n",describe(frames[index]).c_str());
+    }
+    if(shown < frames.size())fprintf(stderr,"\t... %zu more\n",frames.size() - shown);
+}
+void java::lang::Throwable::printStackTrace__V(){
+    ManagedPointer<java::lang::String> text = this->toString__java_cs_lang_cs_String_();
+    fprintf(stderr,"%s\n",to_utf8(std::u16string(text->GetBuffer(),text->length__I())).c_str());
+    this->print_trace(nullptr);
+    for(java::lang::Throwable* caused = this; caused->cause != nullptr; caused = &*caused->cause){
+        text = caused->cause->toString__java_cs_lang_cs_String_();
+        fprintf(stderr,"Caused by: %s\n",to_utf8(std::u16string(text->GetBuffer(),text->length__I())).c_str());
+        caused->cause->print_trace(caused->stack_trace);
+    }
+}
+void report_uncaught(const char* thread,ManagedPointer<java::lang::Throwable> exception){
+    // Output printed before the exception was thrown must come before its trace, even if stdout is buffered.
+    java::lang::System::out->flush__V();
+    fflush(stdout);
+    fprintf(stderr,"Exception in thread \"%s\" ",thread);
+    exception->printStackTrace__V();
+}
@@ -0,0 +1,54 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include "java_cs_lang_cs_String.hpp"
+namespace java{namespace lang{class Throwable;};};
+// Frame of the shadow call stack of a thread. Each translated method pushes one while it runs, and updates its Java source line,
+// so that exceptions can record Java stack traces.
+struct ShadowFrame{
+    const char16_t* class_name;
+    const char16_t* method;
+    // Name of the source file, or null if it is unknown.
+    const char16_t* file;
+    // Java source line being executed, or -1 if it is unknown.
+    int32_t line = -1;
+    // Object constructed by this frame, if it runs a constructor. Frames constructing an exception are not part of its stack trace.
+    java::lang::Object* constructed;
+    ShadowFrame* caller;
+    inline static thread_local ShadowFrame* top = nullptr;
+    ShadowFrame(const char16_t* class_name,const char16_t* method,const char16_t* file,java::lang::Object* constructed = nullptr):
+        class_name(class_name),method(method),file(file),constructed(constructed),caller(top){top = this;}
+    ~ShadowFrame(){top = caller;}
+    ShadowFrame(const ShadowFrame&) = delete;
+    ShadowFrame& operator=(const ShadowFrame&) = delete;
+};
+// Frame of a recorded stack trace. Names point to string literals of translated code, which live as long as the program.
+struct TraceFrame: public gc{
+    const char16_t* class_name;
+    const char16_t* method;
+    const char16_t* file;
+    int32_t line;
+    TraceFrame* caller;
+};
+class java::lang::Throwable: public virtual java::lang::Object{
+    ManagedPointer<java::lang::String> message;
+    // Exception which caused this one to be thrown, or null if it is not known.
+    ManagedPointer<java::lang::Throwable> cause;
+    // Innermost frame of the stack trace, recorded when this exception was constructed.
+    TraceFrame* stack_trace = nullptr;
+    void fill_in_stack_trace();
+    // Prints the stack trace of this exception, without frames it has in common with the trace of `enclosing`(the exception caused by this one).
+    void print_trace(TraceFrame* enclosing);
+    public:
+    virtual ~Throwable() = default;
+    static void _init___V(ManagedPointer<java::lang::Throwable> obj);
+    static void _init__java_cs_lang_cs_String__V(ManagedPointer<java::lang::Throwable> obj,ManagedPointer<java::lang::String> message);
+    virtual ManagedPointer<java::lang::String> getMessage__java_cs_lang_cs_String_();
+    ManagedPointer<java::lang::Throwable> getCause__java_cs_lang_cs_Throwable_();
+    // Sets the cause of this exception. Used by the runtime, which creates exceptions caused by others.
+    void set_cause(ManagedPointer<java::lang::Throwable> cause){this->cause = cause;}
+    virtual ManagedPointer<java::lang::String> toString__java_cs_lang_cs_String_();
+    // Prints this exception and its stack trace to stderr, followed by its causes.
+    virtual void printStackTrace__V();
+};
+// Reports `exception`, thrown out of a thread named `thread`, like the default uncaught exception handler of the JVM.
+void report_uncaught(const char* thread,ManagedPointer<java::lang::Throwable> exception);
@@ -0,0 +1,46 @@
+#include "java_cs_util_cs_ArrayList.hpp"
+#include "java_cs_lang_cs_IndexOutOfBoundsException.hpp"
+namespace{
+    // Throws `java::lang::IndexOutOfBoundsException` if `index` is not an index of an element of a list of `size` elements.
+    void check_index(int32_t index,size_t size){
+        if(index >= 0 && (size_t)index < size)return;
+        std::string message = "Index " + std::to_string(index) + " out of bounds for length " + std::to_string(size);
+        ManagedPointer<java::lang::IndexOutOfBoundsException> exception = new_managed(java::lang::IndexOutOfBoundsException,);
+        ManagedPointer<java::lang::String> jmessage = managed_from_raw(new java::lang::String(std::u16string(message.begin(),message.end())));
+        java::lang::Throwable::_init__java_cs_lang_cs_String__V(exception,jmessage);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+}
+void java::util::ArrayList::_init___V(ManagedPointer<java::util::ArrayList> list){}
+void java::util::ArrayList::_init__I_V(ManagedPointer<java::util::ArrayList> list,int32_t capacity){
+    list->data.reserve(capacity);
+}
+int32_t java::util::ArrayList::size__I(){
+    return (int32_t)this->data.size();
+}
+bool java::util::ArrayList::isEmpty__Z(){
+    return this->data.empty();
+}
+ManagedPointer<java::lang::Object> java::util::ArrayList::get_I_java_cs_lang_cs_Object_(int32_t index){
+    check_index(index,this->data.size());
+    return this->data[index];
+}
+ManagedPointer<java::lang::Object> java::util::ArrayList::set_I_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(int32_t index,ManagedPointer<java::lang::Object> element){
+    check_index(index,this->data.size());
+    ManagedPointer<java::lang::Object> old = this->data[index];
+    this->data[index] = element;
+    return old;
+}
+bool java::util::ArrayList::add_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> element){
+    this->data.push_back(element);
+    return true;
+}
+ManagedPointer<java::lang::Object> java::util::ArrayList::remove_I_java_cs_lang_cs_Object_(int32_t index){
+    check_index(index,this->data.size());
+    ManagedPointer<java::lang::Object> old = this->data[index];
+    this->data.erase(this->data.begin() + index);
+    return old;
+}
+void java::util::ArrayList::clear__V(){
+    this->data.clear();
+}
@@ -0,0 +1,18 @@
+#pragma once
+#include "java_cs_util_cs_List.hpp"
+#include <vector>
+namespace java{namespace util{class ArrayList;};};
+class java::util::ArrayList: public virtual java::util::List{
+    std::vector<ManagedPointer<java::lang::Object>> data;
+    public:
+    virtual ~ArrayList() = default;
+    static void _init___V(ManagedPointer<java::util::ArrayList> list);
+    static void _init__I_V(ManagedPointer<java::util::ArrayList> list,int32_t capacity);
+    virtual int32_t size__I();
+    virtual bool isEmpty__Z();
+    virtual ManagedPointer<java::lang::Object> get_I_java_cs_lang_cs_Object_(int32_t index);
+    virtual ManagedPointer<java::lang::Object> set_I_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(int32_t index,ManagedPointer<java::lang::Object> element);
+    virtual bool add_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> element);
+    virtual ManagedPointer<java::lang::Object> remove_I_java_cs_lang_cs_Object_(int32_t index);
+    virtual void clear__V();
+};
@@ -0,0 +1,30 @@
+#include "java_cs_util_cs_List.hpp"
+#include "java_cs_util_cs_ArrayList.hpp"
+#include "java_cs_util_cs_stream_cs_Stream.hpp"
+ManagedPointer<java::util::stream::Stream> java::util::List::stream__java_cs_util_cs_stream_cs_Stream_(){
+    std::vector<ManagedPointer<java::lang::Object>> data;
+    int32_t size = this->size__I();
+    data.reserve(size);
+    for(int32_t index = 0; index < size; index++){
+        data.push_back(this->get_I_java_cs_lang_cs_Object_(index));
+    }
+    return managed_from_raw(new java::util::stream::Stream(data));
+}
+ManagedPointer<java::util::List> java::util::List::of__java_cs_util_cs_List_(){
+    return new_managed(java::util::ArrayList,);
+}
+ManagedPointer<java::util::List> java::util::List::of_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1){
+    ManagedPointer<java::util::List> list = of__java_cs_util_cs_List_();
+    list->add_java_cs_lang_cs_Object__Z(e1);
+    return list;
+}
+ManagedPointer<java::util::List> java::util::List::of_java_cs_lang_cs_Object_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1,ManagedPointer<java::lang::Object> e2){
+    ManagedPointer<java::util::List> list = of_java_cs_lang_cs_Object__java_cs_util_cs_List_(e1);
+    list->add_java_cs_lang_cs_Object__Z(e2);
+    return list;
+}
+ManagedPointer<java::util::List> java::util::List::of_java_cs_lang_cs_Object_java_cs_lang_cs_Object_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1,ManagedPointer<java::lang::Object> e2,ManagedPointer<java::lang::Object> e3){
+    ManagedPointer<java::util::List> list = of_java_cs_lang_cs_Object_java_cs_lang_cs_Object__java_cs_util_cs_List_(e1,e2);
+    list->add_java_cs_lang_cs_Object__Z(e3);
+    return list;
+}
@@ -0,0 +1,19 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace util{struct List;};};
+namespace java{namespace util{namespace stream{class Stream;};};};
+struct java::util::List: public virtual java::lang::Object{
+    virtual ~List() = default;
+    virtual int32_t size__I() = 0;
+    virtual bool isEmpty__Z() = 0;
+    virtual ManagedPointer<java::lang::Object> get_I_java_cs_lang_cs_Object_(int32_t index) = 0;
+    virtual ManagedPointer<java::lang::Object> set_I_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(int32_t index,ManagedPointer<java::lang::Object> element) = 0;
+    virtual bool add_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> element) = 0;
+    virtual ManagedPointer<java::lang::Object> remove_I_java_cs_lang_cs_Object_(int32_t index) = 0;
+    virtual void clear__V() = 0;
+    virtual ManagedPointer<java::util::stream::Stream> stream__java_cs_util_cs_stream_cs_Stream_();
+    static ManagedPointer<java::util::List> of__java_cs_util_cs_List_();
+    static ManagedPointer<java::util::List> of_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1);
+    static ManagedPointer<java::util::List> of_java_cs_lang_cs_Object_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1,ManagedPointer<java::lang::Object> e2);
+    static ManagedPointer<java::util::List> of_java_cs_lang_cs_Object_java_cs_lang_cs_Object_java_cs_lang_cs_Object__java_cs_util_cs_List_(ManagedPointer<java::lang::Object> e1,ManagedPointer<java::lang::Object> e2,ManagedPointer<java::lang::Object> e3);
+};
@@ -0,0 +1,10 @@
+#include "java_cs_util_cs_Objects.hpp"
+#include "java_cs_lang_cs_NullPointerException.hpp"
+ManagedPointer<java::lang::Object> java::util::Objects::requireNonNull_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(ManagedPointer<java::lang::Object> obj){
+    if(obj == nullptr){
+        ManagedPointer<java::lang::NullPointerException> exception = new_managed(java::lang::NullPointerException,);
+        java::lang::Throwable::_init___V(exception);
+        throw ManagedPointer<java::lang::Throwable>(exception);
+    }
+    return obj;
+}
@@ -0,0 +1,8 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace util{class Objects;};};
+class java::util::Objects: public java::lang::Object{
+    public:
+    virtual ~Objects() = default;
+    static ManagedPointer<java::lang::Object> requireNonNull_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(ManagedPointer<java::lang::Object> obj);
+};
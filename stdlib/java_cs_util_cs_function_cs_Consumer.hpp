@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace util{namespace function{struct Consumer;};};};
+struct java::util::function::Consumer: public virtual java::lang::Object{
+    virtual ~Consumer() = default;
+    virtual void accept_java_cs_lang_cs_Object__V(ManagedPointer<java::lang::Object> obj) = 0;
+};
@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace util{namespace function{struct Function;};};};
+struct java::util::function::Function: public virtual java::lang::Object{
+    virtual ~Function() = default;
+    virtual ManagedPointer<java::lang::Object> apply_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(ManagedPointer<java::lang::Object> obj) = 0;
+};
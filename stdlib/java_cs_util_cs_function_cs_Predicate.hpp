@@ -1,3 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
 namespace java{namespace util{namespace function{struct Predicate;};};};
-
-//java_cs_util_cs_function_cs_Predicate_.hpp
\ No newline at end of file
+struct java::util::function::Predicate: public virtual java::lang::Object{
+    virtual ~Predicate() = default;
+    virtual bool test_java_cs_lang_cs_Object__Z(ManagedPointer<java::lang::Object> obj) = 0;
+};
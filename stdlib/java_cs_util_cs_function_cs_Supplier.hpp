@@ -0,0 +1,7 @@
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+namespace java{namespace util{namespace function{struct Supplier;};};};
+struct java::util::function::Supplier: public virtual java::lang::Object{
+    virtual ~Supplier() = default;
+    virtual ManagedPointer<java::lang::Object> get__java_cs_lang_cs_Object_() = 0;
+};
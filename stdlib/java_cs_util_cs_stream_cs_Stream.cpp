@@ -0,0 +1,38 @@
+#include "java_cs_util_cs_stream_cs_Stream.hpp"
+#include "java_cs_util_cs_ArrayList.hpp"
+#include "java_cs_util_cs_function_cs_Predicate.hpp"
+#include "java_cs_util_cs_function_cs_Function.hpp"
+#include "java_cs_util_cs_function_cs_Consumer.hpp"
+java::util::stream::Stream::Stream(std::vector<ManagedPointer<java::lang::Object>> data){
+    this->data = data;
+}
+ManagedPointer<java::util::stream::Stream> java::util::stream::Stream::filter_java_cs_util_cs_function_cs_Predicate__java_cs_util_cs_stream_cs_Stream_(ManagedPointer<java::util::function::Predicate> predicate){
+    std::vector<ManagedPointer<java::lang::Object>> res;
+    for(auto element: this->data){
+        if(predicate->test_java_cs_lang_cs_Object__Z(element))res.push_back(element);
+    }
+    return managed_from_raw(new java::util::stream::Stream(res));
+}
+ManagedPointer<java::util::stream::Stream> java::util::stream::Stream::map_java_cs_util_cs_function_cs_Function__java_cs_util_cs_stream_cs_Stream_(ManagedPointer<java::util::function::Function> function){
+    std::vector<ManagedPointer<java::lang::Object>> res;
+    res.reserve(this->data.size());
+    for(auto element: this->data){
+        res.push_back(function->apply_java_cs_lang_cs_Object__java_cs_lang_cs_Object_(element));
+    }
+    return managed_from_raw(new java::util::stream::Stream(res));
+}
+void java::util::stream::Stream::forEach_java_cs_util_cs_function_cs_Consumer__V(ManagedPointer<java::util::function::Consumer> consumer){
+    for(auto element: this->data){
+        consumer->accept_java_cs_lang_cs_Object__V(element);
+    }
+}
+int64_t java::util::stream::Stream::count__J(){
+    return (int64_t)this->data.size();
+}
+ManagedPointer<java::util::List> java::util::stream::Stream::toList__java_cs_util_cs_List_(){
+    ManagedPointer<java::util::ArrayList> list = new_managed(java::util::ArrayList,);
+    for(auto element: this->data){
+        list->add_java_cs_lang_cs_Object__Z(element);
+    }
+    return list;
+}
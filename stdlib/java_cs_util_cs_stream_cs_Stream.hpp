@@ -1,3 +1,18 @@
-#include "java_cs_lang_cs_invoke_cs_ LambdaMetafactory.hpp"
-#include "java_cs_util_cs_function_cs_Predicate.hpp"
-CREATE_LAMBDA_CLASS(test__java_cs_util_cs_function_cs_Predicate_,java::util::function::Predicate,());
\ No newline at end of file
+#pragma once
+#include "java_cs_lang_cs_Object.hpp"
+#include <vector>
+namespace java{namespace util{struct List;};};
+namespace java{namespace util{namespace function{struct Predicate;struct Function;struct Consumer;};};};
+namespace java{namespace util{namespace stream{class Stream;};};};
+// Eagerly evaluated stream, backed by a vector of its elements.
+class java::util::stream::Stream: public virtual java::lang::Object{
+    std::vector<ManagedPointer<java::lang::Object>> data;
+    public:
+    Stream(std::vector<ManagedPointer<java::lang::Object>> data);
+    virtual ~Stream() = default;
+    virtual ManagedPointer<java::util::stream::Stream> filter_java_cs_util_cs_function_cs_Predicate__java_cs_util_cs_stream_cs_Stream_(ManagedPointer<java::util::function::Predicate> predicate);
+    virtual ManagedPointer<java::util::stream::Stream> map_java_cs_util_cs_function_cs_Function__java_cs_util_cs_stream_cs_Stream_(ManagedPointer<java::util::function::Function> function);
+    virtual void forEach_java_cs_util_cs_function_cs_Consumer__V(ManagedPointer<java::util::function::Consumer> consumer);
+    virtual int64_t count__J();
+    virtual ManagedPointer<java::util::List> toList__java_cs_util_cs_List_();
+};
@@ -0,0 +1,68 @@
+// Integer arithmetic wraps around, shift distances are masked, and float to integer conversions saturate, like on the JVM.
+public class Arithmetic{
+    static int wrapInt(int a, int b){
+        return a + b;
+    }
+    static long wrapLong(long a, long b){
+        return a * b;
+    }
+    static int negate(int a){
+        return -a;
+    }
+    static int divide(int a, int b){
+        return a / b;
+    }
+    static int remainder(int a, int b){
+        return a % b;
+    }
+    static long divideLong(long a, long b){
+        return a / b;
+    }
+    static void divideByZero(){
+        try{
+            divide(1, 0);
+            System.out.println("No exception!");
+        }
+        catch(ArithmeticException e){
+            System.out.println("int: " + e.getMessage());
+        }
+    }
+    static void remainderByZero(){
+        try{
+            divideLong(1, 0);
+            System.out.println("No exception!");
+        }
+        catch(ArithmeticException e){
+            System.out.println("long: " + e.getMessage());
+        }
+    }
+    static int shiftInt(int a, int by){
+        return a << by;
+    }
+    static int unsignedShift(int a, int by){
+        return a >>> by;
+    }
+    static long shiftLong(long a, int by){
+        return a >> by;
+    }
+    static int floatToInt(float value){
+        return (int)value;
+    }
+    static long doubleToLong(double value){
+        return (long)value;
+    }
+    public static void main(String[] args){
+        System.out.println("wrap: " + wrapInt(Integer.MAX_VALUE, 1));
+        System.out.println("wrap long: " + wrapLong(Long.MAX_VALUE, 3));
+        System.out.println("negate: " + negate(Integer.MIN_VALUE) + " " + negate(5));
+        System.out.println("min / -1: " + divide(Integer.MIN_VALUE, -1));
+        System.out.println("long min / -1: " + divideLong(Long.MIN_VALUE, -1));
+        System.out.println("remainders: " + remainder(-7, 3) + " " + remainder(Integer.MIN_VALUE, -1));
+        divideByZero();
+        remainderByZero();
+        System.out.println("shifts: " + shiftInt(1, 33) + " " + unsignedShift(-1, 28) + " " + shiftLong(-256L, 68));
+        System.out.println("f2i: " + floatToInt(Float.NaN) + " " + floatToInt(1e20f) + " " + floatToInt(-1e20f) + " " + floatToInt(-2.7f));
+        System.out.println("d2l: " + doubleToLong(Double.NaN) + " " + doubleToLong(1e300) + " " + doubleToLong(-1e300));
+        System.out.println("double: " + (0.1 + 0.2) + " " + (-(double)1.5));
+    }
+}
@@ -0,0 +1,73 @@
+// Accessing arrays out of bounds throws `ArrayIndexOutOfBoundsException`, and creating arrays of negative size throws `NegativeArraySizeException`.
+// Translate with `--no-bounds-checks` to disable index checks.
+class IntStack{
+    int[] items;
+    int size;
+    IntStack(int capacity){
+        this.items = new int[capacity];
+    }
+    void push(int item){
+        items[size] = item;
+        size++;
+    }
+    int pop(){
+        size--;
+        return items[size];
+    }
+}
+public class ArrayBounds{
+    // Splits `values` into `rows` rows, of which the last may be shorter.
+    static int[][] rows(int[] values, int rows){
+        int[][] result = new int[rows][values.length / rows];
+        for(int index = 0; index < values.length; index++){
+            result[index / result[0].length][index % result[0].length] = values[index];
+        }
+        return result;
+    }
+    public static void main(String[] args){
+        IntStack stack = new IntStack(2);
+        stack.push(1);
+        stack.push(2);
+        try{
+            stack.push(3);
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            System.out.println("push: " + e.getMessage());
+        }
+        System.out.println("popped: " + stack.pop() + " " + stack.pop());
+        try{
+            stack.pop();
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            System.out.println("pop: " + e.getMessage());
+        }
+        String[] empty = new String[0];
+        try{
+            empty[0] = "value";
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            System.out.println("empty: " + e.getMessage());
+        }
+        int[] values = new int[]{1, 2, 3, 4, 5};
+        try{
+            rows(values, 2);
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            System.out.println("rows: " + e.getMessage());
+        }
+        System.out.println("row length: " + rows(values, 5)[4].length);
+        try{
+            new IntStack(values.length - 6);
+        }
+        catch(NegativeArraySizeException e){
+            System.out.println("capacity: " + e.getMessage());
+        }
+        try{
+            rows(values, -1);
+        }
+        catch(NegativeArraySizeException e){
+            System.out.println("row count: " + e.getMessage());
+        }
+        System.out.println("no rows: " + rows(new int[0], 1).length);
+    }
+}
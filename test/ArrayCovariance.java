@@ -0,0 +1,87 @@
+// Arrays of references are covariant(a `String[]` is an `Object[]`), storing an object of a wrong class into an array throws
+// `ArrayStoreException`, and arrays are objects, supporting `clone`, `getClass` and `equals`.
+class Animal{
+    String name(){
+        return "animal";
+    }
+}
+class Dog extends Animal{
+    String name(){
+        return "dog";
+    }
+}
+interface Pet{
+}
+class Cat extends Animal implements Pet{
+}
+public class ArrayCovariance{
+    static int count(Object[] objects){
+        return objects.length;
+    }
+    static String join(Object... parts){
+        String joined = "";
+        for(int index = 0; index < parts.length; index++){
+            joined = joined + parts[index];
+        }
+        return joined;
+    }
+    static void store(Object[] objects, Object value){
+        objects[0] = value;
+    }
+    static void cast(Object object){
+        Dog[] dogs = (Dog[])object;
+    }
+    public static void main(String[] args){
+        String[] words = new String[]{"a", "b"};
+        System.out.println("count: " + count(words));
+        System.out.println("joined: " + join((Object[])words) + " " + join("c", "d", "e"));
+        store(words, "z");
+        Animal[] animals = new Dog[2];
+        store(animals, new Dog());
+        System.out.println(words[0] + " " + animals[0].name());
+        // Each array only accepts values of its runtime element class, whatever the static type of the array.
+        Object[][] targets = new Object[][]{new String[1], new Dog[1], new int[1][]};
+        Object[] values = new Object[]{new Dog(), new Animal(), new double[1]};
+        for(int index = 0; index < targets.length; index++){
+            try{
+                store(targets[index], values[index]);
+                System.out.println("No exception!");
+            }
+            catch(ArrayStoreException e){
+                System.out.println("ArrayStoreException: " + e.getMessage());
+            }
+        }
+        Object cats = new Cat[1];
+        System.out.println("Cat[] is Pet[]: " + (cats instanceof Pet[]) + ", Animal[]: " + (cats instanceof Animal[]) + ", Dog[]: " + (cats instanceof Dog[]));
+        Object[][] nested = new String[2][3];
+        store(nested, new String[4]);
+        System.out.println("nested: " + nested[0].length);
+        String[] copy = words.clone();
+        if(copy != words && copy.length == 2 && copy[0].equals("z")){
+            System.out.println("clone copies elements");
+        }
+        if(words.equals(words) && !words.equals(copy)){
+            System.out.println("arrays are equal only to themselves");
+        }
+        System.out.println(words.getClass().getName() + " " + new int[2][3].getClass().getName() + " " + nested.getClass().getName());
+        Object object = animals;
+        if(object instanceof Animal[] && object instanceof Object[] && !(object instanceof String[])){
+            System.out.println("instanceof checks array classes");
+        }
+        cast(object);
+        Object[] notDogs = new Object[]{words, new Animal[1]};
+        for(int index = 0; index < notDogs.length; index++){
+            try{
+                cast(notDogs[index]);
+                System.out.println("No exception!");
+            }
+            catch(ClassCastException e){
+                System.out.println("ClassCastException");
+            }
+        }
+        Object[] rows = new int[2][];
+        if(rows instanceof int[][]){
+            System.out.println("int[][] is an Object[]");
+        }
+    }
+}
@@ -0,0 +1,92 @@
+import java.util.ArrayList;
+interface Shape{
+    int sides();
+}
+class Square implements Shape{
+    int size;
+    Square(int size){
+        this.size = size;
+    }
+    public int sides(){
+        return 4;
+    }
+    public boolean equals(Object other){
+        if(!(other instanceof Square)) return false;
+        Square square = (Square)other;
+        if(square.size != this.size) return false;
+        return true;
+    }
+}
+class Triangle implements Shape{
+    public int sides(){
+        return 3;
+    }
+}
+class Casts{
+    static int countSides(ArrayList shapes){
+        int sides = 0;
+        for(int i = 0; i < shapes.size(); i++){
+            Shape shape = (Shape)shapes.get(i);
+            sides += shape.sides();
+        }
+        return sides;
+    }
+    static Square toSquare(Object obj){
+        return (Square)obj;
+    }
+    static Object nothing(){
+        return null;
+    }
+    public static void main(String[] args){
+        Square a = new Square(2);
+        Square b = new Square(2);
+        Square c = new Square(3);
+        if(a.equals(b) && !a.equals(c) && !a.equals(new Triangle()) && !a.equals(null)){
+            System.out.println("equals(Object) works!");
+        }
+        ArrayList shapes = new ArrayList();
+        shapes.add(a);
+        shapes.add(new Triangle());
+        if(countSides(shapes) == 7){
+            System.out.println("Downcasts from collections work!");
+        }
+        Square none = (Square)nothing();
+        if(none == null && !(nothing() instanceof Object)){
+            System.out.println("Null casts work!");
+        }
+        Object shape = new Triangle();
+        if(shape instanceof Shape && !(shape instanceof Square)){
+            System.out.println("Interface checks work!");
+        }
+        Object ints = new int[4];
+        if(ints instanceof int[] && !(ints instanceof float[])){
+            System.out.println("Array checks work!");
+        }
+        try{
+            toSquare(shape);
+            System.out.println("Invalid cast did not throw!");
+        }
+        catch(ClassCastException e){
+            System.out.println(e.getMessage());
+        }
+        // Messages name the module of both classes, which differs for classes of the standard library.
+        Object[] values = new Object[]{"text", ints};
+        for(int index = 0; index < values.length; index++){
+            try{
+                String text = (String)values[index];
+                Integer number = (Integer)values[index];
+                System.out.println("Invalid cast did not throw!");
+            }
+            catch(ClassCastException e){
+                System.out.println(e.getMessage());
+            }
+        }
+        try{
+            String text = (String)shape;
+            System.out.println("Invalid cast did not throw!");
+        }
+        catch(ClassCastException e){
+            System.out.println(e.getMessage());
+        }
+    }
+}
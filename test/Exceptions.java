@@ -0,0 +1,40 @@
+class NegativeException extends Exception{
+  public NegativeException(String message){
+    super(message);
+  }
+}
+class Exceptions{
+  static int check(int value) throws NegativeException{
+    if(value < 0){
+      throw new NegativeException("Value is negative!");
+    }
+    return value;
+  }
+  static void multiCatch(int runtime){
+    try{
+      if(runtime > 0){
+        throw new RuntimeException("Runtime!");
+      }
+      check(-2);
+    }
+    catch(NegativeException | RuntimeException exception){
+      System.out.println(exception.getMessage());
+    }
+  }
+  public static void main(String[] args){
+    try{
+      check(1);
+      System.out.println("1 is not negative.");
+      check(-1);
+      System.out.println("Unreachable!");
+    }
+    catch(NegativeException exception){
+      System.out.println(exception.getMessage());
+    }
+    finally{
+      System.out.println("Finally!");
+    }
+    multiCatch(1);
+    multiCatch(0);
+  }
+}
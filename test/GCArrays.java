@@ -0,0 +1,76 @@
+// Arrays are allocated through the GC. Objects reachable only through arrays must survive collections, while unreachable arrays are freed.
+class Node{
+    int value;
+    Node next;
+    Node(int value){
+        this.value = value;
+    }
+}
+public class GCArrays{
+    static Node[] fill(int length, int first){
+        Node[] nodes = new Node[length];
+        for(int index = 0; index < length; index++){
+            nodes[index] = new Node(first + index);
+        }
+        return nodes;
+    }
+    static long sum(Node[] nodes){
+        long sum = 0;
+        for(int index = 0; index < nodes.length; index++){
+            sum += nodes[index].value;
+        }
+        return sum;
+    }
+    // Allocates a lot of short-lived object and primitive arrays, some of them forming cycles or used as monitors.
+    static void churn(int rounds){
+        for(int round = 0; round < rounds; round++){
+            Node[] garbage = fill(64, round);
+            garbage[63].next = garbage[0];
+            garbage[0].next = garbage[63];
+            int[] numbers = new int[1024];
+            numbers[1023] = round;
+            // Monitors of primitive arrays are freed when the array is collected.
+            synchronized(numbers){
+                numbers[0] = round;
+            }
+        }
+    }
+    static boolean check(Node[][] kept, int length){
+        for(int array = 0; array < kept.length; array++){
+            long first = array * length;
+            long expected = first * length + (long)length * (length - 1) / 2;
+            if(sum(kept[array]) != expected){
+                System.out.println("Array " + array + " was corrupted!");
+                return false;
+            }
+        }
+        return true;
+    }
+    public static void main(String[] args){
+        Node[][] kept = new Node[32][];
+        for(int array = 0; array < kept.length; array++){
+            kept[array] = fill(100, array * 100);
+        }
+        double[][] matrix = new double[64][64];
+        matrix[63][63] = 0.5;
+        for(int pass = 0; pass < 8; pass++){
+            churn(1000);
+            System.gc();
+            if(!check(kept, 100)){
+                return;
+            }
+        }
+        if(matrix[63][63] == 0.5){
+            System.out.println("Arrays survived collections!");
+        }
+        // Primitives are printed through buffers of their own, without a trailing `\r`.
+        System.out.print("Kept arrays: ");
+        System.out.println(kept.length);
+        System.out.print(kept[0].length);
+        System.out.print(' ');
+        System.out.print(matrix[63][63] > 0);
+        System.out.print(' ');
+        System.out.print((float)matrix[63][63]);
+        System.out.println();
+    }
+}
@@ -1,6 +1,11 @@
-// Interfaces don't yet work!
+import java.util.ArrayList;
+import java.util.List;
 interface Animal{
   public void Sound();
+  default void Greet(){
+    System.out.println("Hello!");
+    Sound();
+  }
 }
 class Dog implements Animal{
   public void Sound(){
@@ -17,3 +22,70 @@ class Duck implements Animal{
     System.out.println("Quack!");
   }
 }
+class Bird{
+  public void Sound(){
+    System.out.println("Tweet!");
+  }
+}
+// Inherits the implementation of `Sound` from `Bird`.
+class Parrot extends Bird implements Animal{}
+interface Named{
+  default String name(){
+    return "named";
+  }
+}
+class Base{
+  public String name(){
+    return "base";
+  }
+}
+// The method of the superclass takes precedence over the default method.
+class Derived extends Base implements Named{}
+interface Titled extends Named{
+  default String name(){
+    return "titled";
+  }
+}
+// The default method of `Titled` overrides the one of `Named`, so it is the one inherited.
+class Book implements Titled, Named{}
+class Task implements Runnable{
+  public void run(){
+    System.out.println("Running!");
+  }
+}
+class Interfaces{
+  static void makeSound(Animal animal){
+    animal.Sound();
+  }
+  public static void main(String[] args){
+    makeSound(new Dog());
+    makeSound(new Cow());
+    makeSound(new Duck());
+    makeSound(new Parrot());
+    Animal parrot = new Parrot();
+    parrot.Greet();
+    Named derived = new Derived();
+    System.out.println(derived.name());
+    Named book = new Book();
+    System.out.println(book.name());
+    Runnable task = new Task();
+    task.run();
+    List<Animal> animals = new ArrayList<Animal>();
+    animals.add(new Cow());
+    if(animals.size() == 1){
+      System.out.println("One animal in the list.");
+    }
+    try{
+      animals.get(1);
+    }
+    catch(IndexOutOfBoundsException e){
+      System.out.println(e.getMessage());
+    }
+    try{
+      animals.remove(-1);
+    }
+    catch(IndexOutOfBoundsException e){
+      System.out.println(e.getMessage());
+    }
+  }
+}
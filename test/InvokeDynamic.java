@@ -1,8 +1,85 @@
-// This example uses lambdas, which use `InvokeDynamic` op under the hood.
+// This example uses lambdas and method references, which use `InvokeDynamic` op under the hood.
 import java.util.List;
-class InvokeDynamic { 
+import java.util.function.Function;
+import java.util.function.Supplier;
+interface IntGetter{
+    int get();
+}
+interface IntOp{
+    int apply(int a, int b);
+}
+class Counter{
+    int count;
+    void add(int amount){
+        this.count += amount;
+    }
+    int get(){
+        return this.count;
+    }
+}
+class Box{
+    String name;
+    Box(){
+        this.name = "Empty box";
+    }
+    Box(String name){
+        this.name = name;
+    }
+}
+class InvokeDynamic {
+    int base;
+    InvokeDynamic(int base){
+        this.base = base;
+    }
+    static int mul(int a, int b){
+        return a * b;
+    }
+    // A lambda which captures `this`
+    IntOp adder(){
+        return (a, b) -> a + b + this.base;
+    }
     public static void main(String[] args) {
         long lengthyColors = List.of("Red", "Green", "Blue")
-          .stream().filter(c -> c.length() > 3).count();
+          .stream().filter(c -> {
+              if(c.length() > 3) return true;
+              return false;
+          }).count();
+        if(lengthyColors == 2){
+            System.out.println("Stream filters work!");
+        }
+        // Capturing lambda
+        int offset = 5;
+        IntOp sub = (a, b) -> a - b + offset;
+        // Static method reference
+        IntOp mul = InvokeDynamic::mul;
+        if(sub.apply(10, 3) == 12 && mul.apply(6, 7) == 42 && new InvokeDynamic(100).adder().apply(1, 2) == 103){
+            System.out.println("Lambdas work!");
+        }
+        // Bound method reference
+        Counter counter = new Counter();
+        Runnable increment = () -> counter.add(2);
+        increment.run();
+        increment.run();
+        IntGetter getter = counter::get;
+        if(getter.get() == 4){
+            System.out.println("Bound method references work!");
+        }
+        // Unbound method reference
+        Function<Box, String> name = b -> b.name;
+        // Constructor references
+        Supplier<Box> empty = Box::new;
+        Function<String, Box> named = Box::new;
+        System.out.println(name.apply(empty.get()));
+        System.out.println(named.apply("Named box").name);
+        List.of("Printed", "by", "forEach").stream().forEach(System.out::println);
+        if(List.of("Mapped").stream().map(Box::new).toList().get(0) instanceof Box){
+            System.out.println("Stream maps work!");
+        }
+        // Method references returning primitives are boxed, and ones returning boxes are unboxed.
+        Function<String, Integer> length = String::length;
+        Object boxed = length.apply("four");
+        Supplier<Integer> answer = () -> 42;
+        IntGetter unboxed = answer::get;
+        System.out.println("Boxing: " + boxed + " " + unboxed.get() + " " + boxed.equals(length.apply("five")));
     }
 }
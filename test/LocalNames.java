@@ -0,0 +1,67 @@
+// Locals are named after their names in Java source, if the class file has a `LocalVariableTable`(`javac -g`). Names which are C++
+// keywords, used by the translated code, or taken by the C library, are escaped, and locals with the same name in different scopes
+// get separate names.
+public class LocalNames{
+    static String keywords(int new_, String delete){
+        int auto = 1;
+        String register = delete + auto;
+        int i0 = 2;
+        String java = "java";
+        String null_check = "checked";
+        return register + " " + new_ + " " + i0 + " " + java + " " + null_check;
+    }
+    static String scopes(){
+        String result = "";
+        for(int index = 0; index < 2; index++){
+            result = result + index;
+        }
+        for(int index = 0; index < 3; index++){
+            result = result + index;
+        }
+        {
+            String index = " text";
+            result = result + index;
+        }
+        return result;
+    }
+    // A local named like the variable holding caught exceptions keeps its value when another exception is caught.
+    static String handlers(String text){
+        String exception = "kept";
+        try{
+            try{
+                text.length();
+            }
+            catch(NullPointerException caught){
+                exception = exception + " inner";
+                throw new IllegalArgumentException("outer");
+            }
+        }
+        catch(IllegalArgumentException caught){
+            exception = exception + " " + caught.getMessage();
+        }
+        return exception;
+    }
+    static String library(){
+        int int32_t = 32;
+        int uint8_t = 8;
+        int EOF = -1;
+        int INT32_MAX = 7;
+        String stdout = "out";
+        int errno = 2;
+        int total = int32_t + uint8_t + EOF + INT32_MAX + errno;
+        return stdout + " " + total;
+    }
+    String name = "instance";
+    String field(String template){
+        String operator = template + this.name;
+        return operator;
+    }
+    public static void main(String[] args){
+        System.out.println("keywords: " + keywords(3, "delete"));
+        System.out.println("scopes: " + scopes());
+        System.out.println("handlers: " + handlers(null));
+        System.out.println("library: " + library());
+        System.out.println("field: " + new LocalNames().field("this "));
+        System.out.println("Locals named!");
+    }
+}
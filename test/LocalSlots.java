@@ -0,0 +1,64 @@
+import java.util.ArrayList;
+import java.util.List;
+// javac reuses local slots for locals of different scopes. Each of them is translated to a separate, correctly typed C++ variable.
+public class LocalSlots{
+    static String loops(){
+        String result = "";
+        for(int index = 0; index < 2; index++){
+            String word = "w" + index;
+            result = result + word + " ";
+        }
+        for(int index = 0; index < 2; index++){
+            List<String> list = new ArrayList<>();
+            list.add("item");
+            result = result + list.size() + " ";
+        }
+        return result;
+    }
+    static String kinds(boolean first){
+        String result;
+        if(first){
+            long big = 1L << 40;
+            result = "long " + big;
+        }
+        else{
+            double half = 0.5;
+            result = "double " + half;
+        }
+        {
+            int[] numbers = new int[]{4, 5};
+            result = result + " " + numbers[1];
+        }
+        return result;
+    }
+    static String reassigned(String first){
+        String text = first;
+        String result = text;
+        text = text + "!";
+        return result + " " + text;
+    }
+    static String catches(Object object){
+        String result = "";
+        try{
+            result = result + ((String)object).length();
+        }
+        catch(ClassCastException e){
+            result = result + "ClassCastException";
+        }
+        try{
+            Object[] array = new Object[1];
+            result = result + " " + array[2];
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            result = result + " ArrayIndexOutOfBoundsException";
+        }
+        return result;
+    }
+    public static void main(String[] args){
+        System.out.println("loops: " + loops());
+        System.out.println("kinds: " + kinds(true) + ", " + kinds(false));
+        System.out.println("reassigned: " + reassigned("text"));
+        System.out.println("catches: " + catches("four") + ", " + catches(new ArrayList<String>()));
+        System.out.println("Slots split!");
+    }
+}
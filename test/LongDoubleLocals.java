@@ -0,0 +1,26 @@
+class LongDoubleLocals{
+    static int average(int[] values){
+        long sum = 0;
+        for(int i = 0; i < values.length; i++){
+            sum += values[i];
+        }
+        double mean = (double)sum / values.length;
+        double scaled = mean * 10.0;
+        return (int)scaled;
+    }
+    static int percentOf(int amount, int percent){
+        long big = amount;
+        big *= 1000;
+        double ratio = percent / 100.0;
+        double part = big * ratio;
+        return (int)part;
+    }
+    public static void main(String[] args){
+        if(average(new int[]{1, 2, 3, 4}) == 25){
+            System.out.println("Long sums and double means work!");
+        }
+        if(percentOf(50, 25) == 12500){
+            System.out.println("Mixed long and double locals work!");
+        }
+    }
+}
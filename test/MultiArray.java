@@ -0,0 +1,44 @@
+class MultiArray{
+  static float[][] identity(int size){
+    float[][] matrix = new float[size][size];
+    for(int i = 0; i < size; i++){
+      matrix[i][i] = 1.0f;
+    }
+    return matrix;
+  }
+  static float[][] multiply(float[][] a, float[][] b){
+    int size = a.length;
+    float[][] res = new float[size][size];
+    for(int i = 0; i < size; i++){
+      for(int j = 0; j < size; j++){
+        float sum = 0.0f;
+        for(int k = 0; k < size; k++){
+          sum += a[i][k] * b[k][j];
+        }
+        res[i][j] = sum;
+      }
+    }
+    return res;
+  }
+  public static void main(String[] args){
+    float[][] matrix = identity(4);
+    matrix[1][2] = 3.0f;
+    float[][] squared = multiply(matrix, matrix);
+    if(squared[1][2] == 6.0f && squared[3][3] == 1.0f && squared[0][1] == 0.0f){
+      System.out.println("Matrix multiplication works!");
+    }
+    int[][][] cube = new int[2][3][4];
+    cube[1][2][3] = 7;
+    if(cube.length == 2 && cube[1].length == 3 && cube[1][2].length == 4 && cube[1][2][3] == 7){
+      System.out.println("3D arrays work!");
+    }
+    int[][] jagged = new int[3][];
+    if(jagged[0] == null){
+      System.out.println("Partially specified arrays work!");
+    }
+    jagged[1] = new int[5];
+    String[][] names = new String[2][2];
+    names[0][1] = "Object arrays work!";
+    System.out.println(names[0][1]);
+  }
+}
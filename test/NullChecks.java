@@ -0,0 +1,81 @@
+// Dereferencing null throws `NullPointerException`, whose message names the method it happened in. Translate with `--no-null-checks` to disable the checks.
+interface Shape{
+    int area();
+}
+class Node{
+    int value;
+    Node next;
+    Node(int value, Node next){
+        this.value = value;
+        this.next = next;
+    }
+    int doubled(){
+        return value * 2;
+    }
+}
+public class NullChecks{
+    static int[] missing;
+    // Walks past the end of a list shorter than `count`.
+    static int sum(Node list, int count){
+        int sum = 0;
+        Node node = list;
+        for(int index = 0; index < count; index++){
+            sum += node.value;
+            node = node.next;
+        }
+        return sum;
+    }
+    static void setThird(Node list, int value){
+        list.next.next.value = value;
+    }
+    static int doubleLast(Node list){
+        return list.next.next.doubled();
+    }
+    static int totalArea(Shape[] shapes){
+        int total = 0;
+        for(int index = 0; index < shapes.length; index++){
+            total += shapes[index].area();
+        }
+        return total;
+    }
+    static int first(int[] values){
+        return values[0];
+    }
+    static void fill(int[] values){
+        values[0] = 1;
+    }
+    static int count(int[] values){
+        return values.length;
+    }
+    static void fail(RuntimeException reason){
+        throw reason;
+    }
+    // Static, despite `_init_` in its name.
+    static int re_init_value(Node node){
+        return node.value;
+    }
+    // Runs `action`, printing the message of the `NullPointerException` it throws.
+    static void expectNull(Runnable action){
+        try{
+            action.run();
+        }
+        catch(NullPointerException e){
+            System.out.println(e.getMessage());
+        }
+    }
+    public static void main(String[] args){
+        Node list = new Node(1, new Node(2, null));
+        System.out.println("sum: " + sum(list, 2));
+        expectNull(() -> sum(list, 3));
+        expectNull(() -> setThird(list, 3));
+        expectNull(() -> doubleLast(list));
+        Shape square = () -> 4;
+        expectNull(() -> totalArea(new Shape[]{square, null}));
+        expectNull(() -> first(missing));
+        expectNull(() -> fill(missing));
+        expectNull(() -> count(missing));
+        expectNull(() -> fail(null));
+        expectNull(() -> re_init_value(null));
+        System.out.println("area: " + totalArea(new Shape[]{square, square}));
+    }
+}
@@ -0,0 +1,58 @@
+// Values left on the operand stack by one branch and used after it joins(`?:`, `&&` and `||` results, arguments evaluated before a
+// conditional) are passed between basic blocks in typed locals.
+public class StackMerge{
+    static String pick(boolean first, String a, String b){
+        return first ? a : b;
+    }
+    static int max(int a, int b){
+        return a > b ? a : b;
+    }
+    static long abs(long value){
+        return value < 0 ? -value : value;
+    }
+    static double clamp(double value){
+        return value > 1.0 ? 1.0 : (value < 0.0 ? 0.0 : value);
+    }
+    static boolean between(int value, int low, int high){
+        return value >= low && value <= high;
+    }
+    static boolean outside(int value, int low, int high){
+        return value < low || value > high;
+    }
+    static String describe(int count, String name){
+        return count + " " + (count == 1 ? name : name + "s");
+    }
+    static int sum(int a, int b, int c){
+        return a + b + c;
+    }
+    static String label(Object object){
+        return object == null ? "null" : "object";
+    }
+    // The index is pushed before it is incremented, and stays on the stack across the join of `?:`.
+    static String fill(boolean first){
+        int[] values = new int[3];
+        int index = 0;
+        values[index++] = first ? 1 : 2;
+        values[index++] = first ? 3 : 4;
+        int old = index;
+        int sum = old + (old = 10);
+        return values[0] + " " + values[1] + " " + values[2] + " " + index + " " + sum;
+    }
+    public static void main(String[] args){
+        System.out.println(pick(true, "first", "second") + " " + pick(false, "first", "second"));
+        System.out.println("max: " + max(3, 7) + " " + max(9, -2));
+        System.out.println("abs: " + abs(-5L) + " " + abs(6L));
+        System.out.println("clamp: " + clamp(2.5) + " " + clamp(-1.0) + " " + clamp(0.25));
+        if(between(5, 1, 10) && !between(11, 1, 10) && outside(0, 1, 10) && !outside(3, 1, 10)){
+            System.out.println("boolean results work");
+        }
+        System.out.println(describe(1, "apple") + ", " + describe(3, "pear"));
+        int x = 4;
+        System.out.println("nested arguments: " + sum(x, x > 2 ? 10 : 20, x < 2 ? 100 : 200));
+        System.out.println(label(null) + " " + label("text"));
+        boolean flag = x > 3 && x < 5;
+        String text = flag ? "flag set" : "flag unset";
+        System.out.println(text);
+        System.out.println("fill: " + fill(true) + ", " + fill(false));
+    }
+}
@@ -0,0 +1,46 @@
+// Exceptions record Java stack traces, printed by `printStackTrace`. An exception thrown out of `main` is reported with its trace,
+// and the program exits with status 1.
+class ValidationException extends RuntimeException{
+    ValidationException(String message){
+        super(message);
+    }
+}
+public class StackTraces{
+    static int depth(int left){
+        if(left == 0){
+            throw new IllegalArgumentException("bottom reached");
+        }
+        return depth(left - 1) + 1;
+    }
+    static void validate(String value){
+        if(value.length() == 0){
+            throw new ValidationException("empty value");
+        }
+    }
+    int[] values = new int[2];
+    int read(int index){
+        return values[index];
+    }
+    public static void main(String[] args){
+        try{
+            depth(2);
+        }
+        catch(IllegalArgumentException e){
+            e.printStackTrace();
+        }
+        try{
+            validate("");
+        }
+        catch(ValidationException e){
+            e.printStackTrace();
+        }
+        try{
+            new StackTraces().read(5);
+        }
+        catch(ArrayIndexOutOfBoundsException e){
+            e.printStackTrace();
+        }
+        System.out.println("Stack traces printed!");
+        depth(1);
+    }
+}
@@ -0,0 +1,116 @@
+class Config{
+    // Compile-time constants, emitted as initializers of static fields.
+    static final int ANSWER = 42;
+    static final long BIG = -9223372036854775808L;
+    static final float NOT_A_NUMBER = Float.NaN;
+    static final double TINY = 1e-300;
+    static final char LETTER = 'J';
+    static final String NAME = "config \"quoted\"";
+    static int counter;
+    static{
+        System.out.println("Config initialized");
+        counter = 10;
+    }
+    static int next(){
+        return counter++;
+    }
+}
+class Base{
+    static int created;
+    static{
+        System.out.println("Base initialized");
+    }
+}
+class Derived extends Base{
+    static{
+        System.out.println("Derived initialized");
+    }
+    Derived(){
+        created += 1;
+    }
+}
+class Lazy{
+    static{
+        System.out.println("Lazy initialized");
+    }
+    static void touch(){
+    }
+}
+class Slow{
+    static int value;
+    static{
+        System.out.println("Slow initialized");
+        try{
+            Thread.sleep(100);
+        }
+        catch(InterruptedException e){
+        }
+        value = 7;
+    }
+}
+class SlowReader extends Thread{
+    int seen;
+    public void run(){
+        seen = Slow.value;
+    }
+}
+class First{
+    static int value = Second.value + 1;
+}
+class Second{
+    static int value = First.value + 1;
+}
+class Broken{
+    static int value = fail();
+    static int fail(){
+        throw new IllegalArgumentException("broken");
+    }
+}
+class StaticInit{
+    static String greeting = "main class initialized first";
+    static{
+        System.out.println(greeting);
+    }
+    public static void main(String[] args) throws InterruptedException{
+        System.out.println("main started");
+        Lazy.touch();
+        Lazy.touch();
+        new Derived();
+        new Derived();
+        if(Base.created == 2){
+            System.out.println("Superclass initialized once!");
+        }
+        if(Config.next() == 10 && Config.next() == 11){
+            System.out.println("Static state works!");
+        }
+        System.out.println(Config.NAME);
+        SlowReader[] readers = new SlowReader[4];
+        for(int i = 0; i < readers.length; i++){
+            readers[i] = new SlowReader();
+            readers[i].start();
+        }
+        boolean allSeen = true;
+        for(int i = 0; i < readers.length; i++){
+            readers[i].join();
+            allSeen = allSeen && readers[i].seen == 7;
+        }
+        if(allSeen){
+            System.out.println("Threads wait for static initializers of other threads!");
+        }
+        if(First.value == 2 && Second.value == 1){
+            System.out.println("Recursive initialization works!");
+        }
+        try{
+            System.out.println(Broken.value);
+        }
+        catch(ExceptionInInitializerError e){
+            System.out.println("Initializer failed: " + e.getCause().getMessage());
+        }
+        try{
+            System.out.println(Broken.value);
+        }
+        catch(NoClassDefFoundError e){
+            System.out.println(e.getMessage());
+        }
+    }
+}
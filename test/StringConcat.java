@@ -0,0 +1,33 @@
+class Point{
+    int x;
+    int y;
+    Point(int x, int y){
+        this.x = x;
+        this.y = y;
+    }
+    public String toString(){
+        return "(" + x + ", " + y + ")";
+    }
+}
+class StringConcat{
+    static String nothing(){
+        return null;
+    }
+    public static void main(String[] args){
+        int i = -42;
+        long l = 9000000000L;
+        float f = 1.5f;
+        double d = 0.25;
+        char c = 'c';
+        short s = 7;
+        byte b = -3;
+        String str = "text";
+        System.out.println("int:" + i + " long:" + l + " short:" + s + " byte:" + b);
+        System.out.println("float:" + f + " double:" + d + " big:" + 1e10 + " small:" + 1e-5);
+        System.out.println("char:" + c + " string:" + str + " null:" + nothing());
+        System.out.println("object:" + new Point(1, 2));
+        // Literals containing the recipe tags are passed as constants.
+        System.out.println("tags:\u0001\u0002 escapes:\"\\\t" + i);
+        System.out.println(str + str);
+    }
+}
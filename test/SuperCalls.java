@@ -0,0 +1,34 @@
+class Shape{
+    int sides(){
+        return 0;
+    }
+    int describe(){
+        return sides() * 10;
+    }
+}
+class Square extends Shape{
+    int sides(){
+        return 4;
+    }
+    int describe(){
+        return super.describe() + 1;
+    }
+    int shapeSides(){
+        return super.sides();
+    }
+}
+class SuperCalls{
+    public static void main(String[] args){
+        Square square = new Square();
+        if(square.describe() == 41){
+            System.out.println("Super methods call overriding methods!");
+        }
+        if(square.shapeSides() == 0){
+            System.out.println("Super calls are not virtual!");
+        }
+        Shape shape = square;
+        if(shape.describe() == 41){
+            System.out.println("Overriding methods are called through the base class!");
+        }
+    }
+}
@@ -0,0 +1,54 @@
+class Switch{
+    // Dense keys compile to a `tableswitch`
+    static String dayName(int day){
+        switch(day){
+            case 1: return "Monday";
+            case 2: return "Tuesday";
+            case 3: return "Wednesday";
+            case 4: return "Thursday";
+            case 5: return "Friday";
+            case 6: return "Saturday";
+            case 7: return "Sunday";
+            default: return "Unknown";
+        }
+    }
+    // Dense keys with a negative lower bound and a fallthrough
+    static int sign(int value){
+        int res = 100;
+        switch(value){
+            case -2:
+            case -1:
+                res = -1;
+                break;
+            case 0:
+                res = 0;
+                break;
+            case 1:
+            case 2:
+                res = 1;
+                break;
+        }
+        return res;
+    }
+    // Sparse keys compile to a `lookupswitch`
+    static int sparse(int value){
+        switch(value){
+            case 10: return 1;
+            case 1000: return 2;
+            case 100000: return 3;
+            default: return 0;
+        }
+    }
+    public static void main(String[] args){
+        System.out.println(dayName(1));
+        System.out.println(dayName(5));
+        System.out.println(dayName(7));
+        System.out.println(dayName(8));
+        if(sign(-2) == -1 && sign(-1) == -1 && sign(0) == 0 && sign(2) == 1 && sign(3) == 100 && sign(-3) == 100){
+            System.out.println("Negative ranges work!");
+        }
+        if(sparse(10) == 1 && sparse(1000) == 2 && sparse(100000) == 3 && sparse(11) == 0){
+            System.out.println("Lookup switches work!");
+        }
+    }
+}
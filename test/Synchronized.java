@@ -0,0 +1,73 @@
+class Counter{
+    int value;
+    synchronized void increment(){
+        value += 1;
+    }
+    // Calls another synchronized method, so the monitor must be reentrant.
+    synchronized void incrementTwice(){
+        increment();
+        increment();
+    }
+    synchronized void fail(){
+        throw new RuntimeException("failed while holding the monitor");
+    }
+    static int total;
+    static synchronized void addTotal(){
+        total += 1;
+    }
+}
+class Synchronized{
+    static boolean lockedInside(Counter counter){
+        synchronized(counter){
+            synchronized(counter){
+                if(!Thread.holdsLock(counter)) return false;
+            }
+            if(!Thread.holdsLock(counter)) return false;
+            return true;
+        }
+    }
+    static void failInBlock(Object lock){
+        synchronized(lock){
+            throw new RuntimeException("failed in block");
+        }
+    }
+    static boolean classLocked(){
+        synchronized(Counter.class){
+            if(Thread.holdsLock(Counter.class)) return true;
+            return false;
+        }
+    }
+    public static void main(String[] args){
+        Counter counter = new Counter();
+        counter.increment();
+        counter.incrementTwice();
+        if(counter.value == 3 && !Thread.holdsLock(counter)){
+            System.out.println("Synchronized methods work!");
+        }
+        if(lockedInside(counter) && !Thread.holdsLock(counter)){
+            System.out.println("Nested synchronized blocks work!");
+        }
+        try{
+            counter.fail();
+        }
+        catch(RuntimeException e){
+            System.out.println(e.getMessage());
+        }
+        if(!Thread.holdsLock(counter)){
+            System.out.println("Monitor released after exception in method!");
+        }
+        try{
+            failInBlock(counter);
+        }
+        catch(RuntimeException e){
+            System.out.println(e.getMessage());
+        }
+        if(!Thread.holdsLock(counter)){
+            System.out.println("Monitor released after exception in block!");
+        }
+        Counter.addTotal();
+        if(Counter.total == 1 && classLocked() && !Thread.holdsLock(Counter.class)){
+            System.out.println("Static synchronized methods work!");
+        }
+    }
+}
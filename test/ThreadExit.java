@@ -0,0 +1,35 @@
+class LateWorker extends Thread{
+    public void run(){
+        try{
+            Thread.sleep(100);
+        }
+        catch(InterruptedException e){
+        }
+        System.out.println("Worker finished after main!");
+    }
+}
+class Sleeper implements Runnable{
+    public void run(){
+        try{
+            Thread.sleep(60000);
+        }
+        catch(InterruptedException e){
+        }
+        System.out.println("Daemon thread was waited for!");
+    }
+}
+class ThreadExit{
+    public static void main(String[] args){
+        Thread daemon = new Thread(new Sleeper());
+        daemon.setDaemon(true);
+        daemon.start();
+        try{
+            daemon.setDaemon(false);
+        }
+        catch(IllegalThreadStateException e){
+            System.out.println("Running threads can't become daemons!");
+        }
+        new LateWorker().start();
+        System.out.println("Main finished!");
+    }
+}
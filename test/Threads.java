@@ -0,0 +1,84 @@
+class Worker extends Thread{
+    int[] results;
+    int index;
+    Worker(int[] results, int index){
+        this.results = results;
+        this.index = index;
+    }
+    public void run(){
+        int sum = 0;
+        for(int i = 0; i <= 1000; i++){
+            sum += i;
+        }
+        results[index] = sum;
+    }
+}
+class SharedCounter{
+    int value;
+    synchronized void increment(){
+        value += 1;
+    }
+}
+class Threads{
+    static boolean allDone(int[] results){
+        for(int i = 0; i < results.length; i++){
+            if(results[i] != 500500) return false;
+        }
+        return true;
+    }
+    static void incrementMany(SharedCounter counter){
+        for(int i = 0; i < 10000; i++){
+            counter.increment();
+        }
+    }
+    static void sleepLong(){
+        try{
+            Thread.sleep(10000);
+            System.out.println("Sleep was not interrupted!");
+        }
+        catch(InterruptedException e){
+            System.out.println("Sleep interrupted!");
+        }
+    }
+    public static void main(String[] args) throws InterruptedException{
+        int[] results = new int[4];
+        Worker[] workers = new Worker[4];
+        for(int i = 0; i < workers.length; i++){
+            workers[i] = new Worker(results, i);
+            workers[i].start();
+        }
+        for(int i = 0; i < workers.length; i++){
+            workers[i].join();
+        }
+        if(allDone(results)){
+            System.out.println("Thread subclasses work!");
+        }
+        SharedCounter counter = new SharedCounter();
+        Thread first = new Thread(() -> incrementMany(counter));
+        Thread second = new Thread(() -> incrementMany(counter), "second");
+        first.start();
+        second.start();
+        first.join();
+        second.join();
+        if(counter.value == 20000 && !first.isAlive()){
+            System.out.println("Runnables work!");
+        }
+        System.out.println(second.getName());
+        System.out.println(Thread.currentThread().getName());
+        Thread sleeper = new Thread(Threads::sleepLong);
+        sleeper.start();
+        Thread.sleep(50);
+        sleeper.interrupt();
+        sleeper.join();
+        Thread.currentThread().interrupt();
+        if(Thread.interrupted() && !Thread.interrupted()){
+            System.out.println("Interrupt flag works!");
+        }
+        try{
+            Thread.sleep(-1);
+        }
+        catch(IllegalArgumentException e){
+            System.out.println(e.getMessage());
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// With `--tree-shaking`, classes and methods unreachable from `main` are not translated. The list of removed ones is written to
+// `tree_shaking.txt`.
+interface Shape{
+    String describe();
+}
+class Square implements Shape{
+    public String describe(){
+        return "square";
+    }
+    // Only called by the standard library, through `StringBuilder`.
+    public String toString(){
+        return "Square";
+    }
+    // Never called, removed.
+    int area(){
+        return 4;
+    }
+}
+class Circle implements Shape{
+    // Only called virtually through `Shape`, kept since `pick` creates `Circle`s.
+    public String describe(){
+        return "circle";
+    }
+}
+// Never referenced, removed entirely.
+class Unused{
+    static{
+        System.out.println("Unused initialized!");
+    }
+    static void neverCalled(){
+        System.out.println("Never called!");
+    }
+}
+class Greeter{
+    static String greet(String name){
+        return "Hello, " + name + "!";
+    }
+}
+public class TreeShaking{
+    static Shape pick(int index){
+        if(index == 0){
+            return new Square();
+        }
+        return new Circle();
+    }
+    // Never called, removed.
+    static void helper(){
+        Unused.neverCalled();
+    }
+    public static void main(String[] args){
+        System.out.println(pick(0).describe());
+        System.out.println(pick(1).describe());
+        System.out.println("shape:" + pick(0));
+        java.util.function.Function<String, String> greeter = Greeter::greet;
+        System.out.println(greeter.apply("tree shaking"));
+    }
+}
@@ -0,0 +1,128 @@
+// Types of locals and stack values are inferred from the `StackMapTable`: values of different classes meeting at a join(`?:`, loops)
+// get their common superclass, and local slots are reused for values of unrelated types in different scopes.
+class Animal{
+    String name(){
+        return "animal";
+    }
+}
+class Dog extends Animal{
+    String name(){
+        return "dog";
+    }
+    String bark(){
+        return "woof";
+    }
+}
+class Cat extends Animal{
+    String name(){
+        return "cat";
+    }
+    String meow(){
+        return "meow";
+    }
+}
+public class TypeInference{
+    static Animal pick(boolean dog){
+        return dog ? new Dog() : new Cat();
+    }
+    static String names(int count){
+        String names = "";
+        Animal current = new Dog();
+        for(int index = 0; index < count; index++){
+            names = names + current.name() + " ";
+            current = index % 2 == 0 ? new Cat() : new Dog();
+        }
+        return names;
+    }
+    static String scopes(){
+        String result = "";
+        {
+            Dog dog = new Dog();
+            result = result + dog.bark();
+        }
+        {
+            Cat cat = new Cat();
+            result = result + " " + cat.meow();
+        }
+        {
+            int[] numbers = new int[]{1, 2, 3};
+            result = result + " " + numbers.length;
+        }
+        return result;
+    }
+    static String nullable(boolean dog){
+        Animal pet = null;
+        if(dog){
+            pet = new Dog();
+        }
+        return pet == null ? "no pet" : pet.name();
+    }
+    static String catches(int divisor, String text){
+        String result = "";
+        try{
+            result = result + (10 / divisor);
+        }
+        catch(ArithmeticException e){
+            result = result + "ArithmeticException";
+        }
+        try{
+            result = result + " " + text.length();
+        }
+        catch(NullPointerException e){
+            result = result + " NullPointerException";
+        }
+        return result;
+    }
+    static int length(boolean strings){
+        Object[] items = strings ? new String[2] : new Dog[3];
+        return items.length;
+    }
+    // Locals only ever assigned `null` have no array type to infer, but arrays can still be accessed through them.
+    static String nullArrays(){
+        String result = "";
+        int[] numbers = null;
+        try{
+            result = result + numbers[0];
+        }
+        catch(NullPointerException e){
+            result = result + "load";
+        }
+        try{
+            numbers[0] = 1;
+        }
+        catch(NullPointerException e){
+            result = result + " store";
+        }
+        String[] texts = null;
+        try{
+            result = result + texts[0].length();
+        }
+        catch(NullPointerException e){
+            result = result + " element";
+        }
+        try{
+            result = result + texts.length;
+        }
+        catch(NullPointerException e){
+            result = result + " length";
+        }
+        return result;
+    }
+    public static void main(String[] args){
+        Animal animal = args.length == 0 ? new Dog() : new Cat();
+        System.out.println("ternary: " + animal.name() + " " + pick(false).name());
+        System.out.println("loop: " + names(4));
+        System.out.println("scopes: " + scopes());
+        System.out.println("nullable: " + nullable(true) + ", " + nullable(false));
+        System.out.println("catches: " + catches(2, "text") + ", " + catches(0, null));
+        System.out.println("arrays: " + length(true) + " " + length(false));
+        System.out.println("null arrays: " + nullArrays());
+        Object value = "text";
+        if(value instanceof String){
+            String text = (String)value;
+            System.out.println("cast: " + text);
+        }
+        value = new Cat();
+        System.out.println("Types inferred!");
+    }
+}
@@ -0,0 +1,121 @@
+class BoundedBuffer{
+    int[] items = new int[4];
+    int count;
+    int head;
+    synchronized void put(int item) throws InterruptedException{
+        while(count == items.length){
+            wait();
+        }
+        int tail = head + count;
+        if(tail >= items.length){
+            tail -= items.length;
+        }
+        items[tail] = item;
+        count += 1;
+        notifyAll();
+    }
+    synchronized int take() throws InterruptedException{
+        while(count == 0){
+            wait();
+        }
+        int item = items[head];
+        head += 1;
+        if(head == items.length){
+            head = 0;
+        }
+        count -= 1;
+        notifyAll();
+        return item;
+    }
+}
+class Producer implements Runnable{
+    BoundedBuffer buffer;
+    Producer(BoundedBuffer buffer){
+        this.buffer = buffer;
+    }
+    public void run(){
+        try{
+            for(int i = 1; i <= 1000; i++){
+                buffer.put(i);
+            }
+        }
+        catch(InterruptedException e){
+            System.out.println("Producer interrupted!");
+        }
+    }
+}
+class WaitNotify{
+    static long sum;
+    static void consume(BoundedBuffer buffer) throws InterruptedException{
+        for(int i = 0; i < 1000; i++){
+            sum += buffer.take();
+        }
+    }
+    static void waitForever(Object lock){
+        synchronized(lock){
+            try{
+                lock.wait();
+                System.out.println("Wait was not interrupted!");
+            }
+            catch(InterruptedException e){
+                if(Thread.holdsLock(lock)){
+                    System.out.println("Wait interrupted!");
+                }
+            }
+        }
+    }
+    static void waitWithoutMonitor(Object lock) throws InterruptedException{
+        try{
+            lock.wait();
+        }
+        catch(IllegalMonitorStateException e){
+            System.out.println("wait without monitor throws!");
+        }
+    }
+    static void notifyWithoutMonitor(Object lock){
+        try{
+            lock.notify();
+        }
+        catch(IllegalMonitorStateException e){
+            System.out.println("notify without monitor throws!");
+        }
+    }
+    static void notifyAllWithoutMonitor(Object lock){
+        try{
+            lock.notifyAll();
+        }
+        catch(IllegalMonitorStateException e){
+            System.out.println("notifyAll without monitor throws!");
+        }
+    }
+    static void interruptWaiter(Object lock) throws InterruptedException{
+        Thread waiter = new Thread(() -> waitForever(lock));
+        waiter.start();
+        Thread.sleep(50);
+        waiter.interrupt();
+        waiter.join();
+    }
+    public static void main(String[] args) throws InterruptedException{
+        BoundedBuffer buffer = new BoundedBuffer();
+        Thread producer = new Thread(new Producer(buffer));
+        producer.start();
+        consume(buffer);
+        producer.join();
+        if(sum == 500500){
+            System.out.println("Producer and consumer work!");
+        }
+        Object lock = new Object();
+        waitWithoutMonitor(lock);
+        notifyWithoutMonitor(lock);
+        notifyAllWithoutMonitor(lock);
+        synchronized(lock){
+            synchronized(lock){
+                lock.wait(10);
+                if(Thread.holdsLock(lock)){
+                    System.out.println("Timed wait reacquires the monitor!");
+                }
+            }
+        }
+        interruptWaiter(lock);
+    }
+}
@@ -0,0 +1,8 @@
+// Only this file is passed to `jtcpp` as a source file(`-s`). `Circle`, `Shape` and `Palette` are imported from the classpath.
+class Main{
+    public static void main(String[] args){
+        Shape shape = new Circle();
+        System.out.println(shape.name());
+        System.out.println(Palette.favourite());
+    }
+}
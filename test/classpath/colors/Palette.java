@@ -0,0 +1,5 @@
+class Palette{
+    static String favourite(){
+        return "blue";
+    }
+}
@@ -0,0 +1,5 @@
+class Circle implements Shape{
+    public String name(){
+        return "circle";
+    }
+}
@@ -0,0 +1,3 @@
+interface Shape{
+    String name();
+}
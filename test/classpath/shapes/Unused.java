@@ -0,0 +1,6 @@
+// Not referenced by `Main`, so it should not be translated.
+class Unused{
+    static void unused(){
+        System.out.println("This class should not be imported!");
+    }
+}